@@ -0,0 +1,5 @@
+CREATE TABLE user_settings (
+    user_id bigint NOT NULL,
+    timezone text NOT NULL DEFAULT 'UTC',
+    CONSTRAINT pk_user_settings PRIMARY KEY (user_id)
+);
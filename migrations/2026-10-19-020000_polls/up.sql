@@ -0,0 +1,36 @@
+CREATE TABLE polls (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    author_id bigint NOT NULL,
+    location text,
+    channel_id bigint DEFAULT NULL,
+    message_id bigint DEFAULT NULL,
+    closed boolean NOT NULL DEFAULT false,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_polls PRIMARY KEY (id),
+    CONSTRAINT fk_polls_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id)
+);
+
+CREATE INDEX idx_polls_campaign_id ON polls (campaign_id);
+
+CREATE TABLE poll_options (
+    id serial NOT NULL,
+    poll_id integer NOT NULL,
+    scheduled_date timestamp with time zone NOT NULL,
+    CONSTRAINT pk_poll_options PRIMARY KEY (id),
+    CONSTRAINT fk_poll_options_polls FOREIGN KEY (poll_id) REFERENCES polls (id)
+);
+
+CREATE INDEX idx_poll_options_poll_id ON poll_options (poll_id);
+
+CREATE TABLE poll_votes (
+    id serial NOT NULL,
+    poll_option_id integer NOT NULL,
+    voter_id bigint NOT NULL,
+    voted_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_poll_votes PRIMARY KEY (id),
+    CONSTRAINT fk_poll_votes_poll_options FOREIGN KEY (poll_option_id) REFERENCES poll_options (id),
+    CONSTRAINT uk_poll_option_id_voter_id UNIQUE (poll_option_id, voter_id)
+);
+
+CREATE INDEX idx_poll_votes_poll_option_id ON poll_votes (poll_option_id);
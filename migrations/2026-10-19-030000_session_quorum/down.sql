@@ -0,0 +1,7 @@
+ALTER TABLE sessions
+    DROP COLUMN quorum_notified;
+
+ALTER TABLE campaigns
+    DROP COLUMN quorum_action,
+    DROP COLUMN quorum_deadline_hours,
+    DROP COLUMN min_players;
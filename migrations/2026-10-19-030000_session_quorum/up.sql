@@ -0,0 +1,7 @@
+ALTER TABLE campaigns
+    ADD COLUMN min_players smallint DEFAULT NULL,
+    ADD COLUMN quorum_deadline_hours integer NOT NULL DEFAULT 48,
+    ADD COLUMN quorum_action smallint NOT NULL DEFAULT 0;
+
+ALTER TABLE sessions
+    ADD COLUMN quorum_notified boolean NOT NULL DEFAULT false;
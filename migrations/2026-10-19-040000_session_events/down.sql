@@ -0,0 +1,7 @@
+DROP INDEX idx_sessions_event_id;
+
+ALTER TABLE sessions
+    DROP COLUMN event_id;
+
+ALTER TABLE campaigns
+    DROP COLUMN import_event_interest;
@@ -0,0 +1,7 @@
+ALTER TABLE campaigns
+    ADD COLUMN import_event_interest boolean NOT NULL DEFAULT false;
+
+ALTER TABLE sessions
+    ADD COLUMN event_id bigint DEFAULT NULL;
+
+CREATE INDEX idx_sessions_event_id ON sessions (event_id);
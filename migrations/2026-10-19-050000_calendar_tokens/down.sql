@@ -0,0 +1,4 @@
+DROP INDEX uk_campaigns_calendar_token;
+
+ALTER TABLE campaigns
+    DROP COLUMN calendar_token;
@@ -0,0 +1,4 @@
+ALTER TABLE campaigns
+    ADD COLUMN calendar_token text NOT NULL DEFAULT md5(random()::text || clock_timestamp()::text);
+
+CREATE UNIQUE INDEX uk_campaigns_calendar_token ON campaigns (calendar_token);
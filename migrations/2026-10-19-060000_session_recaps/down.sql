@@ -0,0 +1,3 @@
+ALTER TABLE sessions
+    DROP COLUMN dm_notes,
+    DROP COLUMN recap;
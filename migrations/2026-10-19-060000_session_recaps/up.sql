@@ -0,0 +1,3 @@
+ALTER TABLE sessions
+    ADD COLUMN recap text DEFAULT NULL,
+    ADD COLUMN dm_notes text DEFAULT NULL;
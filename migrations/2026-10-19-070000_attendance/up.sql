@@ -0,0 +1,14 @@
+CREATE TABLE attendance (
+    id serial NOT NULL,
+    session_id integer NOT NULL,
+    player_id bigint NOT NULL,
+    attended boolean NOT NULL,
+    marked_by bigint NOT NULL,
+    marked_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_attendance PRIMARY KEY (id),
+    CONSTRAINT fk_attendance_sessions FOREIGN KEY (session_id) REFERENCES sessions (id),
+    CONSTRAINT uk_attendance_session_id_player_id UNIQUE (session_id, player_id)
+);
+
+CREATE INDEX idx_attendance_session_id ON attendance (session_id);
+CREATE INDEX idx_attendance_player_id ON attendance (player_id);
@@ -0,0 +1,5 @@
+ALTER TABLE responses
+    DROP COLUMN waitlisted;
+
+ALTER TABLE sessions
+    DROP COLUMN max_players;
@@ -0,0 +1,5 @@
+ALTER TABLE sessions
+    ADD COLUMN max_players smallint DEFAULT NULL;
+
+ALTER TABLE responses
+    ADD COLUMN waitlisted boolean NOT NULL DEFAULT false;
@@ -0,0 +1,5 @@
+ALTER TABLE sessions
+    DROP COLUMN thread_id;
+
+ALTER TABLE campaigns
+    DROP COLUMN channel_id;
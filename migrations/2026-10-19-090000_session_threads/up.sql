@@ -0,0 +1,5 @@
+ALTER TABLE campaigns
+    ADD COLUMN channel_id bigint DEFAULT NULL;
+
+ALTER TABLE sessions
+    ADD COLUMN thread_id bigint DEFAULT NULL;
@@ -0,0 +1,7 @@
+DROP TABLE voice_checkins;
+
+ALTER TABLE attendance
+    DROP COLUMN late_minutes;
+
+ALTER TABLE campaigns
+    DROP COLUMN voice_channel_id;
@@ -0,0 +1,18 @@
+ALTER TABLE campaigns
+    ADD COLUMN voice_channel_id bigint DEFAULT NULL;
+
+ALTER TABLE attendance
+    ADD COLUMN late_minutes integer DEFAULT NULL;
+
+CREATE TABLE voice_checkins (
+    id serial NOT NULL,
+    session_id integer NOT NULL,
+    user_id bigint NOT NULL,
+    joined_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    left_date timestamp DEFAULT NULL,
+    CONSTRAINT pk_voice_checkins PRIMARY KEY (id),
+    CONSTRAINT fk_voice_checkins_sessions FOREIGN KEY (session_id) REFERENCES sessions (id)
+);
+
+CREATE INDEX idx_voice_checkins_session_id ON voice_checkins (session_id);
+CREATE INDEX idx_voice_checkins_user_id ON voice_checkins (user_id);
@@ -0,0 +1,15 @@
+CREATE TABLE campaign_members (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    user_id bigint NOT NULL,
+    role smallint NOT NULL DEFAULT 0,
+    status smallint NOT NULL DEFAULT 0,
+    added_by bigint NOT NULL,
+    joined_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_campaign_members PRIMARY KEY (id),
+    CONSTRAINT fk_campaign_members_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_campaign_members_campaign_id_user_id UNIQUE (campaign_id, user_id)
+);
+
+CREATE INDEX idx_campaign_members_campaign_id ON campaign_members (campaign_id);
+CREATE INDEX idx_campaign_members_user_id ON campaign_members (user_id);
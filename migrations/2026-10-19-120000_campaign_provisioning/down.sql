@@ -0,0 +1,3 @@
+ALTER TABLE campaigns
+    DROP COLUMN category_id,
+    DROP COLUMN role_id;
@@ -0,0 +1,3 @@
+ALTER TABLE campaigns
+    ADD COLUMN role_id bigint DEFAULT NULL,
+    ADD COLUMN category_id bigint DEFAULT NULL;
@@ -0,0 +1,7 @@
+DROP INDEX uk_campaigns_guild_id_name;
+
+ALTER TABLE campaigns
+    ADD CONSTRAINT uk_guild_id_name UNIQUE (guild_id, name);
+
+ALTER TABLE campaigns
+    DROP COLUMN deleted_date;
@@ -0,0 +1,9 @@
+ALTER TABLE campaigns
+    ADD COLUMN deleted_date timestamp DEFAULT NULL;
+
+UPDATE campaigns SET deleted_date = CURRENT_TIMESTAMP WHERE deleted = true;
+
+ALTER TABLE campaigns
+    DROP CONSTRAINT uk_guild_id_name;
+
+CREATE UNIQUE INDEX uk_campaigns_guild_id_name ON campaigns (guild_id, name) WHERE deleted = false;
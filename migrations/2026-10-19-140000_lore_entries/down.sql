@@ -0,0 +1 @@
+DROP TABLE lore_entries;
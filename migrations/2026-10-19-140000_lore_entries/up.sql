@@ -0,0 +1,21 @@
+CREATE TABLE lore_entries (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    title text NOT NULL,
+    content text NOT NULL,
+    tags text[] NOT NULL DEFAULT '{}',
+    dm_only boolean NOT NULL DEFAULT true,
+    author_id bigint NOT NULL,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') || setweight(to_tsvector('english', content), 'B')
+    ) STORED,
+    CONSTRAINT pk_lore_entries PRIMARY KEY (id),
+    CONSTRAINT fk_lore_entries_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_lore_entries_campaign_id_title UNIQUE (campaign_id, title)
+);
+
+CREATE INDEX idx_lore_entries_campaign_id ON lore_entries (campaign_id);
+CREATE INDEX idx_lore_entries_tags ON lore_entries USING GIN (tags);
+CREATE INDEX idx_lore_entries_search_vector ON lore_entries USING GIN (search_vector);
@@ -0,0 +1,21 @@
+CREATE TABLE npcs (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    name text NOT NULL,
+    description text,
+    location text,
+    faction text,
+    attitude smallint NOT NULL DEFAULT 2,
+    status smallint NOT NULL DEFAULT 0,
+    secrets text,
+    portrait_url text,
+    revealed text[] NOT NULL DEFAULT '{}',
+    author_id bigint NOT NULL,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_npcs PRIMARY KEY (id),
+    CONSTRAINT fk_npcs_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_npcs_campaign_id_name UNIQUE (campaign_id, name)
+);
+
+CREATE INDEX idx_npcs_campaign_id ON npcs (campaign_id);
@@ -0,0 +1,2 @@
+DROP TABLE quest_objectives;
+DROP TABLE quests;
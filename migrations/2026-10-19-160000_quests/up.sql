@@ -0,0 +1,32 @@
+CREATE TABLE quests (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    title text NOT NULL,
+    giver_npc_id integer,
+    description text,
+    reward text,
+    status smallint NOT NULL DEFAULT 0,
+    author_id bigint NOT NULL,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_quests PRIMARY KEY (id),
+    CONSTRAINT fk_quests_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT fk_quests_npcs FOREIGN KEY (giver_npc_id) REFERENCES npcs (id) ON DELETE SET NULL,
+    CONSTRAINT uk_quests_campaign_id_title UNIQUE (campaign_id, title)
+);
+
+CREATE INDEX idx_quests_campaign_id ON quests (campaign_id);
+
+CREATE TABLE quest_objectives (
+    id serial NOT NULL,
+    quest_id integer NOT NULL,
+    description text NOT NULL,
+    completed boolean NOT NULL DEFAULT false,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_date timestamp,
+    CONSTRAINT pk_quest_objectives PRIMARY KEY (id),
+    CONSTRAINT fk_quest_objectives_quests FOREIGN KEY (quest_id) REFERENCES quests (id) ON DELETE CASCADE,
+    CONSTRAINT uk_quest_objectives_quest_id_description UNIQUE (quest_id, description)
+);
+
+CREATE INDEX idx_quest_objectives_quest_id ON quest_objectives (quest_id);
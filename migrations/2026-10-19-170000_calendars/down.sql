@@ -0,0 +1,7 @@
+ALTER TABLE sessions DROP COLUMN ingame_end_day;
+ALTER TABLE sessions DROP COLUMN ingame_start_day;
+
+DROP TABLE calendar_events;
+DROP TABLE calendar_seasons;
+DROP TABLE calendar_holidays;
+DROP TABLE calendars;
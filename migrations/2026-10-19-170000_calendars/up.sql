@@ -0,0 +1,51 @@
+CREATE TABLE calendars (
+    campaign_id integer NOT NULL,
+    name text NOT NULL,
+    month_names text[] NOT NULL,
+    month_lengths integer[] NOT NULL,
+    weekdays text[] NOT NULL DEFAULT '{}',
+    current_day integer NOT NULL DEFAULT 0,
+    updated_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_calendars PRIMARY KEY (campaign_id),
+    CONSTRAINT fk_calendars_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id)
+);
+
+CREATE TABLE calendar_holidays (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    name text NOT NULL,
+    month smallint NOT NULL,
+    day smallint NOT NULL,
+    CONSTRAINT pk_calendar_holidays PRIMARY KEY (id),
+    CONSTRAINT fk_calendar_holidays_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_calendar_holidays_campaign_id_name UNIQUE (campaign_id, name)
+);
+
+CREATE TABLE calendar_seasons (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    name text NOT NULL,
+    month smallint NOT NULL,
+    day smallint NOT NULL,
+    CONSTRAINT pk_calendar_seasons PRIMARY KEY (id),
+    CONSTRAINT fk_calendar_seasons_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_calendar_seasons_campaign_id_name UNIQUE (campaign_id, name)
+);
+
+CREATE TABLE calendar_events (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    name text NOT NULL,
+    description text,
+    day integer NOT NULL,
+    author_id bigint NOT NULL,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_calendar_events PRIMARY KEY (id),
+    CONSTRAINT fk_calendar_events_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_calendar_events_campaign_id_name UNIQUE (campaign_id, name)
+);
+
+CREATE INDEX idx_calendar_events_campaign_id_day ON calendar_events (campaign_id, day);
+
+ALTER TABLE sessions ADD COLUMN ingame_start_day integer;
+ALTER TABLE sessions ADD COLUMN ingame_end_day integer;
@@ -0,0 +1,2 @@
+DROP TABLE handout_recipients;
+DROP TABLE handouts;
@@ -0,0 +1,28 @@
+CREATE TABLE handouts (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    title text NOT NULL,
+    content text,
+    file_name text,
+    file_data bytea,
+    author_id bigint NOT NULL,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_handouts PRIMARY KEY (id),
+    CONSTRAINT fk_handouts_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_handouts_campaign_id_title UNIQUE (campaign_id, title)
+);
+
+CREATE INDEX idx_handouts_campaign_id ON handouts (campaign_id);
+
+CREATE TABLE handout_recipients (
+    id serial NOT NULL,
+    handout_id integer NOT NULL,
+    user_id bigint NOT NULL,
+    revealed_by bigint NOT NULL,
+    revealed_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_handout_recipients PRIMARY KEY (id),
+    CONSTRAINT fk_handout_recipients_handouts FOREIGN KEY (handout_id) REFERENCES handouts (id) ON DELETE CASCADE,
+    CONSTRAINT uk_handout_recipients_handout_id_user_id UNIQUE (handout_id, user_id)
+);
+
+CREATE INDEX idx_handout_recipients_user_id ON handout_recipients (user_id);
@@ -0,0 +1,21 @@
+CREATE TABLE journal_entries (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    author_id bigint NOT NULL,
+    session_id integer,
+    title text NOT NULL,
+    content text NOT NULL,
+    shared boolean NOT NULL DEFAULT false,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') || setweight(to_tsvector('english', content), 'B')
+    ) STORED,
+    CONSTRAINT pk_journal_entries PRIMARY KEY (id),
+    CONSTRAINT fk_journal_entries_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT fk_journal_entries_sessions FOREIGN KEY (session_id) REFERENCES sessions (id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_journal_entries_campaign_id_author_id ON journal_entries (campaign_id, author_id);
+CREATE INDEX idx_journal_entries_session_id ON journal_entries (session_id);
+CREATE INDEX idx_journal_entries_search_vector ON journal_entries USING GIN (search_vector);
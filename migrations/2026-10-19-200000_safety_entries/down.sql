@@ -0,0 +1 @@
+DROP TABLE safety_entries;
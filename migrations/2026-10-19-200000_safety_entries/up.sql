@@ -0,0 +1,14 @@
+-- Entries are anonymous: there is deliberately no author column, and only the day is kept so
+-- submissions can't be matched to activity by their exact time
+CREATE TABLE safety_entries (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    kind smallint NOT NULL,
+    topic text NOT NULL,
+    created_date date NOT NULL DEFAULT CURRENT_DATE,
+    CONSTRAINT pk_safety_entries PRIMARY KEY (id),
+    CONSTRAINT fk_safety_entries_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_safety_entries_campaign_id_kind_topic UNIQUE (campaign_id, kind, topic)
+);
+
+CREATE INDEX idx_safety_entries_campaign_id ON safety_entries (campaign_id);
@@ -0,0 +1,2 @@
+DROP TABLE questionnaire_answers;
+DROP TABLE questionnaire_questions;
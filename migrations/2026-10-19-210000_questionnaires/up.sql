@@ -0,0 +1,28 @@
+CREATE TABLE questionnaire_questions (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    position integer NOT NULL,
+    prompt text NOT NULL,
+    placeholder text,
+    paragraph boolean NOT NULL DEFAULT false,
+    required boolean NOT NULL DEFAULT false,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_questionnaire_questions PRIMARY KEY (id),
+    CONSTRAINT fk_questionnaire_questions_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT uk_questionnaire_questions_campaign_id_prompt UNIQUE (campaign_id, prompt)
+);
+
+CREATE INDEX idx_questionnaire_questions_campaign_id ON questionnaire_questions (campaign_id);
+
+CREATE TABLE questionnaire_answers (
+    id serial NOT NULL,
+    question_id integer NOT NULL,
+    user_id bigint NOT NULL,
+    answer text NOT NULL,
+    answered_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_questionnaire_answers PRIMARY KEY (id),
+    CONSTRAINT fk_questionnaire_answers_questionnaire_questions FOREIGN KEY (question_id) REFERENCES questionnaire_questions (id) ON DELETE CASCADE,
+    CONSTRAINT uk_questionnaire_answers_question_id_user_id UNIQUE (question_id, user_id)
+);
+
+CREATE INDEX idx_questionnaire_answers_question_id ON questionnaire_answers (question_id);
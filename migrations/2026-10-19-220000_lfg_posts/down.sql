@@ -0,0 +1 @@
+DROP TABLE lfg_posts;
@@ -0,0 +1,19 @@
+CREATE TABLE lfg_posts (
+    id serial NOT NULL,
+    campaign_id integer NOT NULL,
+    session_id integer NOT NULL,
+    system text NOT NULL,
+    min_level smallint NOT NULL,
+    max_level smallint NOT NULL,
+    content_warnings text,
+    channel_id bigint,
+    message_id bigint,
+    closed boolean NOT NULL DEFAULT false,
+    created_date timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_lfg_posts PRIMARY KEY (id),
+    CONSTRAINT fk_lfg_posts_campaigns FOREIGN KEY (campaign_id) REFERENCES campaigns (id),
+    CONSTRAINT fk_lfg_posts_sessions FOREIGN KEY (session_id) REFERENCES sessions (id),
+    CONSTRAINT uk_lfg_posts_campaign_id UNIQUE (campaign_id)
+);
+
+CREATE INDEX idx_lfg_posts_session_id ON lfg_posts (session_id);
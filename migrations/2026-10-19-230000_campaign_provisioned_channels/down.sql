@@ -0,0 +1,3 @@
+ALTER TABLE campaigns
+    DROP COLUMN provisioned_channel_id,
+    DROP COLUMN provisioned_voice_channel_id;
@@ -0,0 +1,3 @@
+ALTER TABLE campaigns
+    ADD COLUMN provisioned_channel_id bigint DEFAULT NULL,
+    ADD COLUMN provisioned_voice_channel_id bigint DEFAULT NULL;
@@ -0,0 +1 @@
+DROP INDEX uk_voice_checkins_session_id_user_id_open;
@@ -0,0 +1,10 @@
+DELETE FROM voice_checkins a
+    USING voice_checkins b
+    WHERE a.session_id = b.session_id
+        AND a.user_id = b.user_id
+        AND a.left_date IS NULL
+        AND b.left_date IS NULL
+        AND a.id > b.id;
+
+CREATE UNIQUE INDEX uk_voice_checkins_session_id_user_id_open ON voice_checkins (session_id, user_id)
+    WHERE left_date IS NULL;
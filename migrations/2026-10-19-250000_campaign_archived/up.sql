@@ -0,0 +1,2 @@
+ALTER TABLE campaigns
+    ADD COLUMN archived boolean NOT NULL DEFAULT false;
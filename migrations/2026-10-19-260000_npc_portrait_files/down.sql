@@ -0,0 +1,4 @@
+ALTER TABLE npcs
+    DROP COLUMN portrait_name,
+    DROP COLUMN portrait_data,
+    ADD COLUMN portrait_url text DEFAULT NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE npcs
+    DROP COLUMN portrait_url,
+    ADD COLUMN portrait_name text DEFAULT NULL,
+    ADD COLUMN portrait_data bytea DEFAULT NULL;
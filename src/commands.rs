@@ -1,3 +1,4 @@
 pub mod dnd;
 pub mod help;
 pub mod settings;
+pub mod timezone;
@@ -1,23 +1,69 @@
 use crate::{
     models::{Campaign, NewCampaign},
-    ops::campaign_ops,
+    ops::{campaign_ops, session_ops},
     responses,
     utils::{
-        autocompletes::autocomplete_campaign,
+        autocompletes::{autocomplete_archived_campaign, autocomplete_campaign},
         checks,
+        date::get_long_date_timestamp,
+        events,
         guilds::get_guild_id,
-        id::{guild_id_to_i64, user_id_to_i64},
+        id::{channel_id_to_i64, guild_id_to_i64, user_id_to_i64},
+        provisioning, threads,
     },
     Context, Error,
 };
 use poise::serenity_prelude as serenity;
 
+pub mod attendance;
+pub mod calendar;
+pub mod export;
+pub mod handout;
+pub mod journal;
+pub mod lfg;
+pub mod lore;
+pub mod members;
+pub mod npc;
+pub mod quest;
+pub mod questionnaire;
+pub mod safety;
 pub mod session;
 
+pub const QUORUM_ACTION_CANCEL: i16 = 0;
+pub const QUORUM_ACTION_ASK_DM: i16 = 1;
+
+pub const DELETE_RETENTION_DAYS: i64 = 30;
+
+#[derive(poise::ChoiceParameter)]
+enum QuorumActionChoice {
+    #[name = "Cancel the session"]
+    Cancel,
+    #[name = "Ask the DM"]
+    AskDm,
+}
+
 /// D&D Campaigns (subcommand required)
 #[poise::command(
     slash_command,
-    subcommands("create", "edit", "delete", "list"),
+    subcommands(
+        "create",
+        "edit",
+        "archive",
+        "restore",
+        "delete",
+        "list",
+        "recaps",
+        "export::export",
+        "export::import",
+        "safety::safety",
+        "questionnaire::questionnaire",
+        "attendance::attendance",
+        "members::join",
+        "members::leave",
+        "members::invite",
+        "members::kick",
+        "members::members"
+    ),
     subcommand_required,
     check = "checks::dnd_check",
     category = "D&D"
@@ -36,6 +82,8 @@ pub async fn create(
         serenity::User,
     >,
     #[description = "The URL to attach to the campaign"] link: Option<String>,
+    #[description = "Create a role, category, text and voice channels for the campaign"]
+    provision: Option<bool>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -61,17 +109,55 @@ pub async fn create(
         created_date: chrono::Utc::now().naive_utc(),
     };
 
-    campaign_ops::create_campaign(ctx, campaign);
+    let campaign = campaign_ops::create_campaign(ctx, campaign);
+
+    if !provision.unwrap_or(false) {
+        return responses::success(ctx, "Campaign created.").await;
+    }
+
+    let bot_id = ctx.cache().current_user().id;
+
+    match provisioning::provision_campaign(ctx.http(), campaign, bot_id).await {
+        Ok(campaign) => {
+            let channel_id = campaign.channel_id;
+
+            campaign_ops::update_campaign(ctx, campaign);
 
-    responses::success(ctx, "Campaign created.").await
+            responses::success(
+                ctx,
+                &format!(
+                    "Campaign created. Head over to <#{}>.",
+                    channel_id.unwrap_or_default()
+                ),
+            )
+            .await
+        }
+        Err(e) => {
+            println!("Failed to provision campaign {}: {:?}", name, e);
+
+            responses::failure(
+                ctx,
+                "Campaign created, but its role and channels could not be set up. Make sure I can manage roles and channels.",
+            )
+            .await
+        }
+    }
 }
 
 /// Edits an existing D&D campaign (DMs only)
 #[poise::command(
     slash_command,
-    subcommands("name", "description", "dm", "link"),
-    subcommand_required,
-    check = "checks::dm_check"
+    subcommands(
+        "name",
+        "description",
+        "dm",
+        "link",
+        "quorum",
+        "events",
+        "channel",
+        "voice"
+    ),
+    subcommand_required
 )]
 pub async fn edit(_ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
@@ -106,14 +192,20 @@ pub async fn name(
         .await;
     }
 
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(old_name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
     let campaign = Campaign {
         name: new_name.clone(),
-        ..campaign_ops::get_campaign(
-            ctx,
-            guild_id,
-            campaign_ops::CampaignFilters::Name(old_name.clone()),
-        )
-        .unwrap()
+        ..campaign
     };
 
     campaign_ops::update_campaign(ctx, campaign);
@@ -143,14 +235,20 @@ pub async fn description(
             .await;
     }
 
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
     let campaign = Campaign {
         description: Some(description),
-        ..campaign_ops::get_campaign(
-            ctx,
-            guild_id,
-            campaign_ops::CampaignFilters::Name(name.clone()),
-        )
-        .unwrap()
+        ..campaign
     };
 
     campaign_ops::update_campaign(ctx, campaign);
@@ -158,7 +256,7 @@ pub async fn description(
     responses::success(ctx, &format!("Campaign {}'s description updated.", name)).await
 }
 
-/// Edits the DM of an existing D&D campaign (subcommand required) (DMs only)
+/// Hands an existing D&D campaign over to a new DM (campaign owner only)
 #[poise::command(slash_command)]
 pub async fn dm(
     ctx: Context<'_>,
@@ -176,14 +274,20 @@ pub async fn dm(
             .await;
     }
 
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_owner_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
     let campaign = Campaign {
         dm_id: user_id_to_i64(dm.id).await,
-        ..campaign_ops::get_campaign(
-            ctx,
-            guild_id,
-            campaign_ops::CampaignFilters::Name(name.clone()),
-        )
-        .unwrap()
+        ..campaign
     };
 
     campaign_ops::update_campaign(ctx, campaign);
@@ -213,14 +317,20 @@ pub async fn link(
             .await;
     }
 
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
     let campaign = Campaign {
         link: Some(link),
-        ..campaign_ops::get_campaign(
-            ctx,
-            guild_id,
-            campaign_ops::CampaignFilters::Name(name.clone()),
-        )
-        .unwrap()
+        ..campaign
     };
 
     campaign_ops::update_campaign(ctx, campaign);
@@ -228,43 +338,376 @@ pub async fn link(
     responses::success(ctx, &format!("Campaign {}'s link updated.", name)).await
 }
 
-/// Deletes an existing D&D campaign (DMs only)
-#[poise::command(slash_command, check = "checks::dm_check")]
-pub async fn delete(
+/// Edits the quorum rules of an existing D&D campaign (DMs only)
+///
+/// Pending sessions are confirmed automatically once enough players are going
+/// If quorum isn't met by the deadline, the session is cancelled or the DM is asked to decide
+#[poise::command(slash_command)]
+pub async fn quorum(
     ctx: Context<'_>,
-    #[description = "The name of the campaign to delete"]
+    #[description = "The name of the campaign to edit"]
     #[autocomplete = "autocomplete_campaign"]
     name: String,
+    #[description = "Minimum players needed to confirm a session"]
+    #[min = 1]
+    min_players: Option<i16>,
+    #[description = "Hours before the session to decide by. Defaults to 48"]
+    #[min = 0]
+    deadline_hours: Option<i32>,
+    #[description = "What to do when quorum isn't met by the deadline"] on_failure: Option<
+        QuorumActionChoice,
+    >,
+    #[description = "Turn off the quorum check"] disable: Option<bool>,
 ) -> Result<(), Error> {
-    async fn delete_campaign(ctx: Context<'_>, name: String) -> Result<(), Error> {
-        ctx.defer().await?;
+    ctx.defer().await?;
 
-        let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
 
-        if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
-            return responses::failure(
-                ctx,
-                &format!("Campaign with name {} does not exist.", name),
-            )
+    if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+            .await;
+    }
+
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let campaign = Campaign {
+        min_players: match disable {
+            Some(true) => None,
+            _ => min_players.or(campaign.min_players),
+        },
+        quorum_deadline_hours: deadline_hours.unwrap_or(campaign.quorum_deadline_hours),
+        quorum_action: match on_failure {
+            Some(QuorumActionChoice::Cancel) => QUORUM_ACTION_CANCEL,
+            Some(QuorumActionChoice::AskDm) => QUORUM_ACTION_ASK_DM,
+            None => campaign.quorum_action,
+        },
+        ..campaign
+    };
+
+    let msg = match campaign.min_players {
+        Some(min_players) => format!(
+            "Campaign {}'s quorum updated to {} players, decided {} hours before each session.",
+            name, min_players, campaign.quorum_deadline_hours
+        ),
+        None => format!("Campaign {}'s quorum disabled.", name),
+    };
+
+    campaign_ops::update_campaign(ctx, campaign);
+
+    responses::success(ctx, &msg).await
+}
+
+/// Edits how an existing D&D campaign syncs with server events (DMs only)
+///
+/// When enabled, users marking a session's event as "Interested" are recorded as going
+#[poise::command(slash_command)]
+pub async fn events(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to edit"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "Record users interested in a session's event as going"]
+    import_interested: bool,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
             .await;
+    }
+
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let campaign = Campaign {
+        import_event_interest: import_interested,
+        ..campaign
+    };
+
+    campaign_ops::update_campaign(ctx, campaign);
+
+    responses::success(ctx, &format!("Campaign {}'s event settings updated.", name)).await
+}
+
+/// Edits the channel an existing D&D campaign's session threads are created in (DMs only)
+///
+/// Leave the channel empty to stop creating session threads
+#[poise::command(slash_command)]
+pub async fn channel(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to edit"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "The channel to create session threads in"]
+    #[channel_types("Text")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+            .await;
+    }
+
+    let channel_id = match channel {
+        Some(channel) => Some(channel_id_to_i64(channel.id).await),
+        None => None,
+    };
+
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let campaign = Campaign {
+        channel_id,
+        ..campaign
+    };
+
+    campaign_ops::update_campaign(ctx, campaign);
+
+    responses::success(ctx, &format!("Campaign {}'s channel updated.", name)).await
+}
+
+/// Edits the voice channel an existing D&D campaign is played in (DMs only)
+///
+/// Players in the channel during a confirmed session are checked in and marked as attended
+/// Leave the channel empty to stop tracking voice attendance
+#[poise::command(slash_command)]
+pub async fn voice(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to edit"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "The voice channel sessions are played in"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+            .await;
+    }
+
+    let voice_channel_id = match channel {
+        Some(channel) => Some(channel_id_to_i64(channel.id).await),
+        None => None,
+    };
+
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    )
+    .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let campaign = Campaign {
+        voice_channel_id,
+        ..campaign
+    };
+
+    campaign_ops::update_campaign(ctx, campaign);
+
+    responses::success(ctx, &format!("Campaign {}'s voice channel updated.", name)).await
+}
+
+/// Archives an existing D&D campaign (campaign owner only)
+///
+/// Upcoming sessions are cancelled and provisioned channels are made read-only
+/// Archived campaigns are kept until they're restored
+#[poise::command(slash_command)]
+pub async fn archive(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to archive"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
         }
+    };
 
-        let campaign = Campaign {
-            deleted: true,
-            ..campaign_ops::get_campaign(
+    if !checks::campaign_owner_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    archive_campaign(ctx, campaign, true).await;
+
+    responses::success(
+        ctx,
+        &format!(
+            "Campaign {} archived. It can be restored with `/campaign restore`.",
+            name
+        ),
+    )
+    .await
+}
+
+/// Restores an archived or deleted D&D campaign (campaign owner only)
+#[poise::command(slash_command)]
+pub async fn restore(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to restore"]
+    #[autocomplete = "autocomplete_archived_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_archived_campaign(ctx, guild_id, &name) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
                 ctx,
-                guild_id,
-                campaign_ops::CampaignFilters::Name(name.clone()),
+                &format!("No archived or deleted campaign with name {} exists.", name),
             )
-            .unwrap()
-        };
+            .await
+        }
+    };
 
-        campaign_ops::update_campaign(ctx, campaign);
+    if !checks::campaign_owner_check(ctx, &campaign).await? {
+        return Ok(());
+    }
 
-        responses::success(ctx, &format!("Campaign {} deleted.", name)).await
+    if campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(
+            ctx,
+            &format!(
+                "Campaign with name {} already exists. Rename it before restoring this one.",
+                name
+            ),
+        )
+        .await;
     }
 
-    responses::verify_command(ctx, &delete_campaign, name).await
+    provisioning::set_campaign_locked(ctx.http(), &campaign, false).await;
+
+    campaign_ops::update_campaign(
+        ctx,
+        Campaign {
+            deleted: false,
+            deleted_date: None,
+            archived: false,
+            ..campaign
+        },
+    );
+
+    responses::success(ctx, &format!("Campaign {} restored.", name)).await
+}
+
+/// Deletes a D&D campaign and everything in it (campaign owner only)
+///
+/// It can be restored for 30 days, after which it's purged along with its provisioned channels and role
+#[poise::command(slash_command)]
+pub async fn delete(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to delete"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_owner_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    archive_campaign(ctx, campaign, false).await;
+
+    responses::success(
+        ctx,
+        &format!(
+            "Campaign {} deleted. It will be permanently removed after {} days, until then it can be restored with `/campaign restore`.",
+            name, DELETE_RETENTION_DAYS
+        ),
+    )
+    .await
+}
+
+/// Hides a campaign until it's restored
+///
+/// Unless it's archived, the retention task permanently deletes it once it expires
+async fn archive_campaign(ctx: Context<'_>, campaign: Campaign, archived: bool) {
+    cancel_upcoming_sessions(ctx, &campaign).await;
+    provisioning::set_campaign_locked(ctx.http(), &campaign, true).await;
+
+    campaign_ops::update_campaign(
+        ctx,
+        Campaign {
+            deleted: true,
+            deleted_date: (!archived).then(|| chrono::Utc::now().naive_utc()),
+            archived,
+            ..campaign
+        },
+    );
+}
+
+/// Cancels a campaign's upcoming sessions, removing their scheduled events
+async fn cancel_upcoming_sessions(ctx: Context<'_>, campaign: &Campaign) {
+    for session in session_ops::cancel_upcoming_sessions(ctx.data(), campaign.id) {
+        events::remove_session_event(ctx.http(), campaign.guild_id, &session).await;
+        threads::post_to_session_thread(ctx.http(), &session, "This session has been cancelled.")
+            .await;
+    }
 }
 
 /// Lists all D&D campaigns
@@ -280,14 +723,8 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
             embeds.push(
                 serenity::CreateEmbed::new()
                     .title(&campaign.name)
-                    .url(match campaign.link {
-                        Some(link) => link,
-                        None => String::from(""),
-                    })
-                    .description(match campaign.description {
-                        Some(description) => description,
-                        None => String::from(""),
-                    })
+                    .url(campaign.link.unwrap_or_default())
+                    .description(campaign.description.unwrap_or_default())
                     .field("DM", format!("<@{}>", campaign.dm_id), false),
             )
         });
@@ -295,3 +732,50 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
 
     responses::paginate_embeds(ctx, embeds).await
 }
+
+/// Pages through the session recaps of a D&D campaign, oldest first
+#[poise::command(slash_command)]
+pub async fn recaps(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if !campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+            .await;
+    }
+
+    let campaign_id = campaign_ops::get_id_from_name(ctx, &name, guild_id).unwrap();
+    let sessions = session_ops::get_session_recaps(ctx, campaign_id).unwrap_or_default();
+    let total = sessions.len();
+    let mut embeds: Vec<serenity::CreateEmbed> = vec![];
+
+    for (index, session) in sessions.into_iter().enumerate() {
+        let scheduled_date = match session.scheduled_date {
+            Some(scheduled_date) => {
+                get_long_date_timestamp(&scheduled_date.format("%Y-%m-%d %H:%M").to_string())?
+            }
+            None => String::from("Unscheduled"),
+        };
+
+        embeds.push(
+            serenity::CreateEmbed::new()
+                .title(format!("Previously on {}…", name))
+                .description(session.recap.unwrap_or_default())
+                .field("Played", scheduled_date, true)
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Recap {}/{} | Session ID: {}",
+                    index + 1,
+                    total,
+                    session.id
+                ))),
+        );
+    }
+
+    responses::paginate_embeds(ctx, embeds).await
+}
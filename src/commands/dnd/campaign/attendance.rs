@@ -0,0 +1,148 @@
+use crate::{
+    ops::{attendance_ops, campaign_ops, response_ops, session_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign, checks, guilds::get_guild_id, id::guild_id_to_i64,
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+use std::collections::BTreeMap;
+
+const PLAYERS_PER_PAGE: usize = 15;
+
+#[derive(Default)]
+struct PlayerStats {
+    attended: usize,
+    no_shows: usize,
+    late: usize,
+    no_responses: usize,
+}
+
+/// Shows how reliably each player attends a D&D campaign's sessions (DMs only)
+///
+/// Attendance is what the DM marked with `/session attendance` or what voice check-ins recorded, not the RSVP
+/// No-shows are players who responded yes but were marked absent
+#[poise::command(slash_command)]
+pub async fn attendance(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "Only count sessions from the last number of days. Defaults to all time"]
+    #[min = 1]
+    #[max = 36500]
+    days: Option<i64>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let since = days.map(|days| chrono::Utc::now().naive_utc() - chrono::Duration::days(days));
+    let sessions = session_ops::get_played_sessions(ctx, campaign.id, since).unwrap_or_default();
+
+    if sessions.is_empty() {
+        return responses::failure(ctx, "No sessions have been played in that window.").await;
+    }
+
+    let session_ids = sessions.iter().map(|s| s.id).collect::<Vec<i32>>();
+    let session_responses =
+        response_ops::get_responses_for_sessions(ctx, &session_ids).unwrap_or_default();
+    let session_attendance =
+        attendance_ops::get_attendance_for_sessions(ctx, &session_ids).unwrap_or_default();
+
+    let mut players: BTreeMap<i64, PlayerStats> = BTreeMap::new();
+
+    session_responses
+        .iter()
+        .map(|r| r.respondee_id)
+        .chain(session_attendance.iter().map(|a| a.player_id))
+        .filter(|player_id| *player_id != campaign.dm_id)
+        .for_each(|player_id| {
+            players.entry(player_id).or_default();
+        });
+
+    for (player_id, stats) in players.iter_mut() {
+        for session_id in &session_ids {
+            let response = session_responses
+                .iter()
+                .find(|r| r.session_id == *session_id && r.respondee_id == *player_id);
+            let marked = session_attendance
+                .iter()
+                .find(|a| a.session_id == *session_id && a.player_id == *player_id);
+            let attended = marked.map(|a| a.attended);
+
+            if marked.is_some_and(|a| a.attended && a.late_minutes.is_some()) {
+                stats.late += 1;
+            }
+
+            match (response.map(|r| r.response), attended) {
+                (_, Some(true)) => stats.attended += 1,
+                (Some(1), Some(false)) => stats.no_shows += 1,
+                _ => {}
+            }
+
+            if response.is_none() {
+                stats.no_responses += 1;
+            }
+        }
+    }
+
+    let mut players = players.into_iter().collect::<Vec<(i64, PlayerStats)>>();
+    players.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.attended));
+
+    let window = match days {
+        Some(days) => format!("last {} days", days),
+        None => String::from("all time"),
+    };
+    let pages = players.len().div_ceil(PLAYERS_PER_PAGE);
+    let mut embeds: Vec<serenity::CreateEmbed> = vec![];
+
+    for (page, chunk) in players.chunks(PLAYERS_PER_PAGE).enumerate() {
+        let lines = chunk
+            .iter()
+            .map(|(player_id, stats)| {
+                format!(
+                    "<@{}>: {}/{} attended ({}%) | {} late | {} no-shows | {} no response",
+                    player_id,
+                    stats.attended,
+                    sessions.len(),
+                    stats.attended * 100 / sessions.len(),
+                    stats.late,
+                    stats.no_shows,
+                    stats.no_responses
+                )
+            })
+            .collect::<Vec<String>>();
+
+        embeds.push(
+            serenity::CreateEmbed::new()
+                .title(format!("{} attendance ({})", campaign.name, window))
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{} | {} sessions played",
+                    page + 1,
+                    pages,
+                    sessions.len()
+                ))),
+        );
+    }
+
+    responses::paginate_embeds(ctx, embeds).await
+}
@@ -0,0 +1,812 @@
+use crate::{
+    models::{
+        Calendar, Campaign, NewCalendarEvent, NewCalendarHoliday, NewCalendarSeason, Session,
+    },
+    ops::{calendar_ops, campaign_ops, session_ops},
+    responses,
+    utils::{
+        autocompletes::{
+            autocomplete_calendar_event, autocomplete_campaign, autocomplete_holiday,
+            autocomplete_month, autocomplete_season,
+        },
+        calendar::{
+            self, format_day, format_day_long, format_span, holidays_on, parse_day, season_on,
+            CalendarPreset, InGameDate, GREGORIAN, HARPTOS,
+        },
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+        threads,
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+const MAX_ADVANCE_DAYS: i32 = 3650;
+
+#[derive(poise::ChoiceParameter)]
+enum PresetChoice {
+    #[name = "Calendar of Harptos (Forgotten Realms)"]
+    Harptos,
+    #[name = "Gregorian"]
+    Gregorian,
+}
+
+impl PresetChoice {
+    fn to_preset(&self) -> &'static CalendarPreset {
+        match self {
+            PresetChoice::Harptos => &HARPTOS,
+            PresetChoice::Gregorian => &GREGORIAN,
+        }
+    }
+}
+
+/// In-world calendars and timekeeping (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands(
+        "setup", "show", "set", "advance", "session", "holiday", "season", "event"
+    ),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn calendar(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Sets up or redefines the calendar of a D&D campaign (DMs only)
+///
+/// Start from a preset, or list months like `Hammer:30, Midwinter:1` and weekdays like `Moonday, Tuesday`
+/// Picking a preset replaces the campaign's holidays and seasons with the preset's
+#[poise::command(slash_command)]
+pub async fn setup(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "A ready-made calendar to start from"] preset: Option<PresetChoice>,
+    #[description = "The name of the calendar"] name: Option<String>,
+    #[description = "Months and their lengths, e.g. Hammer:30, Midwinter:1, Alturiak:30"]
+    months: Option<String>,
+    #[description = "Weekday names in order, e.g. First-day, Second-day"] weekdays: Option<String>,
+    #[description = "Move the current date to the first day of this year"] year: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await? {
+        Some(campaign) => campaign,
+        None => return Ok(()),
+    };
+
+    let existing = calendar_ops::get_calendar(ctx, campaign.id);
+    let preset = preset.map(|p| p.to_preset());
+
+    let (month_names, month_lengths) = match (&months, preset, &existing) {
+        (Some(months), _, _) => match calendar::parse_months(months) {
+            Ok(months) => months,
+            Err(e) => return responses::failure(ctx, &e).await,
+        },
+        (None, Some(preset), _) => preset
+            .months
+            .iter()
+            .map(|(name, length)| (name.to_string(), *length))
+            .unzip(),
+        (None, None, Some(existing)) => {
+            (existing.month_names.clone(), existing.month_lengths.clone())
+        }
+        (None, None, None) => {
+            return responses::failure(ctx, "Pick a preset or list the calendar's months.").await
+        }
+    };
+
+    let weekdays = match (&weekdays, preset, &existing) {
+        (Some(weekdays), _, _) => calendar::parse_weekdays(weekdays),
+        (None, Some(preset), _) => preset.weekdays.iter().map(|w| w.to_string()).collect(),
+        (None, None, Some(existing)) => existing.weekdays.clone(),
+        (None, None, None) => vec![],
+    };
+
+    let name = name
+        .or(preset.map(|p| p.name.to_string()))
+        .or(existing.as_ref().map(|c| c.name.clone()))
+        .unwrap_or_else(|| format!("Calendar of {}", campaign.name));
+
+    let mut new_calendar = Calendar {
+        campaign_id: campaign.id,
+        name,
+        month_names,
+        month_lengths,
+        weekdays,
+        current_day: 0,
+        updated_date: chrono::Utc::now().naive_utc(),
+    };
+
+    // Keep the current date where it was, as far as the new months allow
+    let current_year = existing
+        .as_ref()
+        .map(|c| calendar::to_date(c, c.current_day).year)
+        .unwrap_or(1);
+
+    new_calendar.current_day = match (year, &existing) {
+        (Some(year), _) => calendar::to_day(
+            &new_calendar,
+            InGameDate {
+                year,
+                month: 1,
+                day: 1,
+            },
+        ),
+        (None, Some(existing)) => calendar::to_day(
+            &new_calendar,
+            calendar::to_date(existing, existing.current_day),
+        ),
+        (None, None) => None,
+    }
+    .unwrap_or_else(|| current_year * calendar::year_length(&new_calendar));
+
+    calendar_ops::save_calendar(ctx, &new_calendar);
+
+    if let Some(preset) = preset {
+        calendar_ops::apply_preset(ctx, campaign.id, preset);
+    }
+
+    responses::success(
+        ctx,
+        &format!(
+            "{} is now using the {} with {} months. Today is {}.",
+            campaign.name,
+            new_calendar.name,
+            new_calendar.month_names.len(),
+            format_day_long(&new_calendar, new_calendar.current_day)
+        ),
+    )
+    .await
+}
+
+/// Shows the current in-game date of a D&D campaign with upcoming events
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, calendar) = match find_calendar(ctx, &campaign, false).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let today = calendar.current_day;
+    let holidays = calendar_ops::get_holidays(ctx, campaign.id);
+    let seasons = calendar_ops::get_seasons(ctx, campaign.id);
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("{} — {}", campaign.name, calendar.name))
+        .description(format!(
+            "Today is **{}**.",
+            format_day_long(&calendar, today)
+        ));
+
+    if let Some(season) = season_on(&calendar, &seasons, today) {
+        embed = embed.field("Season", &season.name, true);
+    }
+
+    let todays_holidays = holidays_on(&calendar, &holidays, today)
+        .into_iter()
+        .map(|h| h.name.clone())
+        .collect::<Vec<String>>();
+
+    if !todays_holidays.is_empty() {
+        embed = embed.field("Holiday", todays_holidays.join(", "), true);
+    }
+
+    if let Some((holiday, days)) = next_holiday(&calendar, &holidays) {
+        embed = embed.field(
+            "Next holiday",
+            format!("{} in {}", holiday, days_text(days)),
+            true,
+        );
+    }
+
+    let events = calendar_ops::get_upcoming_events(ctx, campaign.id, today)
+        .into_iter()
+        .map(|e| {
+            let when = match e.day - today {
+                0 => String::from("today"),
+                days => format!("in {}", days_text(days)),
+            };
+
+            match e.description {
+                Some(description) => format!(
+                    "**{}** {} ({})\n> {}",
+                    e.name,
+                    when,
+                    format_day(&calendar, e.day),
+                    description
+                ),
+                None => format!("**{}** {} ({})", e.name, when, format_day(&calendar, e.day)),
+            }
+        })
+        .collect::<Vec<String>>();
+
+    if !events.is_empty() {
+        embed = embed.field("Upcoming events", events.join("\n"), false);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Sets the current in-game date of a D&D campaign (DMs only)
+///
+/// Dates look like `15 Mirtul 1491`, and the year can be left out
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The new date, e.g. 15 Mirtul 1491"] date: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (_, calendar) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let day = match parse_day(&calendar, &date) {
+        Some(day) => day,
+        None => return responses::failure(ctx, "That date doesn't exist in this calendar.").await,
+    };
+
+    calendar_ops::save_calendar(
+        ctx,
+        &Calendar {
+            current_day: day,
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..calendar.clone()
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!("Today is now {}.", format_day_long(&calendar, day)),
+    )
+    .await
+}
+
+/// Moves the in-game date of a D&D campaign forward (DMs only)
+///
+/// Holidays, season changes and events passed along the way are announced in the campaign's channel
+/// Give a session ID to record the days it covered
+#[poise::command(slash_command)]
+pub async fn advance(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "How many days pass"]
+    #[min = 1]
+    #[max = 3650]
+    days: i32,
+    #[description = "The session these days were played in"] session_id: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, calendar) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let session = match session_id {
+        Some(session_id) => match find_session(ctx, &campaign, session_id).await? {
+            Some(session) => Some(session),
+            None => return Ok(()),
+        },
+        None => None,
+    };
+
+    let from = calendar.current_day;
+    let to = match from.checked_add(days.clamp(1, MAX_ADVANCE_DAYS)) {
+        Some(to) => to,
+        None => return responses::failure(ctx, "The calendar can't advance that far.").await,
+    };
+
+    calendar_ops::save_calendar(
+        ctx,
+        &Calendar {
+            current_day: to,
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..calendar.clone()
+        },
+    );
+
+    if let Some(session) = session {
+        session_ops::set_ingame_days(
+            ctx,
+            session.id,
+            session.ingame_start_day.unwrap_or(from),
+            to,
+        );
+    }
+
+    let holidays = calendar_ops::get_holidays(ctx, campaign.id);
+    let seasons = calendar_ops::get_seasons(ctx, campaign.id);
+    let mut lines = vec![format!(
+        "🗓️ {} pass in {}. Today is **{}**.",
+        days_text(to - from),
+        campaign.name,
+        format_day_long(&calendar, to)
+    )];
+
+    let old_season = season_on(&calendar, &seasons, from).map(|s| s.id);
+
+    if let Some(season) = season_on(&calendar, &seasons, to).filter(|s| Some(s.id) != old_season) {
+        lines.push(format!("{} has begun.", season.name));
+    }
+
+    for day in from + 1..=to {
+        for holiday in holidays_on(&calendar, &holidays, day) {
+            lines.push(format!(
+                "{} {} on {}.",
+                holiday.name,
+                if day == to { "is today" } else { "was" },
+                format_day(&calendar, day)
+            ));
+        }
+    }
+
+    for event in calendar_ops::get_events_between(ctx, campaign.id, from, to) {
+        lines.push(format!(
+            "**{}** {} on {}.",
+            event.name,
+            if event.day == to {
+                "happens today"
+            } else {
+                "happened"
+            },
+            format_day(&calendar, event.day)
+        ));
+    }
+
+    let msg = lines.join("\n");
+
+    threads::post_to_campaign_channel(ctx.http(), &campaign, &msg).await;
+
+    responses::success(ctx, &msg).await
+}
+
+/// Records the in-game days a session covered (DMs only)
+#[poise::command(slash_command)]
+pub async fn session(
+    ctx: Context<'_>,
+    #[description = "The ID of the session"] session_id: i32,
+    #[description = "The in-game date the session started on, e.g. 15 Mirtul 1491"] start: String,
+    #[description = "The in-game date the session ended on. Defaults to the start"] end: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let session = match session_ops::get_session(ctx, session_id) {
+        Some(session) => session,
+        None => return responses::failure(ctx, "Session not found.").await,
+    };
+
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let calendar = match calendar_ops::get_calendar(ctx, session.campaign_id) {
+        Some(calendar) => calendar,
+        None => {
+            return responses::failure(
+                ctx,
+                "This campaign has no calendar yet. Set one up with `/calendar setup`.",
+            )
+            .await
+        }
+    };
+
+    let start = parse_day(&calendar, &start);
+    let end = match &end {
+        Some(end) => parse_day(&calendar, end),
+        None => start,
+    };
+
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) if start <= end => (start, end),
+        (Some(_), Some(_)) => {
+            return responses::failure(ctx, "The session can't end before it starts.").await
+        }
+        _ => return responses::failure(ctx, "That date doesn't exist in this calendar.").await,
+    };
+
+    session_ops::set_ingame_days(ctx, session.id, start, end);
+
+    responses::success(
+        ctx,
+        &format!(
+            "Session ID {} covered {}.",
+            session_id,
+            format_span(&calendar, start, end)
+        ),
+    )
+    .await
+}
+
+/// Holidays of an in-world calendar (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("holiday_add", "holiday_remove"),
+    subcommand_required
+)]
+pub async fn holiday(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a yearly holiday to a campaign's calendar (DMs only)
+#[poise::command(slash_command, rename = "add")]
+pub async fn holiday_add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The name of the holiday"]
+    #[max_length = 100]
+    name: String,
+    #[description = "The month it falls in"]
+    #[autocomplete = "autocomplete_month"]
+    month: String,
+    #[description = "The day of the month. Defaults to the first"] day: Option<i16>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, calendar) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let (month, day) = match find_month_day(&calendar, &month, day.unwrap_or(1)) {
+        Some(result) => result,
+        None => return responses::failure(ctx, "That date doesn't exist in this calendar.").await,
+    };
+
+    let name = name.trim();
+
+    if !calendar_ops::add_holiday(
+        ctx,
+        NewCalendarHoliday {
+            campaign_id: campaign.id,
+            name,
+            month,
+            day,
+        },
+    ) {
+        return responses::failure(ctx, &format!("Holiday {} already exists.", name)).await;
+    }
+
+    responses::success(ctx, &format!("Holiday {} added.", name)).await
+}
+
+/// Removes a holiday from a campaign's calendar (DMs only)
+#[poise::command(slash_command, rename = "remove")]
+pub async fn holiday_remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The holiday to remove"]
+    #[autocomplete = "autocomplete_holiday"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, _) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    if !calendar_ops::delete_holiday(ctx, campaign.id, &name) {
+        return responses::failure(ctx, &format!("Holiday {} does not exist.", name)).await;
+    }
+
+    responses::success(ctx, &format!("Holiday {} removed.", name)).await
+}
+
+/// Seasons of an in-world calendar (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("season_add", "season_remove"),
+    subcommand_required
+)]
+pub async fn season(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a season to a campaign's calendar, lasting until the next one starts (DMs only)
+#[poise::command(slash_command, rename = "add")]
+pub async fn season_add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The name of the season"]
+    #[max_length = 100]
+    name: String,
+    #[description = "The month it starts in"]
+    #[autocomplete = "autocomplete_month"]
+    month: String,
+    #[description = "The day of the month it starts on. Defaults to the first"] day: Option<i16>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, calendar) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let (month, day) = match find_month_day(&calendar, &month, day.unwrap_or(1)) {
+        Some(result) => result,
+        None => return responses::failure(ctx, "That date doesn't exist in this calendar.").await,
+    };
+
+    let name = name.trim();
+
+    if !calendar_ops::add_season(
+        ctx,
+        NewCalendarSeason {
+            campaign_id: campaign.id,
+            name,
+            month,
+            day,
+        },
+    ) {
+        return responses::failure(ctx, &format!("Season {} already exists.", name)).await;
+    }
+
+    responses::success(ctx, &format!("Season {} added.", name)).await
+}
+
+/// Removes a season from a campaign's calendar (DMs only)
+#[poise::command(slash_command, rename = "remove")]
+pub async fn season_remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The season to remove"]
+    #[autocomplete = "autocomplete_season"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, _) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    if !calendar_ops::delete_season(ctx, campaign.id, &name) {
+        return responses::failure(ctx, &format!("Season {} does not exist.", name)).await;
+    }
+
+    responses::success(ctx, &format!("Season {} removed.", name)).await
+}
+
+/// In-world events on a campaign's calendar (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("event_add", "event_remove"),
+    subcommand_required
+)]
+pub async fn event(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Schedules an in-world event, like a festival in 10 days (DMs only)
+#[poise::command(slash_command, rename = "add")]
+pub async fn event_add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The name of the event"]
+    #[max_length = 100]
+    name: String,
+    #[description = "How many days from today it happens"]
+    #[min = 0]
+    in_days: Option<i32>,
+    #[description = "The date it happens on instead, e.g. 15 Mirtul 1491"] date: Option<String>,
+    #[description = "What happens"] description: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, calendar) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let day = match (in_days, &date) {
+        (Some(in_days), None) => calendar.current_day + in_days.max(0),
+        (None, Some(date)) => match parse_day(&calendar, date) {
+            Some(day) => day,
+            None => {
+                return responses::failure(ctx, "That date doesn't exist in this calendar.").await
+            }
+        },
+        _ => return responses::failure(ctx, "Give either a number of days or a date.").await,
+    };
+
+    let name = name.trim();
+
+    if !calendar_ops::add_event(
+        ctx,
+        NewCalendarEvent {
+            campaign_id: campaign.id,
+            name,
+            description: description.as_deref(),
+            day,
+            author_id: user_id_to_i64(ctx.author().id).await,
+            created_date: chrono::Utc::now().naive_utc(),
+        },
+    ) {
+        return responses::failure(ctx, &format!("Event {} already exists.", name)).await;
+    }
+
+    responses::success(
+        ctx,
+        &format!(
+            "Event {} scheduled for {}.",
+            name,
+            format_day_long(&calendar, day)
+        ),
+    )
+    .await
+}
+
+/// Removes an in-world event from a campaign's calendar (DMs only)
+#[poise::command(slash_command, rename = "remove")]
+pub async fn event_remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The event to remove"]
+    #[autocomplete = "autocomplete_calendar_event"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, _) = match find_calendar(ctx, &campaign, true).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    if !calendar_ops::delete_event(ctx, campaign.id, &name) {
+        return responses::failure(ctx, &format!("Event {} does not exist.", name)).await;
+    }
+
+    responses::success(ctx, &format!("Event {} removed.", name)).await
+}
+
+/// Finds the next holiday after today, with how many days away it is
+fn next_holiday(
+    calendar: &Calendar,
+    holidays: &[crate::models::CalendarHoliday],
+) -> Option<(String, i32)> {
+    let today = calendar.current_day;
+
+    (1..=calendar::year_length(calendar)).find_map(|days| {
+        holidays_on(calendar, holidays, today + days)
+            .first()
+            .map(|h| (h.name.clone(), days))
+    })
+}
+
+fn find_month_day(calendar: &Calendar, month: &str, day: i16) -> Option<(i16, i16)> {
+    let month = calendar::find_month(calendar, month)?;
+
+    calendar::to_day(
+        calendar,
+        InGameDate {
+            year: 0,
+            month,
+            day,
+        },
+    )
+    .map(|_| (month, day))
+}
+
+fn days_text(days: i32) -> String {
+    if days == 1 {
+        String::from("1 day")
+    } else {
+        format!("{} days", days)
+    }
+}
+
+/// Finds a campaign, replying with why if it can't, and checking the author manages it
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Result<Option<Campaign>, Error> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(None);
+    }
+
+    Ok(Some(campaign))
+}
+
+/// Finds a campaign and its calendar, replying with why if it can't
+///
+/// When `manage` is set, the author must also manage the campaign
+async fn find_calendar(
+    ctx: Context<'_>,
+    name: &str,
+    manage: bool,
+) -> Result<Option<(Campaign, Calendar)>, Error> {
+    let campaign = if manage {
+        match find_campaign(ctx, name).await? {
+            Some(campaign) => campaign,
+            None => return Ok(None),
+        }
+    } else {
+        let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+        match campaign_ops::get_campaign(
+            ctx,
+            guild_id,
+            campaign_ops::CampaignFilters::Name(name.to_string()),
+        ) {
+            Some(campaign) => campaign,
+            None => {
+                responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                    .await?;
+                return Ok(None);
+            }
+        }
+    };
+
+    match calendar_ops::get_calendar(ctx, campaign.id) {
+        Some(calendar) => Ok(Some((campaign, calendar))),
+        None => {
+            responses::failure(
+                ctx,
+                "This campaign has no calendar yet. Set one up with `/calendar setup`.",
+            )
+            .await?;
+            Ok(None)
+        }
+    }
+}
+
+async fn find_session(
+    ctx: Context<'_>,
+    campaign: &Campaign,
+    session_id: i32,
+) -> Result<Option<Session>, Error> {
+    match session_ops::get_session(ctx, session_id) {
+        Some(session) if session.campaign_id == campaign.id => Ok(Some(session)),
+        _ => {
+            responses::failure(ctx, "Session not found in this campaign.").await?;
+            Ok(None)
+        }
+    }
+}
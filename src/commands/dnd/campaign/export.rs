@@ -0,0 +1,158 @@
+use crate::{
+    ops::{campaign_ops, export_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign,
+        checks,
+        export::{from_json, to_json},
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, i64_to_user_id, user_id_to_i64},
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+use std::collections::BTreeSet;
+
+const MAX_IMPORT_SIZE: u32 = 8 * 1024 * 1024;
+
+/// Exports a D&D campaign with its sessions, characters and polls as a JSON file (DMs only)
+#[poise::command(slash_command)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to export"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    // The export holds DM notes and secrets, so only the DM may see it
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let json = match export_ops::export_campaign(ctx, &campaign).map(|export| to_json(&export)) {
+        Some(Ok(json)) => json,
+        _ => return responses::failure(ctx, "Failed to export the campaign.").await,
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("Here's the export of {}.", campaign.name))
+            .attachment(serenity::CreateAttachment::bytes(
+                json.into_bytes(),
+                format!("{}.json", campaign.name),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Imports a D&D campaign from a JSON export (DMs only)
+///
+/// Users who aren't in this server are left off the roster, and you take their place as DM or author
+#[poise::command(slash_command, check = "checks::dm_check")]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "The exported campaign file"] file: serenity::Attachment,
+    #[description = "The name to give the campaign. Defaults to the exported name"] name: Option<
+        String,
+    >,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if file.size > MAX_IMPORT_SIZE {
+        return responses::failure(ctx, "That file is too large to be a campaign export.").await;
+    }
+
+    let bytes = match file.download().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            println!("Failed to download campaign import {}: {:?}", file.url, e);
+            return responses::failure(ctx, "Failed to download the file.").await;
+        }
+    };
+
+    let export = match from_json(&bytes) {
+        Ok(export) => export,
+        Err(e) => return responses::failure(ctx, &e).await,
+    };
+
+    let guild_id = get_guild_id(ctx).await;
+    let guild_id_i64 = guild_id_to_i64(guild_id).await;
+    let name = name.unwrap_or_else(|| export.campaign.name.clone());
+
+    if campaign_ops::does_campaign_exist(ctx, &name, guild_id_i64) {
+        return responses::failure(ctx, &format!("Campaign with name {} already exists.", name))
+            .await;
+    }
+
+    let mut present_user_ids = BTreeSet::new();
+    let mut missing_user_ids = BTreeSet::new();
+
+    for user_id in export.user_ids() {
+        if guild_id
+            .member(ctx, i64_to_user_id(user_id).await)
+            .await
+            .is_ok()
+        {
+            present_user_ids.insert(user_id);
+        } else {
+            missing_user_ids.insert(user_id);
+        }
+    }
+
+    let campaign = match export_ops::import_campaign(
+        ctx.data(),
+        guild_id_i64,
+        &name,
+        &export,
+        &present_user_ids,
+        user_id_to_i64(ctx.author().id).await,
+    ) {
+        Ok(campaign) => campaign,
+        Err(e) => {
+            println!("Failed to import campaign {}: {:?}", name, e);
+            return responses::failure(
+                ctx,
+                "The campaign could not be imported. The file may be from an incompatible version, or a campaign with that name was just created.",
+            )
+            .await;
+        }
+    };
+
+    let mut msg = format!(
+        "Campaign {} imported with {} sessions and {} characters.",
+        campaign.name,
+        export.sessions.len(),
+        export.characters.len()
+    );
+
+    if !missing_user_ids.is_empty() {
+        msg.push_str(&format!(
+            "\nThese users aren't in this server, so they were left off the roster: {}",
+            missing_user_ids
+                .iter()
+                .map(|id| format!("<@{}>", id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+
+    responses::success(ctx, &msg).await
+}
@@ -0,0 +1,512 @@
+use super::members::{MEMBER_ROLE_PLAYER, MEMBER_ROLE_SPECTATOR};
+use crate::{
+    models::{Campaign, Handout, NewHandout},
+    ops::{campaign_ops, handout_ops, member_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_handout},
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, i64_to_channel_id, user_id_to_i64},
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+const MAX_HANDOUT_SIZE: u32 = 8 * 1024 * 1024;
+const LIST_PAGE_SIZE: usize = 10;
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+
+/// D&D campaign handouts revealed to players (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("upload", "reveal", "list", "remove", "mine"),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn handout(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Stores a handout for a D&D campaign without showing it to anyone yet (DMs only)
+///
+/// A handout can be a file, like a letter or a map, some text, or both
+#[poise::command(slash_command)]
+pub async fn upload(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The title of the handout"]
+    #[max_length = 100]
+    title: String,
+    #[description = "The file to hand out"] file: Option<serenity::Attachment>,
+    #[description = "The text of the handout"] text: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let title = title.trim();
+
+    if handout_ops::does_handout_exist(ctx, campaign.id, title) {
+        return responses::failure(
+            ctx,
+            &format!("Handout {} already exists in {}.", title, campaign.name),
+        )
+        .await;
+    }
+
+    if file.is_none() && text.is_none() {
+        return responses::failure(ctx, "A handout needs a file, some text, or both.").await;
+    }
+
+    let file_data = match &file {
+        Some(file) if file.size > MAX_HANDOUT_SIZE => {
+            return responses::failure(ctx, "That file is too large, the limit is 8 MB.").await
+        }
+        Some(file) => match file.download().await {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                println!("Failed to download handout {}: {:?}", file.url, e);
+                return responses::failure(ctx, "Failed to download the file.").await;
+            }
+        },
+        None => None,
+    };
+
+    handout_ops::create_handout(
+        ctx,
+        NewHandout {
+            campaign_id: campaign.id,
+            title,
+            content: text.as_deref(),
+            file_name: file.as_ref().map(|f| f.filename.as_str()),
+            file_data: file_data.as_deref(),
+            author_id: user_id_to_i64(ctx.author().id).await,
+            created_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!(
+            "Handout {} stored. Show it to the players with `/handout reveal`.",
+            title
+        ),
+    )
+    .await
+}
+
+/// Reveals a handout in the campaign's channel, or privately to some players (DMs only)
+///
+/// Named players get the handout by direct message
+/// Revealing in the channel adds the handout to the log of every player on the roster
+#[poise::command(slash_command)]
+pub async fn reveal(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The handout to reveal"]
+    #[autocomplete = "autocomplete_handout"]
+    title: String,
+    #[description = "Only send it to these players, e.g. @Ana @Bo"] players: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let handout = match handout_ops::get_handout(ctx, campaign.id, &title) {
+        Some(handout) => handout,
+        None => {
+            return responses::failure(ctx, &format!("Handout {} does not exist.", title)).await
+        }
+    };
+
+    let revealed_by = user_id_to_i64(ctx.author().id).await;
+
+    let players = match players {
+        Some(players) => players,
+        None => {
+            let channel_id = match campaign.channel_id {
+                Some(channel_id) => i64_to_channel_id(channel_id).await,
+                None => {
+                    return responses::failure(
+                        ctx,
+                        &format!(
+                            "{} has no channel to reveal handouts in. Name some players instead.",
+                            campaign.name
+                        ),
+                    )
+                    .await
+                }
+            };
+
+            if let Err(e) = channel_id
+                .send_message(ctx, handout_message(&campaign, &handout))
+                .await
+            {
+                println!("Failed to reveal handout {}: {:?}", handout.id, e);
+                return responses::failure(ctx, "Failed to post the handout.").await;
+            }
+
+            let member_ids = member_ops::get_member_ids(
+                ctx.data(),
+                campaign.id,
+                &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_SPECTATOR],
+            );
+
+            handout_ops::add_recipients(ctx, handout.id, &member_ids, revealed_by);
+
+            return responses::success(
+                ctx,
+                &format!("Handout {} revealed in <#{}>.", handout.title, channel_id),
+            )
+            .await;
+        }
+    };
+
+    let user_ids = players
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(serenity::utils::parse_user_mention)
+        .collect::<Vec<serenity::UserId>>();
+
+    if user_ids.is_empty() {
+        return responses::failure(ctx, "Mention the players to send the handout to.").await;
+    }
+
+    let mut delivered = vec![];
+    let mut failed = vec![];
+
+    for user_id in user_ids {
+        match user_id
+            .direct_message(ctx, handout_message(&campaign, &handout))
+            .await
+        {
+            Ok(_) => delivered.push(user_id_to_i64(user_id).await),
+            Err(e) => {
+                println!(
+                    "Failed to send handout {} to {}: {:?}",
+                    handout.id, user_id, e
+                );
+                failed.push(user_id);
+            }
+        }
+    }
+
+    handout_ops::add_recipients(ctx, handout.id, &delivered, revealed_by);
+
+    let mut msg = format!(
+        "Handout {} sent to {}.",
+        handout.title,
+        mentions(delivered.iter())
+    );
+
+    if delivered.is_empty() {
+        msg = format!("Handout {} wasn't sent to anyone.", handout.title);
+    }
+
+    if !failed.is_empty() {
+        msg.push_str(&format!(
+            "\nThese players don't accept direct messages: {}",
+            mentions(failed.iter())
+        ));
+    }
+
+    responses::success(ctx, &msg).await
+}
+
+/// Lists the handouts of a D&D campaign and who has received them (DMs only)
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let handouts = handout_ops::get_handout_summaries(ctx, campaign.id);
+    let handout_ids = handouts.iter().map(|(id, _, _)| *id).collect::<Vec<i32>>();
+    let recipients = handout_ops::get_recipients(ctx, &handout_ids);
+    let pages = handouts.len().div_ceil(LIST_PAGE_SIZE);
+
+    let embeds = handouts
+        .chunks(LIST_PAGE_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(|(handout_id, title, file_name)| {
+                    let mut line = format!("**{}**", title);
+
+                    if let Some(file_name) = file_name {
+                        line.push_str(&format!(" 📎 {}", file_name));
+                    }
+
+                    let received_by = recipients
+                        .iter()
+                        .filter(|r| r.handout_id == *handout_id)
+                        .map(|r| r.user_id)
+                        .collect::<Vec<i64>>();
+
+                    if received_by.is_empty() {
+                        line.push_str("\n> Not revealed yet");
+                    } else {
+                        line.push_str(&format!("\n> {}", mentions(received_by.iter())));
+                    }
+
+                    line
+                })
+                .collect::<Vec<String>>();
+
+            serenity::CreateEmbed::new()
+                .title(format!("{} handouts", campaign.name))
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{}",
+                    index + 1,
+                    pages
+                )))
+        })
+        .collect::<Vec<serenity::CreateEmbed>>();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+/// Deletes a handout from a D&D campaign (DMs only)
+///
+/// Players who already received it keep their direct messages, but it leaves their log
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The handout to delete"]
+    #[autocomplete = "autocomplete_handout"]
+    title: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let handout = match handout_ops::get_handout(ctx, campaign.id, &title) {
+        Some(handout) => handout,
+        None => {
+            return responses::failure(ctx, &format!("Handout {} does not exist.", title)).await
+        }
+    };
+
+    handout_ops::delete_handout(ctx, handout.id);
+
+    responses::success(ctx, &format!("Handout {} deleted.", handout.title)).await
+}
+
+/// Reviews the handouts you have received in a D&D campaign
+///
+/// Leave out the title to list them all
+#[poise::command(slash_command)]
+pub async fn mine(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The handout to show again"]
+    #[autocomplete = "autocomplete_handout"]
+    title: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    let title = match title {
+        Some(title) => title,
+        None => {
+            let received = handout_ops::get_received_handouts(ctx, campaign.id, user_id);
+            let pages = received.len().div_ceil(LIST_PAGE_SIZE);
+
+            let embeds = received
+                .chunks(LIST_PAGE_SIZE)
+                .enumerate()
+                .map(|(index, chunk)| {
+                    let lines = chunk
+                        .iter()
+                        .map(|(title, revealed_date)| {
+                            format!("**{}** — {}", title, revealed_date.format("%Y-%m-%d"))
+                        })
+                        .collect::<Vec<String>>();
+
+                    serenity::CreateEmbed::new()
+                        .title(format!("Your {} handouts", campaign.name))
+                        .description(lines.join("\n"))
+                        .footer(serenity::CreateEmbedFooter::new(format!(
+                            "Page {}/{}",
+                            index + 1,
+                            pages
+                        )))
+                })
+                .collect::<Vec<serenity::CreateEmbed>>();
+
+            return responses::paginate_embeds(ctx, embeds).await;
+        }
+    };
+
+    let handout = match handout_ops::get_handout(ctx, campaign.id, &title)
+        .filter(|h| handout_ops::has_received(ctx, h.id, user_id))
+    {
+        Some(handout) => handout,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("You haven't received a handout called {}.", title),
+            )
+            .await
+        }
+    };
+
+    let mut reply = poise::CreateReply::default()
+        .embed(handout_embed(&campaign, &handout))
+        .ephemeral(true);
+
+    if let Some(attachment) = handout_attachment(&handout) {
+        reply = reply.attachment(attachment);
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+fn handout_message(campaign: &Campaign, handout: &Handout) -> serenity::CreateMessage {
+    let message = serenity::CreateMessage::new().embed(handout_embed(campaign, handout));
+
+    match handout_attachment(handout) {
+        Some(attachment) => message.add_file(attachment),
+        None => message,
+    }
+}
+
+/// Builds the embed of a handout, showing images inline
+fn handout_embed(campaign: &Campaign, handout: &Handout) -> serenity::CreateEmbed {
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("📜 {}", handout.title))
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Handout from {}",
+            campaign.name
+        )));
+
+    if let Some(content) = &handout.content {
+        embed = embed.description(content);
+    }
+
+    if let Some(file_name) = handout
+        .file_name
+        .as_ref()
+        .filter(|_| handout.file_data.is_some())
+    {
+        let is_image = file_name.rsplit_once('.').is_some_and(|(_, extension)| {
+            IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+        });
+
+        if is_image {
+            embed = embed.image(format!("attachment://{}", file_name));
+        }
+    }
+
+    embed
+}
+
+fn handout_attachment(handout: &Handout) -> Option<serenity::CreateAttachment> {
+    handout
+        .file_name
+        .as_ref()
+        .zip(handout.file_data.as_ref())
+        .map(|(file_name, file_data)| {
+            serenity::CreateAttachment::bytes(file_data.clone(), file_name.clone())
+        })
+}
+
+fn mentions<T: std::fmt::Display>(user_ids: impl Iterator<Item = T>) -> String {
+    user_ids
+        .map(|id| format!("<@{}>", id))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
@@ -0,0 +1,500 @@
+use crate::{
+    models::{Campaign, JournalEntry, NewJournalEntry},
+    ops::{campaign_ops, journal_ops, session_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign,
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+    },
+    ApplicationContext, Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+const LIST_PAGE_SIZE: usize = 15;
+const EXCERPT_LENGTH: usize = 80;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Journal Entry"]
+struct JournalModal {
+    #[name = "Title"]
+    #[max_length = 100]
+    title: String,
+    #[name = "Notes (markdown)"]
+    #[paragraph]
+    #[max_length = 4000]
+    content: String,
+}
+
+/// Personal D&D campaign journals (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands(
+        "add", "show", "list", "search", "share", "delete", "export", "session"
+    ),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn journal(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Writes a journal entry for a D&D campaign
+///
+/// Entries are private unless shared with the party
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: ApplicationContext<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The session the entry is about"] session_id: Option<i32>,
+    #[description = "Share the entry with the party. Defaults to false"] shared: Option<bool>,
+) -> Result<(), Error> {
+    use poise::Modal as _;
+
+    let campaign = match find_campaign(poise::Context::Application(ctx), &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::is_campaign_member(ctx.data(), &campaign, user_id_to_i64(ctx.author().id).await) {
+        return responses::failure(
+            poise::Context::Application(ctx),
+            "Only players in this campaign can keep a journal for it.",
+        )
+        .await;
+    }
+
+    if let Some(session_id) = session_id {
+        if session_ops::get_session(poise::Context::Application(ctx), session_id)
+            .is_none_or(|session| session.campaign_id != campaign.id)
+        {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                "Session not found in this campaign.",
+            )
+            .await;
+        }
+    }
+
+    let data = match JournalModal::execute(ctx).await? {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+
+    let now = chrono::Utc::now().naive_utc();
+
+    let entry = journal_ops::create_entry(
+        poise::Context::Application(ctx),
+        NewJournalEntry {
+            campaign_id: campaign.id,
+            author_id: user_id_to_i64(ctx.author().id).await,
+            session_id,
+            title: data.title.trim(),
+            content: &data.content,
+            shared: shared.unwrap_or(false),
+            created_date: now,
+            updated_date: now,
+        },
+    );
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Journal entry #{} {} saved{}.",
+                entry.id,
+                entry.title,
+                if entry.shared {
+                    " and shared with the party"
+                } else {
+                    ""
+                }
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Shows a journal entry you wrote or that was shared with the party
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The ID of the entry"] entry_id: i32,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let entry = match find_readable_entry(ctx, entry_id).await {
+        Some(entry) => entry,
+        None => return responses::failure(ctx, "Journal entry not found.").await,
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(entry_embed(&entry))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lists your journal entries for a D&D campaign
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Include entries the party shared. Defaults to false"] include_shared: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    let entries = journal_ops::get_entries(
+        ctx,
+        campaign.id,
+        user_id,
+        include_shared.unwrap_or(false)
+            && checks::is_campaign_member(ctx.data(), &campaign, user_id),
+    );
+
+    responses::paginate_embeds(
+        ctx,
+        entry_list_embeds(&format!("{} journal", campaign.name), &entries),
+    )
+    .await
+}
+
+/// Searches your journal and the party's shared entries for a D&D campaign
+///
+/// Supports quoted phrases, `or` and `-word`
+#[poise::command(slash_command)]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "What to search for"] query: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    let entries = journal_ops::search_entries(
+        ctx,
+        campaign.id,
+        user_id,
+        checks::is_campaign_member(ctx.data(), &campaign, user_id),
+        &query,
+    );
+
+    responses::paginate_embeds(
+        ctx,
+        entry_list_embeds(&format!("Journal results for \"{}\"", query), &entries),
+    )
+    .await
+}
+
+/// Shares one of your journal entries with the party, or makes it private again
+#[poise::command(slash_command)]
+pub async fn share(
+    ctx: Context<'_>,
+    #[description = "The ID of the entry"] entry_id: i32,
+    #[description = "Whether the party can read it"] shared: bool,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let entry = match find_own_entry(ctx, entry_id).await {
+        Some(entry) => entry,
+        None => return responses::failure(ctx, "Journal entry not found.").await,
+    };
+
+    journal_ops::update_entry(
+        ctx,
+        JournalEntry {
+            shared,
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..entry
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!(
+            "Journal entry #{} is now {}.",
+            entry_id,
+            if shared {
+                "shared with the party"
+            } else {
+                "private"
+            }
+        ),
+    )
+    .await
+}
+
+/// Deletes one of your journal entries
+#[poise::command(slash_command)]
+pub async fn delete(
+    ctx: Context<'_>,
+    #[description = "The ID of the entry"] entry_id: i32,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let entry = match find_own_entry(ctx, entry_id).await {
+        Some(entry) => entry,
+        None => return responses::failure(ctx, "Journal entry not found.").await,
+    };
+
+    journal_ops::delete_entry(ctx, entry.id);
+
+    responses::success(ctx, &format!("Journal entry #{} deleted.", entry.id)).await
+}
+
+/// Exports your journal for a D&D campaign as a markdown file
+#[poise::command(slash_command)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let entries = journal_ops::get_entries(
+        ctx,
+        campaign.id,
+        user_id_to_i64(ctx.author().id).await,
+        false,
+    );
+
+    if entries.is_empty() {
+        return responses::failure(ctx, "No results found.").await;
+    }
+
+    let mut markdown = format!("# {} journal\n", campaign.name);
+
+    for entry in &entries {
+        markdown.push_str(&format!(
+            "\n## {}\n\n*{}*\n\n{}\n",
+            entry.title,
+            entry_details(entry),
+            entry.content.trim_end()
+        ));
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Here's your {} journal with {} entries.",
+                campaign.name,
+                entries.len()
+            ))
+            .attachment(serenity::CreateAttachment::bytes(
+                markdown.into_bytes(),
+                format!("{} journal.md", campaign.name),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Shows everything you and the party wrote about a session
+#[poise::command(slash_command)]
+pub async fn session(
+    ctx: Context<'_>,
+    #[description = "The ID of the session"] session_id: i32,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match session_ops::get_session(ctx, session_id).and_then(|session| {
+        campaign_ops::get_campaign(
+            ctx,
+            guild_id,
+            campaign_ops::CampaignFilters::Id(session.campaign_id),
+        )
+    }) {
+        Some(campaign) => campaign,
+        None => return responses::failure(ctx, "Session not found.").await,
+    };
+
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    let entries = journal_ops::get_session_entries(
+        ctx,
+        session_id,
+        user_id,
+        checks::is_campaign_member(ctx.data(), &campaign, user_id),
+    );
+
+    let embeds = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            entry_embed(entry).footer(serenity::CreateEmbedFooter::new(format!(
+                "Entry {}/{} about session ID {}",
+                index + 1,
+                entries.len(),
+                session_id
+            )))
+        })
+        .collect::<Vec<serenity::CreateEmbed>>();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+fn entry_embed(entry: &JournalEntry) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new()
+        .title(format!("#{} {}", entry.id, entry.title))
+        .description(format!(
+            "<@{}> · {}\n\n{}",
+            entry.author_id,
+            entry_details(entry),
+            entry.content
+        ))
+}
+
+fn entry_list_embeds(title: &str, entries: &[JournalEntry]) -> Vec<serenity::CreateEmbed> {
+    let pages = entries.len().div_ceil(LIST_PAGE_SIZE);
+
+    entries
+        .chunks(LIST_PAGE_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(|entry| {
+                    let mut excerpt = entry
+                        .content
+                        .chars()
+                        .take(EXCERPT_LENGTH)
+                        .collect::<String>();
+
+                    if excerpt.len() < entry.content.len() {
+                        excerpt.push('…');
+                    }
+
+                    format!(
+                        "**#{} {}** — <@{}> · {}\n> {}",
+                        entry.id,
+                        entry.title,
+                        entry.author_id,
+                        entry_details(entry),
+                        excerpt.replace('\n', " ")
+                    )
+                })
+                .collect::<Vec<String>>();
+
+            serenity::CreateEmbed::new()
+                .title(title)
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{}",
+                    index + 1,
+                    pages
+                )))
+        })
+        .collect()
+}
+
+/// Describes when an entry was written, which session it's about and whether it's shared
+fn entry_details(entry: &JournalEntry) -> String {
+    let mut details = entry.created_date.format("%Y-%m-%d").to_string();
+
+    if let Some(session_id) = entry.session_id {
+        details.push_str(&format!(" · session ID {}", session_id));
+    }
+
+    if entry.shared {
+        details.push_str(" · shared");
+    }
+
+    details
+}
+
+/// Finds an entry in this server that the author wrote or that was shared with their party
+async fn find_readable_entry(ctx: Context<'_>, entry_id: i32) -> Option<JournalEntry> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    let entry = journal_ops::get_entry(ctx, entry_id)?;
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Id(entry.campaign_id),
+    )?;
+
+    let readable = entry.author_id == user_id
+        || (entry.shared && checks::is_campaign_member(ctx.data(), &campaign, user_id));
+
+    readable.then_some(entry)
+}
+
+async fn find_own_entry(ctx: Context<'_>, entry_id: i32) -> Option<JournalEntry> {
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    find_readable_entry(ctx, entry_id)
+        .await
+        .filter(|entry| entry.author_id == user_id)
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
@@ -0,0 +1,488 @@
+use super::session::{STATUS_CANCELLED, STATUS_COMPLETED, STATUS_PENDING};
+use crate::{
+    models::{
+        Campaign, LfgPost, NewCampaign, NewLfgPost, NewResponse, NewSession, Response, Session,
+    },
+    ops::{campaign_ops, lfg_ops, response_ops, session_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_date, autocomplete_lfg},
+        checks,
+        date::{get_long_date_short_time_timestamp, is_naive_date_in_future, parse_natural_date},
+        guilds::get_guild_id,
+        id::{channel_id_to_i64, guild_id_to_i64, i64_to_channel_id, user_id_to_i64},
+        notifications,
+        users::get_timezone,
+    },
+    Context, Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const LFG_PREFIX: &str = "lfg_";
+
+/// Looking-for-group board for one-shots (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("post", "list", "close"),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn lfg(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Advertises an open one-shot table that players can sign up for (DMs only)
+///
+/// Creates a campaign and a session for the one-shot
+/// Players signing up once every seat is taken are put on a waitlist
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, check = "checks::dm_check")]
+pub async fn post(
+    ctx: Context<'_>,
+    #[description = "The name of the one-shot"]
+    #[max_length = 100]
+    name: String,
+    #[description = "The game system, e.g. D&D 5e"]
+    #[max_length = 50]
+    system: String,
+    #[description = "The lowest character level"]
+    #[min = 1]
+    #[max = 20]
+    min_level: i16,
+    #[description = "The highest character level"]
+    #[min = 1]
+    #[max = 20]
+    max_level: i16,
+    #[description = "The number of players"]
+    #[min = 1]
+    seats: i16,
+    #[description = "Date and time of the one-shot (e.g. YYYY-MM-DD HH:MM, next friday 7pm)"]
+    #[autocomplete = autocomplete_date]
+    scheduled_date: String,
+    #[description = "Content warnings players should know about"]
+    #[max_length = 1000]
+    content_warnings: Option<String>,
+    #[description = "Where to meet"] location: Option<String>,
+    #[description = "What the one-shot is about"]
+    #[max_length = 2000]
+    description: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if min_level > max_level {
+        return responses::failure(
+            ctx,
+            "The lowest level can't be higher than the highest level.",
+        )
+        .await;
+    }
+
+    let scheduled_date = match parse_natural_date(&scheduled_date, get_timezone(ctx).await) {
+        Some(scheduled_date) => scheduled_date,
+        None => return responses::failure(ctx, "Invalid date format.").await,
+    };
+
+    if !is_naive_date_in_future(scheduled_date) {
+        return responses::failure(ctx, "Scheduled date must be in the future.").await;
+    }
+
+    if campaign_ops::does_campaign_exist(ctx, &name, guild_id) {
+        return responses::failure(ctx, &format!("Campaign with name {} already exists.", name))
+            .await;
+    }
+
+    let author_id = user_id_to_i64(ctx.author().id).await;
+    let created_date = chrono::Utc::now().naive_utc();
+
+    let result = lfg_ops::create_post(
+        ctx,
+        NewCampaign {
+            guild_id,
+            dm_id: author_id,
+            name: &name,
+            description: description.as_deref(),
+            link: None,
+            deleted: false,
+            created_date,
+        },
+        NewSession {
+            campaign_id: 0,
+            author_id,
+            location: location.as_deref(),
+            status: STATUS_PENDING,
+            created_date,
+            scheduled_date: Some(scheduled_date),
+            max_players: Some(seats),
+        },
+        NewLfgPost {
+            campaign_id: 0,
+            session_id: 0,
+            system: system.trim(),
+            min_level,
+            max_level,
+            content_warnings: content_warnings
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty()),
+            channel_id: Some(channel_id_to_i64(ctx.channel_id()).await),
+            created_date,
+        },
+    );
+
+    let (campaign, session, post) = match result {
+        Ok(result) => result,
+        // Another campaign took the name since it was checked
+        Err(diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            _,
+        )) => {
+            return responses::failure(ctx, &format!("Campaign with name {} already exists.", name))
+                .await
+        }
+        Err(e) => {
+            println!("Failed to post one-shot {}: {:?}", name, e);
+            return responses::failure(ctx, "Failed to post the one-shot.").await;
+        }
+    };
+
+    let handle = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(build_lfg_embed(&post, &campaign, &session, &[])?)
+                .components(build_lfg_components(&post)),
+        )
+        .await?;
+
+    let message = handle.message().await?;
+
+    lfg_ops::set_message_id(ctx, post.id, message.id.get() as i64);
+
+    Ok(())
+}
+
+/// Lists the open one-shot tables of the server
+#[poise::command(slash_command)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = get_guild_id(ctx).await;
+    let posts = lfg_ops::get_open_posts(ctx, guild_id_to_i64(guild_id).await);
+
+    if posts.is_empty() {
+        return responses::failure(ctx, "There are no open tables right now.").await;
+    }
+
+    let mut lines = vec![];
+
+    for (post, campaign, session) in &posts {
+        let going = response_ops::get_responses_for_session(ctx.data(), session.id)
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.response == 1 && !r.waitlisted)
+            .count();
+
+        let link = match (post.channel_id, post.message_id) {
+            (Some(channel_id), Some(message_id)) => format!(
+                " | [Sign up](https://discord.com/channels/{}/{}/{})",
+                guild_id, channel_id, message_id
+            ),
+            _ => String::new(),
+        };
+
+        lines.push(format!(
+            "**{}** | {} | level {} | {} | {}/{} seats{}",
+            campaign.name,
+            post.system,
+            format_levels(post),
+            format_date(session)?,
+            going,
+            session.max_players.unwrap_or_default(),
+            link
+        ));
+    }
+
+    let embeds = lines
+        .chunks(10)
+        .map(|page| {
+            serenity::CreateEmbed::new()
+                .title("Open tables")
+                .description(page.join("\n"))
+        })
+        .collect();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+/// Closes signups for a one-shot table (DMs only)
+#[poise::command(slash_command)]
+pub async fn close(
+    ctx: Context<'_>,
+    #[description = "The name of the one-shot"]
+    #[autocomplete = "autocomplete_lfg"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.clone()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let post = match lfg_ops::get_post(ctx, campaign.id) {
+        Some(post) if !post.closed => post,
+        _ => return responses::failure(ctx, "That table has no open signups.").await,
+    };
+
+    lfg_ops::close_post(ctx, post.id);
+
+    if let (Some(channel_id), Some(message_id), Some(session)) = (
+        post.channel_id,
+        post.message_id,
+        session_ops::get_session(ctx, post.session_id),
+    ) {
+        let signups =
+            response_ops::get_responses_for_session(ctx.data(), session.id).unwrap_or_default();
+        let post = LfgPost {
+            closed: true,
+            ..post
+        };
+
+        let edited = i64_to_channel_id(channel_id)
+            .await
+            .edit_message(
+                ctx,
+                serenity::MessageId::new(message_id as u64),
+                serenity::EditMessage::new()
+                    .embed(build_lfg_embed(&post, &campaign, &session, &signups)?)
+                    .components(vec![]),
+            )
+            .await;
+
+        if let Err(e) = edited {
+            println!("Failed to update LFG post {}: {:?}", post.id, e);
+        }
+    }
+
+    responses::success(ctx, &format!("Signups for {} are closed.", campaign.name)).await
+}
+
+/// Signs a player up for a one-shot, or withdraws them, when they press a button on its post
+pub async fn handle_signup(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let (action, post_id) = match interaction
+        .data
+        .custom_id
+        .strip_prefix(LFG_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+        .and_then(|(action, id)| id.parse::<i32>().ok().map(|id| (action, id)))
+    {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let (post, campaign, session) = match lfg_ops::get_post_with_details(data, post_id) {
+        Some(result) => result,
+        None => return reply_ephemeral(ctx, interaction, "This table no longer exists.").await,
+    };
+
+    if post.closed || session.status == STATUS_CANCELLED || session.status == STATUS_COMPLETED {
+        return reply_ephemeral(ctx, interaction, "Signups for this table are closed.").await;
+    }
+
+    let user_id = user_id_to_i64(interaction.user.id).await;
+
+    if user_id == campaign.dm_id {
+        return reply_ephemeral(ctx, interaction, "You're running this table.").await;
+    }
+
+    let response = if action == "join" { 1 } else { 0 };
+
+    let signed_up = response_ops::get_responses_for_session(data, session.id)
+        .unwrap_or_default()
+        .iter()
+        .any(|r| r.respondee_id == user_id && r.response == 1);
+
+    if response == 0 && !signed_up {
+        return reply_ephemeral(ctx, interaction, "You're not signed up for this table.").await;
+    }
+
+    let outcome = response_ops::create_response(
+        data,
+        NewResponse {
+            session_id: session.id,
+            respondee_id: user_id,
+            response,
+            responded_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    notifications::notify_promoted(ctx, session.id, &outcome.promoted).await;
+
+    let signups = response_ops::get_responses_for_session(data, session.id).unwrap_or_default();
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(build_lfg_embed(&post, &campaign, &session, &signups)?),
+            ),
+        )
+        .await?;
+
+    let msg = match (response, outcome.waitlist_position) {
+        (1, Some(position)) => format!(
+            "The table is full. You're at position {} on the waitlist.",
+            position
+        ),
+        (1, None) => format!("You're signed up for {}.", campaign.name),
+        _ => format!("You've withdrawn from {}.", campaign.name),
+    };
+
+    interaction
+        .create_followup(
+            ctx,
+            serenity::CreateInteractionResponseFollowup::new()
+                .content(msg)
+                .ephemeral(true),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn build_lfg_embed(
+    post: &LfgPost,
+    campaign: &Campaign,
+    session: &Session,
+    signups: &[Response],
+) -> Result<serenity::CreateEmbed, Error> {
+    let going = signups
+        .iter()
+        .filter(|r| r.response == 1 && !r.waitlisted)
+        .map(|r| format!("<@{}>", r.respondee_id))
+        .collect::<Vec<String>>();
+    let waitlist = signups
+        .iter()
+        .filter(|r| r.response == 1 && r.waitlisted)
+        .map(|r| format!("<@{}>", r.respondee_id))
+        .collect::<Vec<String>>();
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(match post.closed {
+            true => format!("{} (closed)", campaign.name),
+            false => campaign.name.clone(),
+        })
+        .description(format!(
+            "Hosted by <@{}>{}",
+            campaign.dm_id,
+            campaign
+                .description
+                .as_deref()
+                .map(|d| format!("\n\n{}", d))
+                .unwrap_or_default()
+        ))
+        .field("System", &post.system, true)
+        .field("Level", format_levels(post), true)
+        .field(
+            "Seats",
+            format!(
+                "{}/{}",
+                going.len(),
+                session.max_players.unwrap_or_default()
+            ),
+            true,
+        )
+        .field("When", format_date(session)?, true);
+
+    if let Some(location) = &session.location {
+        embed = embed.field("Where", location, true);
+    }
+
+    if let Some(content_warnings) = &post.content_warnings {
+        embed = embed.field("Content warnings", content_warnings, false);
+    }
+
+    embed = embed.field(
+        "Players",
+        match going.is_empty() {
+            true => "None yet".to_string(),
+            false => going.join(", "),
+        },
+        false,
+    );
+
+    if !waitlist.is_empty() {
+        embed = embed.field("Waitlist", waitlist.join(", "), false);
+    }
+
+    Ok(embed.footer(serenity::CreateEmbedFooter::new(format!(
+        "Session ID: {}",
+        session.id
+    ))))
+}
+
+fn build_lfg_components(post: &LfgPost) -> Vec<serenity::CreateActionRow> {
+    vec![serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("{}join_{}", LFG_PREFIX, post.id))
+            .label("Sign up")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(format!("{}leave_{}", LFG_PREFIX, post.id))
+            .label("Withdraw")
+            .style(serenity::ButtonStyle::Secondary),
+    ])]
+}
+
+fn format_levels(post: &LfgPost) -> String {
+    match post.min_level == post.max_level {
+        true => post.min_level.to_string(),
+        false => format!("{}–{}", post.min_level, post.max_level),
+    }
+}
+
+fn format_date(session: &Session) -> Result<String, Error> {
+    match session.scheduled_date {
+        Some(date) => Ok(get_long_date_short_time_timestamp(
+            &date.format("%Y-%m-%d %H:%M").to_string(),
+        )?),
+        None => Ok("TBD".to_string()),
+    }
+}
+
+async fn reply_ephemeral(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    msg: &str,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(msg)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,414 @@
+use crate::{
+    models::{Campaign, LoreEntry, NewLoreEntry},
+    ops::{campaign_ops, lore_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_lore_title},
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+    },
+    ApplicationContext, Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+const LIST_PAGE_SIZE: usize = 20;
+
+#[derive(poise::ChoiceParameter)]
+enum VisibilityChoice {
+    #[name = "DM only"]
+    DmOnly,
+    #[name = "Players"]
+    Players,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Lore Entry"]
+struct LoreModal {
+    #[name = "Title"]
+    #[max_length = 100]
+    title: String,
+    #[name = "Content (markdown)"]
+    #[paragraph]
+    #[max_length = 4000]
+    content: String,
+}
+
+/// D&D campaign lore, locations and factions (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("add", "edit", "show", "search", "list"),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn lore(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a lore entry to a D&D campaign (DMs only)
+///
+/// Entries are DM-only unless made visible to players
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: ApplicationContext<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Comma-separated tags, e.g. npc, faction"] tags: Option<String>,
+    #[description = "Who can read the entry. Defaults to DM only"] visibility: Option<
+        VisibilityChoice,
+    >,
+) -> Result<(), Error> {
+    use poise::Modal as _;
+
+    let campaign = match find_campaign(poise::Context::Application(ctx), &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(poise::Context::Application(ctx), &campaign).await? {
+        return Ok(());
+    }
+
+    let data = match LoreModal::execute(ctx).await? {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+    let title = data.title.trim();
+
+    if lore_ops::does_entry_exist(poise::Context::Application(ctx), campaign.id, title) {
+        return responses::failure(
+            poise::Context::Application(ctx),
+            &format!("Lore entry {} already exists in {}.", title, campaign.name),
+        )
+        .await;
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+
+    lore_ops::create_entry(
+        poise::Context::Application(ctx),
+        NewLoreEntry {
+            campaign_id: campaign.id,
+            title,
+            content: &data.content,
+            tags: tags.as_deref().map(parse_tags).unwrap_or_default(),
+            dm_only: !matches!(visibility, Some(VisibilityChoice::Players)),
+            author_id: user_id_to_i64(ctx.author().id).await,
+            created_date: now,
+            updated_date: now,
+        },
+    );
+
+    responses::success(
+        poise::Context::Application(ctx),
+        &format!("Lore entry {} added to {}.", title, campaign.name),
+    )
+    .await
+}
+
+/// Edits a lore entry of a D&D campaign (DMs only)
+///
+/// Leave tags or visibility empty to keep them as they are
+#[poise::command(slash_command)]
+pub async fn edit(
+    ctx: ApplicationContext<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The title of the entry to edit"]
+    #[autocomplete = "autocomplete_lore_title"]
+    title: String,
+    #[description = "Comma-separated tags, replacing the current ones"] tags: Option<String>,
+    #[description = "Who can read the entry"] visibility: Option<VisibilityChoice>,
+) -> Result<(), Error> {
+    use poise::Modal as _;
+
+    let campaign = match find_campaign(poise::Context::Application(ctx), &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(poise::Context::Application(ctx), &campaign).await? {
+        return Ok(());
+    }
+
+    let entry = match lore_ops::get_entry(poise::Context::Application(ctx), campaign.id, &title) {
+        Some(entry) => entry,
+        None => {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                &format!("Lore entry {} does not exist.", title),
+            )
+            .await
+        }
+    };
+
+    let defaults = LoreModal {
+        title: entry.title.clone(),
+        content: entry.content.clone(),
+    };
+
+    let data = match LoreModal::execute_with_defaults(ctx, defaults).await? {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+    let new_title = data.title.trim().to_string();
+
+    if new_title != entry.title
+        && lore_ops::does_entry_exist(poise::Context::Application(ctx), campaign.id, &new_title)
+    {
+        return responses::failure(
+            poise::Context::Application(ctx),
+            &format!(
+                "Lore entry {} already exists in {}.",
+                new_title, campaign.name
+            ),
+        )
+        .await;
+    }
+
+    lore_ops::update_entry(
+        poise::Context::Application(ctx),
+        LoreEntry {
+            title: new_title.clone(),
+            content: data.content,
+            tags: match tags {
+                Some(tags) => parse_tags(&tags),
+                None => entry.tags.clone(),
+            },
+            dm_only: match visibility {
+                Some(visibility) => matches!(visibility, VisibilityChoice::DmOnly),
+                None => entry.dm_only,
+            },
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..entry
+        },
+    );
+
+    responses::success(
+        poise::Context::Application(ctx),
+        &format!("Lore entry {} updated.", new_title),
+    )
+    .await
+}
+
+/// Shows a lore entry of a D&D campaign
+///
+/// DM-only entries are only shown to the campaign's DMs, privately
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The title of the entry"]
+    #[autocomplete = "autocomplete_lore_title"]
+    title: String,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let is_dm = is_dm(ctx, &campaign).await;
+
+    let entry = match lore_ops::get_entry(ctx, campaign.id, &title) {
+        Some(entry) if is_dm || !entry.dm_only => entry,
+        _ => {
+            return responses::failure(ctx, &format!("Lore entry {} does not exist.", title)).await
+        }
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(entry_embed(&entry))
+            .ephemeral(entry.dm_only),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Searches the lore of a D&D campaign
+///
+/// Supports quoted phrases, "or" and excluding words with a leading "-"
+#[poise::command(slash_command)]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "What to search for"] query: String,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let is_dm = is_dm(ctx, &campaign).await;
+
+    if is_dm {
+        ctx.defer_ephemeral().await?;
+    } else {
+        ctx.defer().await?;
+    }
+
+    let entries = lore_ops::search_entries(ctx, campaign.id, is_dm, &query).unwrap_or_default();
+    let total = entries.len();
+
+    let embeds = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            entry_embed(entry).footer(serenity::CreateEmbedFooter::new(format!(
+                "Result {}/{} for \"{}\"",
+                index + 1,
+                total,
+                query
+            )))
+        })
+        .collect::<Vec<serenity::CreateEmbed>>();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+/// Lists the lore entries of a D&D campaign
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Only list entries with this tag"] tag: Option<String>,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let is_dm = is_dm(ctx, &campaign).await;
+
+    if is_dm {
+        ctx.defer_ephemeral().await?;
+    } else {
+        ctx.defer().await?;
+    }
+
+    let entries = lore_ops::get_entries(
+        ctx,
+        campaign.id,
+        is_dm,
+        tag.map(|tag| tag.trim().to_lowercase()),
+    )
+    .unwrap_or_default();
+    let pages = entries.len().div_ceil(LIST_PAGE_SIZE);
+
+    let embeds = entries
+        .chunks(LIST_PAGE_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(|entry| {
+                    let mut line = format!("**{}**", entry.title);
+
+                    if !entry.tags.is_empty() {
+                        line.push_str(&format!(" — {}", entry.tags.join(", ")));
+                    }
+
+                    if entry.dm_only {
+                        line.push_str(" (DM only)");
+                    }
+
+                    line
+                })
+                .collect::<Vec<String>>();
+
+            serenity::CreateEmbed::new()
+                .title(format!("{} lore", campaign.name))
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{}",
+                    index + 1,
+                    pages
+                )))
+        })
+        .collect::<Vec<serenity::CreateEmbed>>();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+fn entry_embed(entry: &LoreEntry) -> serenity::CreateEmbed {
+    let mut embed = serenity::CreateEmbed::new()
+        .title(&entry.title)
+        .description(&entry.content);
+
+    if !entry.tags.is_empty() {
+        embed = embed.field("Tags", entry.tags.join(", "), true);
+    }
+
+    embed.field(
+        "Visibility",
+        if entry.dm_only { "DM only" } else { "Players" },
+        true,
+    )
+}
+
+/// Splits comma-separated tags, lowercasing them and dropping blanks and duplicates
+fn parse_tags(tags: &str) -> Vec<String> {
+    let mut parsed: Vec<String> = vec![];
+
+    for tag in tags.split(',').map(|tag| tag.trim().to_lowercase()) {
+        if !tag.is_empty() && !parsed.contains(&tag) {
+            parsed.push(tag);
+        }
+    }
+
+    parsed
+}
+
+async fn is_dm(ctx: Context<'_>, campaign: &Campaign) -> bool {
+    checks::can_manage_campaign(ctx.data(), campaign, user_id_to_i64(ctx.author().id).await)
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
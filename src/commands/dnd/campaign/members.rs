@@ -0,0 +1,422 @@
+use super::questionnaire::join_prompt;
+use crate::{
+    models::{Campaign, NewCampaignMember},
+    ops::{campaign_ops, member_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign,
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+        notifications,
+    },
+    Context, Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const MEMBER_ROLE_PLAYER: i16 = 0;
+pub const MEMBER_ROLE_CO_DM: i16 = 1;
+pub const MEMBER_ROLE_SPECTATOR: i16 = 2;
+
+pub const MEMBER_STATUS_PENDING: i16 = 0;
+pub const MEMBER_STATUS_ACTIVE: i16 = 1;
+
+pub const MEMBER_PREFIX: &str = "campaign_member_";
+
+#[derive(poise::ChoiceParameter)]
+enum JoinRoleChoice {
+    Player,
+    Spectator,
+}
+
+#[derive(poise::ChoiceParameter)]
+enum MemberRoleChoice {
+    Player,
+    #[name = "Co-DM"]
+    CoDm,
+    Spectator,
+}
+
+pub fn get_role_name(role: i16) -> &'static str {
+    match role {
+        MEMBER_ROLE_PLAYER => "Player",
+        MEMBER_ROLE_CO_DM => "Co-DM",
+        MEMBER_ROLE_SPECTATOR => "Spectator",
+        _ => "Unknown",
+    }
+}
+
+/// Asks to join a D&D campaign
+///
+/// The campaign's DM approves or denies the request
+#[poise::command(slash_command)]
+pub async fn join(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to join"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "How you'd like to join. Defaults to player"] role: Option<JoinRoleChoice>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &name).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    if campaign.dm_id == user_id {
+        return responses::failure(ctx, "You're already the DM of this campaign.").await;
+    }
+
+    match member_ops::get_member(ctx, campaign.id, user_id) {
+        Some(member) if member.status == MEMBER_STATUS_ACTIVE => {
+            return responses::failure(ctx, "You're already a member of this campaign.").await
+        }
+        Some(_) => {
+            return responses::failure(ctx, "Your request to join this campaign is pending.").await
+        }
+        None => {}
+    }
+
+    let role = match role {
+        Some(JoinRoleChoice::Spectator) => MEMBER_ROLE_SPECTATOR,
+        _ => MEMBER_ROLE_PLAYER,
+    };
+
+    let member = member_ops::add_member(
+        ctx,
+        NewCampaignMember {
+            campaign_id: campaign.id,
+            user_id,
+            role,
+            status: MEMBER_STATUS_PENDING,
+            added_by: user_id,
+            joined_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    let components = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("{}approve_{}", MEMBER_PREFIX, member.id))
+            .label("Approve")
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(format!("{}deny_{}", MEMBER_PREFIX, member.id))
+            .label("Deny")
+            .style(serenity::ButtonStyle::Danger),
+    ]);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "<@{}> would like to join {} as a {}. <@{}>, do you approve?",
+                user_id,
+                campaign.name,
+                get_role_name(role).to_lowercase(),
+                campaign.dm_id
+            ))
+            .components(vec![components]),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Leaves a D&D campaign
+#[poise::command(slash_command)]
+pub async fn leave(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign to leave"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &name).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !member_ops::delete_member(ctx, campaign.id, user_id_to_i64(ctx.author().id).await) {
+        return responses::failure(ctx, "You're not a member of this campaign.").await;
+    }
+
+    responses::success(ctx, &format!("You've left {}.", campaign.name)).await
+}
+
+/// Adds a user to a D&D campaign, or changes their role if they're already a member (DMs only)
+#[poise::command(slash_command)]
+pub async fn invite(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "The user to add"] user: serenity::User,
+    #[description = "Their role in the campaign. Defaults to player"] role: Option<
+        MemberRoleChoice,
+    >,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &name).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let user_id = user_id_to_i64(user.id).await;
+
+    if campaign.dm_id == user_id {
+        return responses::failure(ctx, "That user is already the DM of this campaign.").await;
+    }
+
+    let role = match role {
+        Some(MemberRoleChoice::CoDm) => MEMBER_ROLE_CO_DM,
+        Some(MemberRoleChoice::Spectator) => MEMBER_ROLE_SPECTATOR,
+        _ => MEMBER_ROLE_PLAYER,
+    };
+
+    member_ops::add_member(
+        ctx,
+        NewCampaignMember {
+            campaign_id: campaign.id,
+            user_id,
+            role,
+            status: MEMBER_STATUS_ACTIVE,
+            added_by: user_id_to_i64(ctx.author().id).await,
+            joined_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    notifications::notify_user(
+        ctx.serenity_context(),
+        user_id,
+        &format!(
+            "You've been added to {} as a {}.{}",
+            campaign.name,
+            get_role_name(role).to_lowercase(),
+            if role == MEMBER_ROLE_PLAYER {
+                join_prompt(ctx.data(), &campaign)
+            } else {
+                String::new()
+            }
+        ),
+    )
+    .await;
+
+    responses::success(
+        ctx,
+        &format!(
+            "<@{}> is now a {} in {}.",
+            user_id,
+            get_role_name(role).to_lowercase(),
+            campaign.name
+        ),
+    )
+    .await
+}
+
+/// Removes a user from a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn kick(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+    #[description = "The user to remove"] user: serenity::User,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &name).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    if !member_ops::delete_member(ctx, campaign.id, user_id_to_i64(user.id).await) {
+        return responses::failure(ctx, "That user is not a member of this campaign.").await;
+    }
+
+    responses::success(
+        ctx,
+        &format!("<@{}> has been removed from {}.", user.id, campaign.name),
+    )
+    .await
+}
+
+/// Lists the members of a D&D campaign
+#[poise::command(slash_command)]
+pub async fn members(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &name).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(ctx, &format!("Campaign with name {} does not exist.", name))
+                .await
+        }
+    };
+
+    let members = member_ops::get_members(ctx, campaign.id).unwrap_or_default();
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("{} members", campaign.name))
+        .field("DM", format!("<@{}>", campaign.dm_id), false);
+
+    for (title, role, status) in [
+        ("Co-DMs", MEMBER_ROLE_CO_DM, MEMBER_STATUS_ACTIVE),
+        ("Players", MEMBER_ROLE_PLAYER, MEMBER_STATUS_ACTIVE),
+        ("Spectators", MEMBER_ROLE_SPECTATOR, MEMBER_STATUS_ACTIVE),
+    ] {
+        let mentions = members
+            .iter()
+            .filter(|m| m.role == role && m.status == status)
+            .map(|m| format!("<@{}>", m.user_id))
+            .collect::<Vec<String>>();
+
+        if !mentions.is_empty() {
+            embed = embed.field(title, mentions.join(", "), false);
+        }
+    }
+
+    let pending = members
+        .iter()
+        .filter(|m| m.status == MEMBER_STATUS_PENDING)
+        .map(|m| format!("<@{}> ({})", m.user_id, get_role_name(m.role)))
+        .collect::<Vec<String>>();
+
+    if !pending.is_empty() {
+        embed = embed.field("Pending requests", pending.join(", "), false);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Approves or denies a join request when the campaign's DM or a co-DM presses its button
+pub async fn handle_request(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let (action, member_id) = match interaction
+        .data
+        .custom_id
+        .strip_prefix(MEMBER_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+        .and_then(|(action, id)| id.parse::<i32>().ok().map(|id| (action, id)))
+    {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let (member, campaign) = match member_ops::get_member_with_campaign(data, member_id) {
+        Some(result) => result,
+        None => {
+            return reply_ephemeral(ctx, interaction, "This request no longer exists.").await;
+        }
+    };
+
+    let responder_id = user_id_to_i64(interaction.user.id).await;
+
+    if !checks::can_manage_campaign(data, &campaign, responder_id) {
+        return reply_ephemeral(
+            ctx,
+            interaction,
+            "Only the campaign's DM can respond to join requests.",
+        )
+        .await;
+    }
+
+    let outcome = if action == "approve" {
+        member_ops::approve_member(data, member.id);
+        "approved"
+    } else {
+        member_ops::delete_member_by_id(data, member.id);
+        "denied"
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "<@{}>'s request to join {} was {} by <@{}>.",
+                        member.user_id, campaign.name, outcome, responder_id
+                    ))
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    notifications::notify_user(
+        ctx,
+        member.user_id,
+        &format!(
+            "Your request to join {} was {}.{}",
+            campaign.name,
+            outcome,
+            if action == "approve" && member.role == MEMBER_ROLE_PLAYER {
+                join_prompt(data, &campaign)
+            } else {
+                String::new()
+            }
+        ),
+    )
+    .await;
+
+    Ok(())
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
+
+async fn reply_ephemeral(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    msg: &str,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(msg)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,684 @@
+use crate::{
+    models::{Campaign, NewNpc, Npc},
+    ops::{campaign_ops, npc_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_npc},
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+    },
+    Context, Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const ATTITUDE_HOSTILE: i16 = 0;
+pub const ATTITUDE_UNFRIENDLY: i16 = 1;
+pub const ATTITUDE_NEUTRAL: i16 = 2;
+pub const ATTITUDE_FRIENDLY: i16 = 3;
+pub const ATTITUDE_ALLIED: i16 = 4;
+
+pub const NPC_STATUS_ALIVE: i16 = 0;
+pub const NPC_STATUS_DEAD: i16 = 1;
+pub const NPC_STATUS_UNKNOWN: i16 = 2;
+
+pub const REVEAL_PREFIX: &str = "npc_reveal_";
+
+/// The fields of an NPC that can be revealed to players, in display order
+///
+/// Until its name is revealed, players don't see the NPC at all
+const FIELDS: [&str; 8] = [
+    "name",
+    "description",
+    "location",
+    "faction",
+    "attitude",
+    "status",
+    "portrait",
+    "secrets",
+];
+
+const LIST_PAGE_SIZE: usize = 20;
+const MAX_PORTRAIT_SIZE: u32 = 8 * 1024 * 1024;
+
+#[derive(poise::ChoiceParameter)]
+enum AttitudeChoice {
+    Hostile,
+    Unfriendly,
+    Neutral,
+    Friendly,
+    Allied,
+}
+
+impl AttitudeChoice {
+    fn to_attitude(&self) -> i16 {
+        match self {
+            AttitudeChoice::Hostile => ATTITUDE_HOSTILE,
+            AttitudeChoice::Unfriendly => ATTITUDE_UNFRIENDLY,
+            AttitudeChoice::Neutral => ATTITUDE_NEUTRAL,
+            AttitudeChoice::Friendly => ATTITUDE_FRIENDLY,
+            AttitudeChoice::Allied => ATTITUDE_ALLIED,
+        }
+    }
+}
+
+#[derive(poise::ChoiceParameter)]
+enum NpcStatusChoice {
+    Alive,
+    Dead,
+    Unknown,
+}
+
+impl NpcStatusChoice {
+    fn to_status(&self) -> i16 {
+        match self {
+            NpcStatusChoice::Alive => NPC_STATUS_ALIVE,
+            NpcStatusChoice::Dead => NPC_STATUS_DEAD,
+            NpcStatusChoice::Unknown => NPC_STATUS_UNKNOWN,
+        }
+    }
+}
+
+pub fn get_attitude_name(attitude: i16) -> &'static str {
+    match attitude {
+        ATTITUDE_HOSTILE => "Hostile",
+        ATTITUDE_UNFRIENDLY => "Unfriendly",
+        ATTITUDE_NEUTRAL => "Neutral",
+        ATTITUDE_FRIENDLY => "Friendly",
+        ATTITUDE_ALLIED => "Allied",
+        _ => "Unknown",
+    }
+}
+
+pub fn get_npc_status_name(status: i16) -> &'static str {
+    match status {
+        NPC_STATUS_ALIVE => "Alive",
+        NPC_STATUS_DEAD => "Dead",
+        _ => "Unknown",
+    }
+}
+
+/// D&D campaign NPCs (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("add", "edit", "show", "list"),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn npc(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds an NPC to a D&D campaign (DMs only)
+///
+/// Everything starts hidden from players, use `/npc show` to reveal fields at the table
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The name of the NPC"]
+    #[max_length = 100]
+    name: String,
+    #[description = "What the NPC is like"] description: Option<String>,
+    #[description = "Where the NPC can be found"] location: Option<String>,
+    #[description = "Who the NPC works for"] faction: Option<String>,
+    #[description = "How the NPC feels about the party. Defaults to neutral"] attitude: Option<
+        AttitudeChoice,
+    >,
+    #[description = "Whether the NPC is alive. Defaults to alive"] status: Option<NpcStatusChoice>,
+    #[description = "What only the DM knows about the NPC"] secrets: Option<String>,
+    #[description = "A portrait of the NPC"] portrait: Option<serenity::Attachment>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let portrait_data = match &portrait {
+        Some(portrait) => match download_portrait(portrait).await {
+            Ok(bytes) => Some(bytes),
+            Err(msg) => return responses::failure(ctx, msg).await,
+        },
+        None => None,
+    };
+
+    let name = name.trim();
+
+    if npc_ops::does_npc_exist(ctx, campaign.id, name) {
+        return responses::failure(
+            ctx,
+            &format!("NPC {} already exists in {}.", name, campaign.name),
+        )
+        .await;
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+
+    npc_ops::create_npc(
+        ctx,
+        NewNpc {
+            campaign_id: campaign.id,
+            name,
+            description: description.as_deref(),
+            location: location.as_deref(),
+            faction: faction.as_deref(),
+            attitude: attitude
+                .map(|a| a.to_attitude())
+                .unwrap_or(ATTITUDE_NEUTRAL),
+            status: status.map(|s| s.to_status()).unwrap_or(NPC_STATUS_ALIVE),
+            secrets: secrets.as_deref(),
+            author_id: user_id_to_i64(ctx.author().id).await,
+            created_date: now,
+            updated_date: now,
+            portrait_name: portrait.as_ref().map(|p| p.filename.as_str()),
+            portrait_data: portrait_data.as_deref(),
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!(
+            "NPC {} added to {}. It's hidden from players for now.",
+            name, campaign.name
+        ),
+    )
+    .await
+}
+
+/// Edits an NPC of a D&D campaign (DMs only)
+///
+/// Only the given fields are changed
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The NPC to edit"]
+    #[autocomplete = "autocomplete_npc"]
+    name: String,
+    #[description = "The new name of the NPC"]
+    #[max_length = 100]
+    new_name: Option<String>,
+    #[description = "What the NPC is like"] description: Option<String>,
+    #[description = "Where the NPC can be found"] location: Option<String>,
+    #[description = "Who the NPC works for"] faction: Option<String>,
+    #[description = "How the NPC feels about the party"] attitude: Option<AttitudeChoice>,
+    #[description = "Whether the NPC is alive"] status: Option<NpcStatusChoice>,
+    #[description = "What only the DM knows about the NPC"] secrets: Option<String>,
+    #[description = "A portrait of the NPC"] portrait: Option<serenity::Attachment>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let npc = match npc_ops::get_npc(ctx, campaign.id, &name) {
+        Some(npc) => npc,
+        None => return responses::failure(ctx, &format!("NPC {} does not exist.", name)).await,
+    };
+
+    let portrait_data = match &portrait {
+        Some(portrait) => match download_portrait(portrait).await {
+            Ok(bytes) => Some(bytes),
+            Err(msg) => return responses::failure(ctx, msg).await,
+        },
+        None => None,
+    };
+
+    let new_name = new_name
+        .map(|n| n.trim().to_string())
+        .unwrap_or_else(|| npc.name.clone());
+
+    if new_name != npc.name && npc_ops::does_npc_exist(ctx, campaign.id, &new_name) {
+        return responses::failure(
+            ctx,
+            &format!("NPC {} already exists in {}.", new_name, campaign.name),
+        )
+        .await;
+    }
+
+    npc_ops::update_npc(
+        ctx,
+        Npc {
+            name: new_name.clone(),
+            description: description.or(npc.description.clone()),
+            location: location.or(npc.location.clone()),
+            faction: faction.or(npc.faction.clone()),
+            attitude: attitude.map(|a| a.to_attitude()).unwrap_or(npc.attitude),
+            status: status.map(|s| s.to_status()).unwrap_or(npc.status),
+            secrets: secrets.or(npc.secrets.clone()),
+            portrait_name: portrait.map(|p| p.filename).or(npc.portrait_name.clone()),
+            portrait_data: portrait_data.or(npc.portrait_data.clone()),
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..npc
+        },
+    );
+
+    responses::success(ctx, &format!("NPC {} updated.", new_name)).await
+}
+
+/// Shows an NPC of a D&D campaign
+///
+/// Players see what the party knows, while DMs see everything and can reveal fields to the table
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The NPC to show"]
+    #[autocomplete = "autocomplete_npc"]
+    name: String,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let is_dm =
+        checks::can_manage_campaign(ctx.data(), &campaign, user_id_to_i64(ctx.author().id).await);
+
+    let npc = match npc_ops::get_npc(ctx, campaign.id, &name) {
+        Some(npc) if is_dm || is_revealed(&npc, "name") => npc,
+        _ => return responses::failure(ctx, &format!("NPC {} does not exist.", name)).await,
+    };
+
+    let (mut reply, attachment) = if is_dm {
+        (
+            poise::CreateReply::default()
+                .embed(dm_embed(&npc))
+                .components(reveal_buttons(&npc))
+                .ephemeral(true),
+            portrait_attachment(&npc),
+        )
+    } else {
+        (
+            poise::CreateReply::default().embed(player_embed(&npc)),
+            portrait_attachment(&npc).filter(|_| is_revealed(&npc, "portrait")),
+        )
+    };
+
+    if let Some(attachment) = attachment {
+        reply = reply.attachment(attachment);
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Lists the NPCs of a D&D campaign
+///
+/// Players only see the NPCs the party has met
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let is_dm =
+        checks::can_manage_campaign(ctx.data(), &campaign, user_id_to_i64(ctx.author().id).await);
+
+    if is_dm {
+        ctx.defer_ephemeral().await?;
+    } else {
+        ctx.defer().await?;
+    }
+
+    let npcs = npc_ops::get_npcs(ctx, campaign.id, is_dm).unwrap_or_default();
+    let pages = npcs.len().div_ceil(LIST_PAGE_SIZE);
+
+    let embeds = npcs
+        .chunks(LIST_PAGE_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(|npc| {
+                    let known = |field: &str| is_dm || is_revealed(npc, field);
+                    let mut details = vec![];
+
+                    if known("faction") {
+                        details.extend(npc.faction.clone());
+                    }
+
+                    if known("location") {
+                        details.extend(npc.location.clone());
+                    }
+
+                    if known("status") {
+                        details.push(get_npc_status_name(npc.status).to_string());
+                    }
+
+                    let mut line = format!("**{}**", npc.name);
+
+                    if !details.is_empty() {
+                        line.push_str(&format!(" — {}", details.join(", ")));
+                    }
+
+                    if !is_revealed(npc, "name") {
+                        line.push_str(" (hidden)");
+                    }
+
+                    line
+                })
+                .collect::<Vec<String>>();
+
+            serenity::CreateEmbed::new()
+                .title(format!("{} NPCs", campaign.name))
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{}",
+                    index + 1,
+                    pages
+                )))
+        })
+        .collect::<Vec<serenity::CreateEmbed>>();
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+/// Reveals a field of an NPC to the table when the campaign's DM presses its button
+pub async fn handle_reveal(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let (field, npc_id) = match interaction
+        .data
+        .custom_id
+        .strip_prefix(REVEAL_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+        .and_then(|(field, id)| id.parse::<i32>().ok().map(|id| (field, id)))
+    {
+        Some(result) if FIELDS.contains(&result.0) => result,
+        _ => return Ok(()),
+    };
+
+    let (npc, campaign) = match npc_ops::get_npc_with_campaign(data, npc_id) {
+        Some(result) => result,
+        None => return reply_ephemeral(ctx, interaction, "This NPC no longer exists.").await,
+    };
+
+    if !checks::can_manage_campaign(data, &campaign, user_id_to_i64(interaction.user.id).await) {
+        return reply_ephemeral(ctx, interaction, "Only the DMs can reveal NPC details.").await;
+    }
+
+    // Another press already revealed it, so only refresh the buttons
+    let npc = match npc_ops::reveal_field(data, npc_id, field) {
+        Some(npc) => npc,
+        None => {
+            interaction
+                .create_response(
+                    ctx,
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .embed(dm_embed(&npc))
+                            .components(reveal_buttons(&npc))
+                            .files(portrait_attachment(&npc)),
+                    ),
+                )
+                .await?;
+
+            return Ok(());
+        }
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(dm_embed(&npc))
+                    .components(reveal_buttons(&npc))
+                    .files(portrait_attachment(&npc)),
+            ),
+        )
+        .await?;
+
+    let mut message = serenity::CreateMessage::new().embed(reveal_embed(&npc, field));
+
+    // Only the name and portrait reveals show the portrait
+    if let Some(attachment) = portrait_attachment(&npc)
+        .filter(|_| matches!(field, "name" | "portrait") && is_revealed(&npc, "portrait"))
+    {
+        message = message.add_file(attachment);
+    }
+
+    interaction.channel_id.send_message(ctx, message).await?;
+
+    Ok(())
+}
+
+async fn reply_ephemeral(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    msg: &str,
+) -> Result<(), Error> {
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::Message(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(msg)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub fn is_revealed(npc: &Npc, field: &str) -> bool {
+    npc.revealed.iter().any(|f| f == field)
+}
+
+fn field_label(field: &str) -> &'static str {
+    match field {
+        "name" => "Name",
+        "description" => "Description",
+        "location" => "Location",
+        "faction" => "Faction",
+        "attitude" => "Attitude",
+        "status" => "Status",
+        "portrait" => "Portrait",
+        "secrets" => "Secrets",
+        _ => "Unknown",
+    }
+}
+
+/// Gets the value of a text field, or `None` if the NPC doesn't have one
+fn field_value(npc: &Npc, field: &str) -> Option<String> {
+    match field {
+        "name" => Some(npc.name.clone()),
+        "description" => npc.description.clone(),
+        "location" => npc.location.clone(),
+        "faction" => npc.faction.clone(),
+        "attitude" => Some(get_attitude_name(npc.attitude).to_string()),
+        "status" => Some(get_npc_status_name(npc.status).to_string()),
+        "portrait" => npc.portrait_data.as_ref().and(npc.portrait_name.clone()),
+        "secrets" => npc.secrets.clone(),
+        _ => None,
+    }
+}
+
+/// Builds an embed with the given fields, leaving out the name and portrait which have their own spots
+fn npc_embed<'a>(
+    title: String,
+    npc: &Npc,
+    fields: impl Iterator<Item = &'a str>,
+    show_portrait: bool,
+) -> serenity::CreateEmbed {
+    let mut embed = serenity::CreateEmbed::new().title(title);
+
+    for field in fields.filter(|f| *f != "name" && *f != "portrait") {
+        if let Some(value) = field_value(npc, field) {
+            embed = embed.field(
+                field_label(field),
+                value,
+                !matches!(field, "description" | "secrets"),
+            );
+        }
+    }
+
+    match field_value(npc, "portrait") {
+        Some(file_name) if show_portrait => embed.thumbnail(format!("attachment://{}", file_name)),
+        _ => embed,
+    }
+}
+
+fn player_embed(npc: &Npc) -> serenity::CreateEmbed {
+    npc_embed(
+        npc.name.clone(),
+        npc,
+        FIELDS.into_iter().filter(|f| is_revealed(npc, f)),
+        is_revealed(npc, "portrait"),
+    )
+}
+
+fn dm_embed(npc: &Npc) -> serenity::CreateEmbed {
+    let hidden = FIELDS
+        .into_iter()
+        .filter(|f| !is_revealed(npc, f) && field_value(npc, f).is_some())
+        .map(field_label)
+        .collect::<Vec<&str>>();
+
+    let embed = npc_embed(npc.name.clone(), npc, FIELDS.into_iter(), true);
+
+    if hidden.is_empty() {
+        embed.footer(serenity::CreateEmbedFooter::new(
+            "Everything is known to the players",
+        ))
+    } else {
+        embed.footer(serenity::CreateEmbedFooter::new(format!(
+            "Hidden from players: {}",
+            hidden.join(", ")
+        )))
+    }
+}
+
+/// Builds the message posted to the table when a field is revealed
+fn reveal_embed(npc: &Npc, field: &str) -> serenity::CreateEmbed {
+    let title = if is_revealed(npc, "name") {
+        npc.name.clone()
+    } else {
+        String::from("An unknown NPC")
+    };
+
+    match field {
+        "name" => player_embed(npc).title(format!("The party meets {}", npc.name)),
+        "portrait" => serenity::CreateEmbed::new().title(title).image(format!(
+            "attachment://{}",
+            field_value(npc, "portrait").unwrap_or_default()
+        )),
+        _ => npc_embed(title, npc, std::iter::once(field), false),
+    }
+}
+
+/// Builds a button for each field that has a value and is still hidden from players
+fn reveal_buttons(npc: &Npc) -> Vec<serenity::CreateActionRow> {
+    let buttons = FIELDS
+        .into_iter()
+        .filter(|f| !is_revealed(npc, f) && field_value(npc, f).is_some())
+        .map(|f| {
+            serenity::CreateButton::new(format!("{}{}_{}", REVEAL_PREFIX, f, npc.id))
+                .label(format!("Reveal {}", field_label(f).to_lowercase()))
+                .style(serenity::ButtonStyle::Secondary)
+        })
+        .collect::<Vec<serenity::CreateButton>>();
+
+    buttons
+        .chunks(5)
+        .map(|chunk| serenity::CreateActionRow::Buttons(chunk.to_vec()))
+        .collect()
+}
+
+fn portrait_attachment(npc: &Npc) -> Option<serenity::CreateAttachment> {
+    npc.portrait_name
+        .as_ref()
+        .zip(npc.portrait_data.as_ref())
+        .map(|(file_name, file_data)| {
+            serenity::CreateAttachment::bytes(file_data.clone(), file_name.clone())
+        })
+}
+
+/// Downloads a portrait so it keeps working after Discord's attachment link expires
+async fn download_portrait(portrait: &serenity::Attachment) -> Result<Vec<u8>, &'static str> {
+    if !is_image(portrait) {
+        return Err("The portrait must be an image.");
+    }
+
+    if portrait.size > MAX_PORTRAIT_SIZE {
+        return Err("That portrait is too large, the limit is 8 MB.");
+    }
+
+    portrait.download().await.map_err(|e| {
+        println!("Failed to download NPC portrait {}: {:?}", portrait.url, e);
+        "Failed to download the portrait."
+    })
+}
+
+fn is_image(attachment: &serenity::Attachment) -> bool {
+    attachment
+        .content_type
+        .as_deref()
+        .is_some_and(|t| t.starts_with("image/"))
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
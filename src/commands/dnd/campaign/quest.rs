@@ -0,0 +1,428 @@
+use super::npc::is_revealed;
+use crate::{
+    models::{Campaign, NewQuest, Quest, QuestObjective},
+    ops::{campaign_ops, npc_ops, quest_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_npc, autocomplete_quest},
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+        threads,
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub mod objective;
+
+pub const QUEST_STATUS_OPEN: i16 = 0;
+pub const QUEST_STATUS_COMPLETED: i16 = 1;
+pub const QUEST_STATUS_FAILED: i16 = 2;
+pub const QUEST_STATUS_ABANDONED: i16 = 3;
+
+const MAX_FIELD_LENGTH: usize = 1024;
+
+#[derive(poise::ChoiceParameter)]
+enum QuestStatusChoice {
+    Open,
+    Completed,
+    Failed,
+    Abandoned,
+}
+
+impl QuestStatusChoice {
+    fn to_status(&self) -> i16 {
+        match self {
+            QuestStatusChoice::Open => QUEST_STATUS_OPEN,
+            QuestStatusChoice::Completed => QUEST_STATUS_COMPLETED,
+            QuestStatusChoice::Failed => QUEST_STATUS_FAILED,
+            QuestStatusChoice::Abandoned => QUEST_STATUS_ABANDONED,
+        }
+    }
+}
+
+pub fn get_quest_status_name(status: i16) -> &'static str {
+    match status {
+        QUEST_STATUS_OPEN => "Open",
+        QUEST_STATUS_COMPLETED => "Completed",
+        QUEST_STATUS_FAILED => "Failed",
+        QUEST_STATUS_ABANDONED => "Abandoned",
+        _ => "Unknown",
+    }
+}
+
+/// D&D campaign quests and objectives (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("add", "edit", "show", "list", "objective::objective"),
+    subcommand_required,
+    check = "checks::dnd_check",
+    category = "D&D"
+)]
+pub async fn quest(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a quest to a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The title of the quest"]
+    #[max_length = 100]
+    title: String,
+    #[description = "The NPC who gave the quest"]
+    #[autocomplete = "autocomplete_npc"]
+    giver: Option<String>,
+    #[description = "What the quest is about"] description: Option<String>,
+    #[description = "What the party gets for completing it"] reward: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let title = title.trim();
+
+    if quest_ops::does_quest_exist(ctx, campaign.id, title) {
+        return responses::failure(
+            ctx,
+            &format!("Quest {} already exists in {}.", title, campaign.name),
+        )
+        .await;
+    }
+
+    let giver_npc_id = match giver {
+        Some(giver) => match npc_ops::get_npc(ctx, campaign.id, &giver) {
+            Some(npc) => Some(npc.id),
+            None => {
+                return responses::failure(ctx, &format!("NPC {} does not exist.", giver)).await
+            }
+        },
+        None => None,
+    };
+
+    let now = chrono::Utc::now().naive_utc();
+
+    quest_ops::create_quest(
+        ctx,
+        NewQuest {
+            campaign_id: campaign.id,
+            title,
+            giver_npc_id,
+            description: description.as_deref(),
+            reward: reward.as_deref(),
+            status: QUEST_STATUS_OPEN,
+            author_id: user_id_to_i64(ctx.author().id).await,
+            created_date: now,
+            updated_date: now,
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!(
+            "Quest {} added to {}. Add objectives with `/quest objective add`.",
+            title, campaign.name
+        ),
+    )
+    .await
+}
+
+/// Edits a quest of a D&D campaign (DMs only)
+///
+/// Only the given fields are changed, and status changes are posted in the campaign's channel
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest to edit"]
+    #[autocomplete = "autocomplete_quest"]
+    title: String,
+    #[description = "The new title of the quest"]
+    #[max_length = 100]
+    new_title: Option<String>,
+    #[description = "The NPC who gave the quest"]
+    #[autocomplete = "autocomplete_npc"]
+    giver: Option<String>,
+    #[description = "What the quest is about"] description: Option<String>,
+    #[description = "What the party gets for completing it"] reward: Option<String>,
+    #[description = "Where the quest stands"] status: Option<QuestStatusChoice>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let quest = match quest_ops::get_quest(ctx, campaign.id, &title) {
+        Some(quest) => quest,
+        None => return responses::failure(ctx, &format!("Quest {} does not exist.", title)).await,
+    };
+
+    let new_title = new_title
+        .map(|t| t.trim().to_string())
+        .unwrap_or_else(|| quest.title.clone());
+
+    if new_title != quest.title && quest_ops::does_quest_exist(ctx, campaign.id, &new_title) {
+        return responses::failure(
+            ctx,
+            &format!("Quest {} already exists in {}.", new_title, campaign.name),
+        )
+        .await;
+    }
+
+    let giver_npc_id = match giver {
+        Some(giver) => match npc_ops::get_npc(ctx, campaign.id, &giver) {
+            Some(npc) => Some(npc.id),
+            None => {
+                return responses::failure(ctx, &format!("NPC {} does not exist.", giver)).await
+            }
+        },
+        None => quest.giver_npc_id,
+    };
+
+    let old_status = quest.status;
+    let new_status = status.map(|s| s.to_status()).unwrap_or(old_status);
+
+    quest_ops::update_quest(
+        ctx,
+        Quest {
+            title: new_title.clone(),
+            giver_npc_id,
+            description: description.or(quest.description.clone()),
+            reward: reward.or(quest.reward.clone()),
+            status: new_status,
+            updated_date: chrono::Utc::now().naive_utc(),
+            ..quest
+        },
+    );
+
+    if new_status != old_status {
+        threads::post_to_campaign_channel(
+            ctx.http(),
+            &campaign,
+            &format!(
+                "📜 Quest **{}** is now {}.",
+                new_title,
+                get_quest_status_name(new_status).to_lowercase()
+            ),
+        )
+        .await;
+    }
+
+    responses::success(ctx, &format!("Quest {} updated.", new_title)).await
+}
+
+/// Shows a quest of a D&D campaign with its objectives
+#[poise::command(slash_command)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest to show"]
+    #[autocomplete = "autocomplete_quest"]
+    title: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let quest = match quest_ops::get_quest(ctx, campaign.id, &title) {
+        Some(quest) => quest,
+        None => return responses::failure(ctx, &format!("Quest {} does not exist.", title)).await,
+    };
+
+    let objectives = quest_ops::get_objectives(ctx, &[quest.id]);
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(&quest.title)
+        .description(quest.description.clone().unwrap_or_default())
+        .field("Status", get_quest_status_name(quest.status), true);
+
+    // Players only learn who gave the quest once the party has met the NPC
+    if let Some(npc) = quest
+        .giver_npc_id
+        .and_then(|npc_id| npc_ops::get_npc_by_id(ctx, npc_id))
+        .filter(|npc| is_revealed(npc, "name"))
+    {
+        embed = embed.field("Giver", npc.name, true);
+    }
+
+    if let Some(reward) = &quest.reward {
+        embed = embed.field("Reward", reward, true);
+    }
+
+    if !objectives.is_empty() {
+        let lines = objectives
+            .iter()
+            .map(objective_line)
+            .collect::<Vec<String>>();
+
+        embed = embed.field(
+            format!("Objectives ({})", objective_progress(&objectives)),
+            truncate_lines(&lines),
+            false,
+        );
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Lists the quests of a D&D campaign grouped by status
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let quests = quest_ops::get_quests(ctx, campaign.id).unwrap_or_default();
+
+    if quests.is_empty() {
+        return responses::failure(ctx, "No results found.").await;
+    }
+
+    let quest_ids = quests.iter().map(|q| q.id).collect::<Vec<i32>>();
+    let objectives = quest_ops::get_objectives(ctx, &quest_ids);
+
+    let mut embed = serenity::CreateEmbed::new().title(format!("{} quests", campaign.name));
+
+    for status in [
+        QUEST_STATUS_OPEN,
+        QUEST_STATUS_COMPLETED,
+        QUEST_STATUS_FAILED,
+        QUEST_STATUS_ABANDONED,
+    ] {
+        let lines = quests
+            .iter()
+            .filter(|q| q.status == status)
+            .map(|q| {
+                let quest_objectives = objectives
+                    .iter()
+                    .filter(|o| o.quest_id == q.id)
+                    .collect::<Vec<&QuestObjective>>();
+
+                if quest_objectives.is_empty() {
+                    format!("**{}**", q.title)
+                } else {
+                    format!(
+                        "**{}** ({})",
+                        q.title,
+                        objective_progress(&quest_objectives)
+                    )
+                }
+            })
+            .collect::<Vec<String>>();
+
+        if !lines.is_empty() {
+            embed = embed.field(get_quest_status_name(status), truncate_lines(&lines), false);
+        }
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+pub fn objective_line(objective: &QuestObjective) -> String {
+    if objective.completed {
+        format!("☑ ~~{}~~", objective.description)
+    } else {
+        format!("☐ {}", objective.description)
+    }
+}
+
+fn objective_progress<T: std::borrow::Borrow<QuestObjective>>(objectives: &[T]) -> String {
+    format!(
+        "{}/{}",
+        objectives.iter().filter(|o| o.borrow().completed).count(),
+        objectives.len()
+    )
+}
+
+/// Joins lines to fit in an embed field, noting how many didn't fit
+fn truncate_lines(lines: &[String]) -> String {
+    let mut value = String::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let more = format!("\n…and {} more", lines.len() - index);
+
+        if value.len() + line.len() + 1 + more.len() > MAX_FIELD_LENGTH {
+            value.push_str(&more);
+            break;
+        }
+
+        if !value.is_empty() {
+            value.push('\n');
+        }
+
+        value.push_str(line);
+    }
+
+    value
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
@@ -0,0 +1,243 @@
+use super::objective_line;
+use crate::{
+    models::{Campaign, NewQuestObjective, Quest},
+    ops::{campaign_ops, quest_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_objective, autocomplete_quest},
+        checks,
+        guilds::get_guild_id,
+        id::guild_id_to_i64,
+        threads,
+    },
+    Context, Error,
+};
+
+/// D&D quest objectives (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("add", "check", "uncheck", "remove"),
+    subcommand_required
+)]
+pub async fn objective(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds an objective to a quest (DMs only)
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest to add the objective to"]
+    #[autocomplete = "autocomplete_quest"]
+    quest: String,
+    #[description = "What the party needs to do"]
+    #[max_length = 200]
+    description: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, quest) = match find_quest(ctx, &campaign, &quest).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let description = description.trim();
+
+    let objective = quest_ops::add_objective(
+        ctx,
+        NewQuestObjective {
+            quest_id: quest.id,
+            description,
+            completed: false,
+            created_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    if objective.is_none() {
+        return responses::failure(
+            ctx,
+            &format!("Quest {} already has that objective.", quest.title),
+        )
+        .await;
+    }
+
+    responses::success(
+        ctx,
+        &format!("Objective added to {} in {}.", quest.title, campaign.name),
+    )
+    .await
+}
+
+/// Checks off an objective of a quest and announces it in the campaign's channel (DMs only)
+#[poise::command(slash_command)]
+pub async fn check(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest the objective belongs to"]
+    #[autocomplete = "autocomplete_quest"]
+    quest: String,
+    #[description = "The objective to check off"]
+    #[autocomplete = "autocomplete_objective"]
+    objective: String,
+) -> Result<(), Error> {
+    set_completed(ctx, campaign, quest, objective, true).await
+}
+
+/// Unchecks an objective of a quest and announces it in the campaign's channel (DMs only)
+#[poise::command(slash_command)]
+pub async fn uncheck(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest the objective belongs to"]
+    #[autocomplete = "autocomplete_quest"]
+    quest: String,
+    #[description = "The objective to uncheck"]
+    #[autocomplete = "autocomplete_objective"]
+    objective: String,
+) -> Result<(), Error> {
+    set_completed(ctx, campaign, quest, objective, false).await
+}
+
+/// Removes an objective from a quest (DMs only)
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The quest the objective belongs to"]
+    #[autocomplete = "autocomplete_quest"]
+    quest: String,
+    #[description = "The objective to remove"]
+    #[autocomplete = "autocomplete_objective"]
+    objective: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (_, quest) = match find_quest(ctx, &campaign, &quest).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let objective = match quest_ops::get_objective(ctx, quest.id, &objective) {
+        Some(objective) => objective,
+        None => return responses::failure(ctx, "Objective not found.").await,
+    };
+
+    quest_ops::delete_objective(ctx, objective.id);
+
+    responses::success(ctx, &format!("Objective removed from {}.", quest.title)).await
+}
+
+async fn set_completed(
+    ctx: Context<'_>,
+    campaign: String,
+    quest: String,
+    objective: String,
+    completed: bool,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (campaign, quest) = match find_quest(ctx, &campaign, &quest).await? {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    let objective = match quest_ops::get_objective(ctx, quest.id, &objective) {
+        Some(objective) => objective,
+        None => return responses::failure(ctx, "Objective not found.").await,
+    };
+
+    if objective.completed == completed {
+        return responses::failure(
+            ctx,
+            if completed {
+                "That objective is already checked off."
+            } else {
+                "That objective isn't checked off."
+            },
+        )
+        .await;
+    }
+
+    quest_ops::set_objective_completed(ctx, objective.id, completed);
+
+    let objectives = quest_ops::get_objectives(ctx, &[quest.id]);
+    let done = objectives.iter().filter(|o| o.completed).count();
+
+    let mut msg = format!(
+        "📜 **{}** ({}/{})\n{}",
+        quest.title,
+        done,
+        objectives.len(),
+        objectives
+            .iter()
+            .find(|o| o.id == objective.id)
+            .map(objective_line)
+            .unwrap_or_default()
+    );
+
+    if completed && done == objectives.len() {
+        msg.push_str("\nEvery objective of this quest is done!");
+    }
+
+    threads::post_to_campaign_channel(ctx.http(), &campaign, &msg).await;
+
+    responses::success(
+        ctx,
+        &format!(
+            "Objective {} in {}.",
+            if completed {
+                "checked off"
+            } else {
+                "unchecked"
+            },
+            quest.title
+        ),
+    )
+    .await
+}
+
+/// Finds a quest for a command that changes it, replying with why if it can't
+async fn find_quest(
+    ctx: Context<'_>,
+    campaign_name: &str,
+    quest_title: &str,
+) -> Result<Option<(Campaign, Quest)>, Error> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(campaign_name.to_string()),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign_name),
+            )
+            .await?;
+            return Ok(None);
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(None);
+    }
+
+    match quest_ops::get_quest(ctx, campaign.id, quest_title) {
+        Some(quest) => Ok(Some((campaign, quest))),
+        None => {
+            responses::failure(ctx, &format!("Quest {} does not exist.", quest_title)).await?;
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,671 @@
+use super::members::{MEMBER_ROLE_PLAYER, MEMBER_STATUS_ACTIVE};
+use crate::{
+    models::{Campaign, NewQuestionnaireQuestion, QuestionnaireAnswer, QuestionnaireQuestion},
+    ops::{campaign_ops, member_ops, questionnaire_ops},
+    responses,
+    utils::{
+        autocompletes::{autocomplete_campaign, autocomplete_question},
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+    },
+    ApplicationContext, Context, Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+// Discord allows five inputs per modal, so longer questionnaires are split into pages
+const QUESTIONS_PER_PAGE: usize = 5;
+const MAX_QUESTIONS: usize = 25;
+const MAX_ANSWER_LENGTH: u16 = 1000;
+const MAX_FIELD_LENGTH: usize = 1024;
+const EMBED_TEXT_LIMIT: usize = 5000;
+const PAGE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(900);
+
+/// Prompt, placeholder, paragraph and required flags of the questions added by `defaults`
+const DEFAULT_QUESTIONS: [(&str, &str, bool, bool); 5] = [
+    (
+        "What tone do you enjoy in a campaign?",
+        "E.g. lighthearted, heroic, grim, horror, political intrigue",
+        true,
+        true,
+    ),
+    (
+        "When are you usually available to play?",
+        "Days, times and your timezone",
+        true,
+        true,
+    ),
+    (
+        "Any content you want kept out of the game?",
+        "You can also submit lines and veils anonymously with /campaign safety",
+        true,
+        false,
+    ),
+    (
+        "What's your character concept?",
+        "Race, class, background, or just a vibe",
+        true,
+        true,
+    ),
+    ("Anything else the DM should know?", "", true, false),
+];
+
+/// One page of the questionnaire, built at runtime since the questions are set by the DM
+struct QuestionnairePage {
+    title: String,
+    questions: Vec<QuestionnaireQuestion>,
+    answers: Vec<Option<String>>,
+}
+
+impl poise::Modal for QuestionnairePage {
+    fn create(defaults: Option<Self>, custom_id: String) -> serenity::CreateInteractionResponse {
+        let (title, components) = match defaults {
+            Some(page) => {
+                let components = page
+                    .questions
+                    .iter()
+                    .zip(page.answers.iter())
+                    .enumerate()
+                    .map(|(index, (question, answer))| {
+                        let style = if question.paragraph {
+                            serenity::InputTextStyle::Paragraph
+                        } else {
+                            serenity::InputTextStyle::Short
+                        };
+
+                        let mut input = serenity::CreateInputText::new(
+                            style,
+                            &question.prompt,
+                            index.to_string(),
+                        )
+                        .required(question.required)
+                        .max_length(MAX_ANSWER_LENGTH);
+
+                        if let Some(placeholder) = &question.placeholder {
+                            input = input.placeholder(placeholder);
+                        }
+
+                        if let Some(answer) = answer {
+                            input = input.value(answer);
+                        }
+
+                        serenity::CreateActionRow::InputText(input)
+                    })
+                    .collect();
+
+                (page.title, components)
+            }
+            None => ("Session zero".to_string(), vec![]),
+        };
+
+        serenity::CreateInteractionResponse::Modal(
+            serenity::CreateModal::new(custom_id, title).components(components),
+        )
+    }
+
+    fn parse(mut data: serenity::ModalInteractionData) -> Result<Self, &'static str> {
+        let answers = (0..data.components.len())
+            .map(|index| {
+                poise::find_modal_text(&mut data, &index.to_string())
+                    .map(|answer| answer.trim().to_string())
+                    .filter(|answer| !answer.is_empty())
+            })
+            .collect();
+
+        Ok(Self {
+            title: String::new(),
+            questions: vec![],
+            answers,
+        })
+    }
+}
+
+/// Session zero questionnaires for D&D campaigns (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("add", "remove", "defaults", "list", "answer", "overview"),
+    subcommand_required
+)]
+pub async fn questionnaire(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Adds a question to the session zero questionnaire of a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The question to ask"]
+    #[max_length = 45]
+    prompt: String,
+    #[description = "An example answer shown in the empty text box"]
+    #[max_length = 100]
+    placeholder: Option<String>,
+    #[description = "Use a multi-line text box. Defaults to true"] paragraph: Option<bool>,
+    #[description = "Whether players must answer it. Defaults to true"] required: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let prompt = prompt.trim();
+
+    if prompt.is_empty() {
+        return responses::failure(ctx, "The question can't be empty.").await;
+    }
+
+    if questionnaire_ops::get_questions(ctx, campaign.id).len() >= MAX_QUESTIONS {
+        return responses::failure(
+            ctx,
+            &format!(
+                "A questionnaire can have at most {} questions.",
+                MAX_QUESTIONS
+            ),
+        )
+        .await;
+    }
+
+    let added = questionnaire_ops::add_questions(
+        ctx,
+        vec![NewQuestionnaireQuestion {
+            campaign_id: campaign.id,
+            position: questionnaire_ops::get_next_position(ctx, campaign.id),
+            prompt,
+            placeholder: placeholder
+                .as_deref()
+                .map(str::trim)
+                .filter(|p| !p.is_empty()),
+            paragraph: paragraph.unwrap_or(true),
+            required: required.unwrap_or(true),
+            created_date: chrono::Utc::now().naive_utc(),
+        }],
+    );
+
+    if added == 0 {
+        return responses::failure(ctx, "That question is already in the questionnaire.").await;
+    }
+
+    responses::success(
+        ctx,
+        &format!("Question added to the {} questionnaire.", campaign.name),
+    )
+    .await
+}
+
+/// Removes a question and its answers from the questionnaire of a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The question to remove"]
+    #[autocomplete = "autocomplete_question"]
+    prompt: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    if !questionnaire_ops::delete_question(ctx, campaign.id, &prompt) {
+        return responses::failure(ctx, "Question not found.").await;
+    }
+
+    responses::success(ctx, "Question removed.").await
+}
+
+/// Adds questions on tone, availability, content limits and character concept (DMs only)
+#[poise::command(slash_command)]
+pub async fn defaults(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let existing = questionnaire_ops::get_questions(ctx, campaign.id).len();
+
+    if existing + DEFAULT_QUESTIONS.len() > MAX_QUESTIONS {
+        return responses::failure(
+            ctx,
+            &format!(
+                "A questionnaire can have at most {} questions.",
+                MAX_QUESTIONS
+            ),
+        )
+        .await;
+    }
+
+    let start = questionnaire_ops::get_next_position(ctx, campaign.id);
+    let now = chrono::Utc::now().naive_utc();
+
+    let questions = DEFAULT_QUESTIONS
+        .iter()
+        .zip(start..)
+        .map(
+            |(&(prompt, placeholder, paragraph, required), position)| NewQuestionnaireQuestion {
+                campaign_id: campaign.id,
+                position,
+                prompt,
+                placeholder: Some(placeholder).filter(|p| !p.is_empty()),
+                paragraph,
+                required,
+                created_date: now,
+            },
+        )
+        .collect();
+
+    let added = questionnaire_ops::add_questions(ctx, questions);
+
+    responses::success(
+        ctx,
+        &format!(
+            "Added {} default question{} to the {} questionnaire.",
+            added,
+            if added == 1 { "" } else { "s" },
+            campaign.name
+        ),
+    )
+    .await
+}
+
+/// Lists the questions of a D&D campaign's session zero questionnaire
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let questions = questionnaire_ops::get_questions(ctx, campaign.id);
+
+    if questions.is_empty() {
+        return responses::failure(ctx, "This campaign has no questionnaire yet.").await;
+    }
+
+    let lines = questions
+        .iter()
+        .enumerate()
+        .map(|(index, question)| {
+            format!(
+                "{}. {}{}",
+                index + 1,
+                question.prompt,
+                if question.required {
+                    ""
+                } else {
+                    " *(optional)*"
+                }
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} session zero questionnaire", campaign.name))
+        .description(lines.join("\n"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Answer it with /campaign questionnaire answer",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Answers the session zero questionnaire of a D&D campaign
+///
+/// Earlier answers are filled in, so it can also be used to change them
+#[poise::command(slash_command)]
+pub async fn answer(
+    ctx: ApplicationContext<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    let campaign = match find_campaign(poise::Context::Application(ctx), &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    if member_ops::get_member(poise::Context::Application(ctx), campaign.id, user_id)
+        .is_none_or(|member| member.status != MEMBER_STATUS_ACTIVE)
+    {
+        return responses::failure(
+            poise::Context::Application(ctx),
+            "You're not a member of this campaign.",
+        )
+        .await;
+    }
+
+    let questions = questionnaire_ops::get_questions(poise::Context::Application(ctx), campaign.id);
+
+    if questions.is_empty() {
+        return responses::failure(
+            poise::Context::Application(ctx),
+            "This campaign has no questionnaire yet.",
+        )
+        .await;
+    }
+
+    let question_ids = questions.iter().map(|q| q.id).collect::<Vec<i32>>();
+    let previous = questionnaire_ops::get_answers(
+        poise::Context::Application(ctx),
+        &question_ids,
+        Some(user_id),
+    );
+
+    let pages = questions.chunks(QUESTIONS_PER_PAGE).collect::<Vec<_>>();
+    let mut press: Option<serenity::ComponentInteraction> = None;
+
+    for (index, questions) in pages.iter().enumerate() {
+        let page = QuestionnairePage {
+            title: if pages.len() == 1 {
+                "Session zero".to_string()
+            } else {
+                format!("Session zero ({}/{})", index + 1, pages.len())
+            },
+            questions: questions.to_vec(),
+            answers: questions
+                .iter()
+                .map(|question| {
+                    previous
+                        .iter()
+                        .find(|a| a.question_id == question.id)
+                        .map(|a| a.answer.clone())
+                })
+                .collect(),
+        };
+
+        // A modal has to be the response to an interaction, so pages after the first are opened
+        // from a button
+        let submitted = match press.take() {
+            None => poise::execute_modal(ctx, Some(page), Some(PAGE_TIMEOUT)).await?,
+            Some(press) => {
+                poise::execute_modal_on_component_interaction(
+                    ctx,
+                    press,
+                    Some(page),
+                    Some(PAGE_TIMEOUT),
+                )
+                .await?
+            }
+        };
+
+        let submitted = match submitted {
+            Some(submitted) => submitted,
+            None => return Ok(()),
+        };
+
+        let answers = questions
+            .iter()
+            .zip(submitted.answers)
+            .map(|(question, answer)| (question.id, answer))
+            .collect::<Vec<_>>();
+
+        questionnaire_ops::save_answers(poise::Context::Application(ctx), user_id, &answers);
+
+        if index + 1 == pages.len() {
+            break;
+        }
+
+        let continue_id = format!("{}_questionnaire_{}", ctx.id(), index + 1);
+        let handle = ctx
+            .send(
+                poise::CreateReply::default()
+                    .content(format!("Page {} of {} saved.", index + 1, pages.len()))
+                    .components(vec![serenity::CreateActionRow::Buttons(vec![
+                        serenity::CreateButton::new(&continue_id).label("Continue"),
+                    ])])
+                    .ephemeral(true),
+            )
+            .await?;
+
+        let author_id = ctx.author().id;
+
+        press = serenity::collector::ComponentInteractionCollector::new(ctx)
+            .filter(move |press| press.user.id == author_id && press.data.custom_id == continue_id)
+            .timeout(PAGE_TIMEOUT)
+            .await;
+
+        handle
+            .edit(
+                poise::Context::Application(ctx),
+                poise::CreateReply::default().components(vec![]),
+            )
+            .await?;
+
+        if press.is_none() {
+            return Ok(());
+        }
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Thanks! Your answers for {} have been saved.",
+                campaign.name
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Shows the compiled questionnaire answers of a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn overview(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Only show this player's answers"] player: Option<serenity::User>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let questions = questionnaire_ops::get_questions(ctx, campaign.id);
+
+    if questions.is_empty() {
+        return responses::failure(ctx, "This campaign has no questionnaire yet.").await;
+    }
+
+    let player_id = match &player {
+        Some(player) => Some(user_id_to_i64(player.id).await),
+        None => None,
+    };
+
+    let question_ids = questions.iter().map(|q| q.id).collect::<Vec<i32>>();
+    let answers = questionnaire_ops::get_answers(ctx, &question_ids, player_id);
+
+    let mut respondents = answers.iter().map(|a| a.user_id).collect::<Vec<i64>>();
+    respondents.dedup();
+
+    let mut embeds = vec![];
+
+    if player_id.is_none() {
+        let pending = member_ops::get_member_ids(ctx.data(), campaign.id, &[MEMBER_ROLE_PLAYER])
+            .into_iter()
+            .filter(|id| !respondents.contains(id))
+            .map(|id| format!("<@{}>", id))
+            .collect::<Vec<String>>();
+
+        embeds.push(
+            serenity::CreateEmbed::new()
+                .title(format!("{} session zero", campaign.name))
+                .field("Answered", respondents.len().to_string(), true)
+                .field(
+                    "Still to answer",
+                    if pending.is_empty() {
+                        "Nobody".to_string()
+                    } else {
+                        fit_field(&pending.join(", "))
+                    },
+                    true,
+                ),
+        );
+    } else if respondents.is_empty() {
+        return responses::failure(ctx, "That player hasn't answered the questionnaire yet.").await;
+    }
+
+    for respondent in respondents {
+        embeds.extend(respondent_embeds(
+            &campaign, &questions, &answers, respondent,
+        ));
+    }
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
+/// Compiles one player's answers, splitting them over several embeds when they're long
+fn respondent_embeds(
+    campaign: &Campaign,
+    questions: &[QuestionnaireQuestion],
+    answers: &[QuestionnaireAnswer],
+    respondent: i64,
+) -> Vec<serenity::CreateEmbed> {
+    let mut embeds = vec![];
+    let mut embed = new_respondent_embed(campaign, respondent);
+    let mut length = 0;
+    let mut fields = 0;
+
+    for question in questions {
+        let value = answers
+            .iter()
+            .find(|a| a.question_id == question.id && a.user_id == respondent)
+            .map_or("*No answer*".to_string(), |a| fit_field(&a.answer));
+
+        if fields > 0 && length + question.prompt.len() + value.len() > EMBED_TEXT_LIMIT {
+            embeds.push(embed);
+            embed = new_respondent_embed(campaign, respondent);
+            length = 0;
+            fields = 0;
+        }
+
+        length += question.prompt.len() + value.len();
+        fields += 1;
+        embed = embed.field(&question.prompt, value, false);
+    }
+
+    embeds.push(embed);
+    embeds
+}
+
+/// Shortens text to fit in an embed field
+fn fit_field(text: &str) -> String {
+    if text.chars().count() <= MAX_FIELD_LENGTH {
+        return text.to_string();
+    }
+
+    let mut value = text.chars().take(MAX_FIELD_LENGTH - 1).collect::<String>();
+    value.push('…');
+    value
+}
+
+fn new_respondent_embed(campaign: &Campaign, respondent: i64) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new()
+        .title(format!("{} session zero", campaign.name))
+        .description(format!("Answers from <@{}>", respondent))
+}
+
+/// A reminder to append to a new player's welcome message when the campaign has a questionnaire
+pub fn join_prompt(data: &Data, campaign: &Campaign) -> String {
+    if questionnaire_ops::has_questions(data, campaign.id) {
+        format!(
+            "\nPlease answer its session zero questionnaire with `/campaign questionnaire answer {}`.",
+            campaign.name
+        )
+    } else {
+        String::new()
+    }
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
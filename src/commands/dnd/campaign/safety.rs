@@ -0,0 +1,279 @@
+use super::members::MEMBER_ROLE_CO_DM;
+use crate::{
+    models::{Campaign, NewSafetyEntry, SafetyEntry},
+    ops::{campaign_ops, member_ops, safety_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign,
+        checks,
+        guilds::get_guild_id,
+        id::{guild_id_to_i64, user_id_to_i64},
+        notifications,
+    },
+    Context, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const SAFETY_KIND_LINE: i16 = 0;
+pub const SAFETY_KIND_VEIL: i16 = 1;
+
+#[derive(poise::ChoiceParameter)]
+enum SafetyKindChoice {
+    #[name = "Line (keep it out of the game)"]
+    Line,
+    #[name = "Veil (happens off-screen)"]
+    Veil,
+}
+
+impl SafetyKindChoice {
+    fn to_kind(&self) -> i16 {
+        match self {
+            SafetyKindChoice::Line => SAFETY_KIND_LINE,
+            SafetyKindChoice::Veil => SAFETY_KIND_VEIL,
+        }
+    }
+}
+
+/// Anonymously asks the DM to move on from the current scene
+///
+/// Only the DMs are told, and never who used it
+#[poise::command(slash_command, check = "checks::dnd_check", category = "D&D")]
+pub async fn xcard(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Also post a neutral message in this channel. Defaults to false"]
+    announce: Option<bool>,
+    #[description = "Anything you want the DM to know. It's sent anonymously"] note: Option<String>,
+) -> Result<(), Error> {
+    // Safety tools must stay anonymous, so nothing here may store or log `ctx.author()`
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !is_member(ctx, &campaign).await {
+        return responses::failure(ctx, "Only players in this campaign can use the X-card.").await;
+    }
+
+    let mut dm_ids = member_ops::get_member_ids(ctx.data(), campaign.id, &[MEMBER_ROLE_CO_DM]);
+    dm_ids.push(campaign.dm_id);
+
+    let mut msg = format!(
+        "🛑 Someone used the X-card in {}. Please move on from the current scene.",
+        campaign.name
+    );
+
+    if let Some(note) = note.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        msg.push_str(&format!("\n> {}", note.replace('\n', "\n> ")));
+    }
+
+    notifications::notify_users(ctx.serenity_context(), &dm_ids, &msg).await;
+
+    if announce.unwrap_or(false) {
+        // Sent as a new message rather than a reply, which would show who used the command
+        ctx.channel_id()
+            .send_message(
+                ctx,
+                serenity::CreateMessage::new()
+                    .content("🛑 The X-card has been used. Let's move on, no explanation needed.")
+                    .allowed_mentions(serenity::CreateAllowedMentions::new()),
+            )
+            .await?;
+    }
+
+    responses::success(ctx, "The DM has been told anonymously.").await
+}
+
+/// Lines and veils for a D&D campaign (subcommand required)
+#[poise::command(
+    slash_command,
+    subcommands("submit", "review", "remove"),
+    subcommand_required
+)]
+pub async fn safety(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Anonymously adds a line or veil to a D&D campaign
+///
+/// Lines are kept out of the game entirely, veils only happen off-screen
+#[poise::command(slash_command)]
+pub async fn submit(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "Whether it's a line or a veil"] kind: SafetyKindChoice,
+    #[description = "The topic, e.g. harm to animals"]
+    #[max_length = 200]
+    topic: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !is_member(ctx, &campaign).await {
+        return responses::failure(
+            ctx,
+            "Only players in this campaign can submit lines and veils.",
+        )
+        .await;
+    }
+
+    let topic = topic.trim();
+
+    if topic.is_empty() {
+        return responses::failure(ctx, "The topic can't be empty.").await;
+    }
+
+    // Already-listed topics are reported the same way, so nobody learns what others submitted
+    safety_ops::add_entry(
+        ctx,
+        NewSafetyEntry {
+            campaign_id: campaign.id,
+            kind: kind.to_kind(),
+            topic,
+            created_date: chrono::Utc::now().date_naive(),
+        },
+    );
+
+    responses::success(
+        ctx,
+        &format!(
+            "Submitted anonymously. The DM of {} can review it.",
+            campaign.name
+        ),
+    )
+    .await
+}
+
+/// Reviews the lines and veils of a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn review(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let entries = safety_ops::get_entries(ctx, campaign.id);
+
+    if entries.is_empty() {
+        return responses::failure(ctx, "No lines or veils have been submitted.").await;
+    }
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("{} lines and veils", campaign.name))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Remove an entry with /campaign safety remove",
+        ));
+
+    for (kind, name) in [(SAFETY_KIND_LINE, "Lines"), (SAFETY_KIND_VEIL, "Veils")] {
+        let lines = entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(entry_line)
+            .collect::<Vec<String>>();
+
+        if !lines.is_empty() {
+            embed = embed.field(name, lines.join("\n"), false);
+        }
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Removes a line or veil from a D&D campaign (DMs only)
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The name of the campaign"]
+    #[autocomplete = "autocomplete_campaign"]
+    campaign: String,
+    #[description = "The ID of the entry, as shown by /campaign safety review"] entry_id: i32,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let campaign = match find_campaign(ctx, &campaign).await {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(
+                ctx,
+                &format!("Campaign with name {} does not exist.", campaign),
+            )
+            .await
+        }
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    if !safety_ops::delete_entry(ctx, campaign.id, entry_id) {
+        return responses::failure(ctx, "Entry not found.").await;
+    }
+
+    responses::success(ctx, &format!("Entry {} removed.", entry_id)).await
+}
+
+fn entry_line(entry: &SafetyEntry) -> String {
+    format!(
+        "`{}` {} ({})",
+        entry.id,
+        entry.topic,
+        entry.created_date.format("%Y-%m-%d")
+    )
+}
+
+/// Checks the author plays in or runs the campaign, without keeping any trace of who asked
+async fn is_member(ctx: Context<'_>, campaign: &Campaign) -> bool {
+    checks::is_campaign_member(ctx.data(), campaign, user_id_to_i64(ctx.author().id).await)
+}
+
+async fn find_campaign(ctx: Context<'_>, name: &str) -> Option<Campaign> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(name.to_string()),
+    )
+}
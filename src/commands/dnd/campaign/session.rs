@@ -1,30 +1,68 @@
+use super::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER};
 use crate::{
     models::{NewSession, Session},
-    ops::{campaign_ops, response_ops, session_ops},
+    ops::{calendar_ops, campaign_ops, member_ops, response_ops, session_ops},
     responses,
     utils::{
-        autocompletes::autocomplete_campaign,
+        autocompletes::{autocomplete_campaign, autocomplete_date},
+        calendar::format_span,
         checks,
-        date::{is_date_format_valid, is_date_in_future},
+        date::{get_long_date_short_time_timestamp, is_naive_date_in_future, parse_natural_date},
+        events,
         guilds::get_guild_id,
+        ics::build_calendar,
         id::{guild_id_to_i64, user_id_to_i64},
+        notifications, threads,
+        users::get_timezone,
     },
     Context, Error,
 };
 use diesel::prelude::*;
 use poise::serenity_prelude as serenity;
 
+pub mod poll;
+pub mod recap;
 pub mod response;
 
-const STATUS_PENDING: i16 = 0;
-const STATUS_CONFIRMED: i16 = 1;
-const STATUS_CANCELLED: i16 = 2;
+pub const STATUS_PENDING: i16 = 0;
+pub const STATUS_CONFIRMED: i16 = 1;
+pub const STATUS_CANCELLED: i16 = 2;
+pub const STATUS_COMPLETED: i16 = 3;
+pub const STATUS_POSTPONED: i16 = 4;
+
+const HISTORY_PAGE_SIZE: usize = 10;
+const HISTORY_RECAP_LENGTH: usize = 200;
 
 #[derive(poise::ChoiceParameter)]
 enum StatusChoice {
     Pending,
     Confirmed,
     Cancelled,
+    Completed,
+    Postponed,
+}
+
+impl StatusChoice {
+    fn to_status(&self) -> i16 {
+        match self {
+            StatusChoice::Pending => STATUS_PENDING,
+            StatusChoice::Confirmed => STATUS_CONFIRMED,
+            StatusChoice::Cancelled => STATUS_CANCELLED,
+            StatusChoice::Completed => STATUS_COMPLETED,
+            StatusChoice::Postponed => STATUS_POSTPONED,
+        }
+    }
+}
+
+pub fn get_status_name(status: i16) -> &'static str {
+    match status {
+        STATUS_PENDING => "Pending",
+        STATUS_CONFIRMED => "Confirmed",
+        STATUS_CANCELLED => "Cancelled",
+        STATUS_COMPLETED => "Completed",
+        STATUS_POSTPONED => "Postponed",
+        _ => "Unknown",
+    }
 }
 
 /// D&D Sessions (subcommand required)
@@ -36,9 +74,16 @@ enum StatusChoice {
         "cancel",
         "clear_all",
         "list",
+        "history",
         "set",
+        "ics",
+        "poll::poll",
+        "recap::recap",
+        "recap::notes",
         "response::respond",
-        "response::dmrespond"
+        "response::dmrespond",
+        "response::attendance",
+        "response::checkins"
     ),
     subcommand_required,
     check = "checks::dnd_check",
@@ -51,25 +96,29 @@ pub async fn session(_ctx: Context<'_>) -> Result<(), Error> {
 /// Creates a new D&D session (DMs only)
 ///
 /// The scheduled date must be in the future
-#[poise::command(slash_command, check = "checks::dm_check")]
+/// Dates can be absolute (YYYY-MM-DD HH:MM) or relative (e.g. next friday 7pm)
+#[poise::command(slash_command)]
 pub async fn create(
     ctx: Context<'_>,
     #[autocomplete = autocomplete_campaign]
     #[description = "Campaign to attribute the session to"]
     campaign: String,
     #[description = "Where to meet"] location: String,
-    #[description = "Date and time of the session (YYYY-MM-DD HH:MM)"] scheduled_date: String,
+    #[description = "Date and time of the session (e.g. YYYY-MM-DD HH:MM, next friday 7pm)"]
+    #[autocomplete = autocomplete_date]
+    scheduled_date: String,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
     let created_date = chrono::Utc::now().naive_utc();
 
-    if !is_date_format_valid(&scheduled_date) {
-        return responses::failure(ctx, "Invalid date format.").await;
-    }
+    let scheduled_date = match parse_natural_date(&scheduled_date, get_timezone(ctx).await) {
+        Some(scheduled_date) => scheduled_date,
+        None => return responses::failure(ctx, "Invalid date format.").await,
+    };
 
-    if !is_date_in_future(&scheduled_date) {
+    if !is_naive_date_in_future(scheduled_date) {
         return responses::failure(ctx, "Scheduled date must be in the future.").await;
     }
 
@@ -77,10 +126,17 @@ pub async fn create(
         return responses::failure(ctx, "Campaign not found.").await;
     }
 
-    let scheduled_date =
-        Some(chrono::NaiveDateTime::parse_from_str(&scheduled_date, "%Y-%m-%d %H:%M").unwrap());
+    let scheduled_date = Some(scheduled_date);
 
-    let campaign_id = campaign_ops::get_id_from_name(ctx, &campaign, guild_id).unwrap();
+    let campaign =
+        campaign_ops::get_campaign(ctx, guild_id, campaign_ops::CampaignFilters::Name(campaign))
+            .unwrap();
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let campaign_id = campaign.id;
 
     let new_session = NewSession {
         campaign_id,
@@ -89,19 +145,30 @@ pub async fn create(
         status: STATUS_PENDING,
         created_date,
         scheduled_date,
+        max_players: None,
     };
 
-    session_ops::create_session(ctx, new_session);
+    let session = session_ops::create_session(ctx, new_session);
+
+    let pings = member_pings(ctx, campaign_id);
 
-    responses::success(ctx, "Session created.").await
+    match publish_session(ctx, session).await {
+        Some(thread_id) => {
+            responses::success(
+                ctx,
+                &format!("Session created. Discuss it in <#{}>.{}", thread_id, pings),
+            )
+            .await
+        }
+        None => responses::success(ctx, &format!("Session created.{}", pings)).await,
+    }
 }
 
 /// Edits and existing session (DMs only)
 #[poise::command(
     slash_command,
-    subcommands("location", "date"),
-    subcommand_required,
-    check = "checks::dm_check"
+    subcommands("location", "date", "seats"),
+    subcommand_required
 )]
 pub async fn edit(_ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
@@ -120,6 +187,10 @@ pub async fn location(
         return responses::failure(ctx, "Session not found.").await;
     }
 
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
     let updated_session = Session {
         id: session_id,
         location: Some(location.clone()),
@@ -128,6 +199,8 @@ pub async fn location(
 
     session_ops::update_session(ctx, updated_session);
 
+    sync_event(ctx, session_ops::get_session(ctx, session_id).unwrap()).await;
+
     responses::success(
         ctx,
         &format!(
@@ -143,7 +216,9 @@ pub async fn location(
 pub async fn date(
     ctx: Context<'_>,
     #[description = "The ID of the session to edit"] session_id: i32,
-    #[description = "The new date (YYYY-MM-DD HH:MM)"] date: String,
+    #[description = "The new date (e.g. YYYY-MM-DD HH:MM, next friday 7pm)"]
+    #[autocomplete = autocomplete_date]
+    date: String,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -151,33 +226,89 @@ pub async fn date(
         return responses::failure(ctx, "Session not found.").await;
     }
 
-    if !is_date_format_valid(&date) {
-        return responses::failure(ctx, "Invalid date format.").await;
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
     }
 
-    if !is_date_in_future(&date) {
+    let date = match parse_natural_date(&date, get_timezone(ctx).await) {
+        Some(date) => date,
+        None => return responses::failure(ctx, "Invalid date format.").await,
+    };
+
+    if !is_naive_date_in_future(date) {
         return responses::failure(ctx, "Scheduled date must be in the future.").await;
     }
 
-    let date = chrono::NaiveDateTime::parse_from_str(&date, "%Y-%m-%d %H:%M").unwrap();
-
     let updated_session = Session {
         id: session_id,
         scheduled_date: Some(date),
+        quorum_notified: false,
         ..session_ops::get_session(ctx, session_id).unwrap()
     };
 
     session_ops::update_session(ctx, updated_session);
 
+    sync_event(ctx, session_ops::get_session(ctx, session_id).unwrap()).await;
+
     responses::success(
         ctx,
-        &format!("Date updated to {} for session ID {}", date, session_id,),
+        &format!(
+            "Date updated to {} for session ID {}",
+            get_long_date_short_time_timestamp(&date.format("%Y-%m-%d %H:%M").to_string())?,
+            session_id,
+        ),
+    )
+    .await
+}
+
+/// Edits the number of seats of an existing session (DMs only)
+///
+/// Players responding yes once every seat is taken are put on a waitlist
+#[poise::command(slash_command)]
+pub async fn seats(
+    ctx: Context<'_>,
+    #[description = "The ID of the session to edit"] session_id: i32,
+    #[description = "The maximum number of players. Leave empty for no limit"]
+    #[min = 1]
+    max_players: Option<i16>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if !session_ops::does_session_exist(ctx, session_id) {
+        return responses::failure(ctx, "Session not found.").await;
+    }
+
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let updated_session = Session {
+        id: session_id,
+        max_players,
+        ..session_ops::get_session(ctx, session_id).unwrap()
+    };
+
+    session_ops::update_session(ctx, updated_session);
+
+    let promoted = response_ops::promote_waitlisted(ctx.data(), session_id);
+
+    notifications::notify_promoted(ctx.serenity_context(), session_id, &promoted).await;
+
+    responses::success(
+        ctx,
+        &match max_players {
+            Some(max_players) => format!(
+                "Seats updated to {} for session ID {}",
+                max_players, session_id
+            ),
+            None => format!("Seat limit removed for session ID {}", session_id),
+        },
     )
     .await
 }
 
 /// Cancels a D&D session (DMs only)
-#[poise::command(slash_command, check = "checks::dm_check")]
+#[poise::command(slash_command)]
 pub async fn cancel(
     ctx: Context<'_>,
     #[description = "The ID of the session to cancel"] session_id: i32,
@@ -188,12 +319,26 @@ pub async fn cancel(
         return responses::failure(ctx, "Session not found.").await;
     }
 
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let session = session_ops::get_session(ctx, session_id).unwrap();
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    events::remove_session_event(ctx.http(), guild_id, &session).await;
+
+    threads::post_to_session_thread(ctx.http(), &session, "This session has been cancelled.").await;
+
     let updated_session = Session {
         id: session_id,
         status: STATUS_CANCELLED,
-        ..session_ops::get_session(ctx, session_id).unwrap()
+        event_id: None,
+        ..session
     };
 
+    threads::archive_session_thread(ctx.http(), &updated_session).await;
+
     session_ops::update_session(ctx, updated_session);
 
     responses::success(ctx, "Session cancelled.").await
@@ -258,10 +403,13 @@ pub async fn list(
         let campaign_name = result.1;
         let mut going: Vec<String> = vec![];
         let mut not_going: Vec<String> = vec![];
+        let mut waitlist: Vec<(chrono::NaiveDateTime, String)> = vec![];
 
-        if let Some(responses) = response_ops::get_responses_for_session(ctx, session.id) {
+        if let Some(responses) = response_ops::get_responses_for_session(ctx.data(), session.id) {
             responses.into_iter().for_each(|r| {
-                if r.response == 1 {
+                if r.response == 1 && r.waitlisted {
+                    waitlist.push((r.responded_date, format!("<@{}>", r.respondee_id)));
+                } else if r.response == 1 {
                     going.push(format!("<@{}>", r.respondee_id));
                 } else if r.response == 0 {
                     not_going.push(format!("<@{}>", r.respondee_id));
@@ -269,13 +417,15 @@ pub async fn list(
             });
         }
 
-        let status = match session.status {
-            0 => String::from("Pending"),
-            1 => String::from("Confirmed"),
-            2 => String::from("Cancelled"),
-            _ => String::from("Unknown"),
+        waitlist.sort();
+
+        let seats = match session.max_players {
+            Some(max_players) => format!("{}/{}", going.len(), max_players),
+            None => "Unlimited".to_string(),
         };
 
+        let status = get_status_name(session.status);
+
         let location = match session.location {
             Some(location) => location,
             None => "None".to_string(),
@@ -309,6 +459,19 @@ pub async fn list(
                     },
                     false,
                 )
+                .field("Seats", seats, true)
+                .field(
+                    "Waitlist",
+                    match waitlist.is_empty() {
+                        true => "None".to_string(),
+                        false => waitlist
+                            .into_iter()
+                            .map(|(_, respondee)| respondee)
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                    },
+                    true,
+                )
                 .footer(serenity::CreateEmbedFooter::new(format!(
                     "Session ID: {} | Created at {}",
                     session.id, session.created_date
@@ -319,8 +482,118 @@ pub async fn list(
     responses::paginate_embeds(ctx, embeds).await
 }
 
+/// Lists past D&D sessions of a campaign
+///
+/// Optionally filtered by status and by a date range (YYYY-MM-DD)
+#[poise::command(slash_command)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[autocomplete = autocomplete_campaign]
+    #[description = "Campaign"]
+    campaign: String,
+    #[description = "Only show sessions with this status"] status: Option<StatusChoice>,
+    #[description = "Only show sessions on or after this date (YYYY-MM-DD)"] from: Option<String>,
+    #[description = "Only show sessions on or before this date (YYYY-MM-DD)"] to: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    if !campaign_ops::does_campaign_exist(ctx, &campaign, guild_id) {
+        return responses::failure(ctx, "Campaign not found.").await;
+    }
+
+    let from = match from.as_deref().map(parse_filter_date) {
+        Some(None) => return responses::failure(ctx, "Invalid date format.").await,
+        Some(Some(from)) => Some(from.and_hms_opt(0, 0, 0).unwrap()),
+        None => None,
+    };
+
+    let to = match to.as_deref().map(parse_filter_date) {
+        Some(None) => return responses::failure(ctx, "Invalid date format.").await,
+        Some(Some(to)) => Some(to.and_hms_opt(23, 59, 59).unwrap()),
+        None => None,
+    };
+
+    let campaign_id = campaign_ops::get_id_from_name(ctx, &campaign, guild_id).unwrap();
+    let sessions = session_ops::get_session_history(
+        ctx,
+        campaign_id,
+        status.map(|status| status.to_status()),
+        from,
+        to,
+    )
+    .unwrap_or_default();
+    let calendar = calendar_ops::get_calendar(ctx, campaign_id);
+
+    let completed = sessions
+        .iter()
+        .filter(|session| session.status == STATUS_COMPLETED)
+        .count();
+    let pages = sessions.len().div_ceil(HISTORY_PAGE_SIZE);
+    let mut embeds: Vec<serenity::CreateEmbed> = vec![];
+
+    for (page, chunk) in sessions.chunks(HISTORY_PAGE_SIZE).enumerate() {
+        let mut lines: Vec<String> = vec![];
+
+        for session in chunk {
+            let going = response_ops::get_responses_for_session(ctx.data(), session.id)
+                .unwrap_or_default()
+                .iter()
+                .filter(|r| r.response == 1 && !r.waitlisted)
+                .count();
+
+            lines.push(format!(
+                "**#{}** {} | {} | {} | {} going",
+                session.id,
+                get_long_date_short_time_timestamp(
+                    &session
+                        .scheduled_date
+                        .unwrap()
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                )?,
+                get_status_name(session.status),
+                session.location.as_deref().unwrap_or("None"),
+                going
+            ));
+
+            if let (Some(calendar), Some(start), Some(end)) =
+                (&calendar, session.ingame_start_day, session.ingame_end_day)
+            {
+                lines.push(format!("🗓️ {}", format_span(calendar, start, end)));
+            }
+
+            if let Some(recap) = &session.recap {
+                let mut excerpt = recap.chars().take(HISTORY_RECAP_LENGTH).collect::<String>();
+
+                if excerpt.len() < recap.len() {
+                    excerpt.push('…');
+                }
+
+                lines.push(format!("> {}", excerpt.replace('\n', " ")));
+            }
+        }
+
+        embeds.push(
+            serenity::CreateEmbed::default()
+                .title(format!("{} history", campaign))
+                .description(lines.join("\n"))
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {}/{} | {} sessions | {} completed",
+                    page + 1,
+                    pages,
+                    sessions.len(),
+                    completed
+                ))),
+        );
+    }
+
+    responses::paginate_embeds(ctx, embeds).await
+}
+
 /// Sets the status of an existing session (DMs only)
-#[poise::command(slash_command, check = "checks::dm_check")]
+#[poise::command(slash_command)]
 pub async fn set(
     ctx: Context<'_>,
     #[description = "The ID of the session to edit"] session_id: i32,
@@ -332,23 +605,168 @@ pub async fn set(
         return responses::failure(ctx, "Session not found.").await;
     }
 
-    let status = match status {
-        StatusChoice::Pending => STATUS_PENDING,
-        StatusChoice::Confirmed => STATUS_CONFIRMED,
-        StatusChoice::Cancelled => STATUS_CANCELLED,
-    };
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let status = status.to_status();
+
+    let mut session = session_ops::get_session(ctx, session_id).unwrap();
+
+    if status == STATUS_CANCELLED {
+        let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+        events::remove_session_event(ctx.http(), guild_id, &session).await;
+        session.event_id = None;
+    }
 
     let updated_session = Session {
         id: session_id,
         status,
-        ..session_ops::get_session(ctx, session_id).unwrap()
+        ..session
     };
 
+    if status == STATUS_COMPLETED || status == STATUS_CANCELLED {
+        threads::archive_session_thread(ctx.http(), &updated_session).await;
+    }
+
     session_ops::update_session(ctx, updated_session);
 
     responses::success(
         ctx,
-        &format!("Status updated to {} for session ID {}", status, session_id),
+        &format!(
+            "Status updated to {} for session ID {}",
+            get_status_name(status),
+            session_id
+        ),
     )
     .await
 }
+
+/// Exports upcoming sessions of a campaign as an iCalendar (.ics) file
+///
+/// Also links the campaign's calendar feed, which calendar apps can subscribe to
+#[poise::command(slash_command)]
+pub async fn ics(
+    ctx: Context<'_>,
+    #[autocomplete = autocomplete_campaign]
+    #[description = "Campaign"]
+    campaign: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(campaign),
+    ) {
+        Some(campaign) => campaign,
+        None => return responses::failure(ctx, "Campaign not found.").await,
+    };
+
+    let sessions =
+        session_ops::get_sessions_since(ctx.data(), campaign.id, chrono::Utc::now().naive_utc())
+            .unwrap_or_default();
+
+    let content = match &ctx.data().public_url {
+        Some(public_url) => format!(
+            "Subscribe to {} at {}/calendars/{}.ics",
+            campaign.name,
+            public_url.trim_end_matches('/'),
+            campaign.calendar_token
+        ),
+        None => format!("Upcoming sessions for {}.", campaign.name),
+    };
+
+    let reply = poise::CreateReply::default().content(content).attachment(
+        serenity::CreateAttachment::bytes(
+            build_calendar(&campaign, &sessions).into_bytes(),
+            format!("{}.ics", campaign.name.replace(char::is_whitespace, "_")),
+        ),
+    );
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+fn parse_filter_date(date: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+}
+
+/// Mentions a campaign's players and co-DMs so they hear about a new session
+pub fn member_pings(ctx: Context<'_>, campaign_id: i32) -> String {
+    let member_ids = member_ops::get_member_ids(
+        ctx.data(),
+        campaign_id,
+        &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM],
+    );
+
+    if member_ids.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        "\n{}",
+        member_ids
+            .iter()
+            .map(|member_id| format!("<@{}>", member_id))
+            .collect::<Vec<String>>()
+            .join(" ")
+    )
+}
+
+/// Mirrors a newly created session into a scheduled event and a discussion thread
+///
+/// Returns the ID of the thread, if one was created
+async fn publish_session(ctx: Context<'_>, session: Session) -> Option<i64> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Id(session.campaign_id),
+    )?;
+
+    let event_id = events::sync_session_event(ctx.http(), &campaign, &session).await;
+    let thread_id = threads::create_session_thread(ctx.http(), &campaign, &session).await;
+
+    session_ops::update_session(
+        ctx,
+        Session {
+            event_id,
+            thread_id,
+            ..session
+        },
+    );
+
+    thread_id
+}
+
+/// Mirrors a session into the guild's scheduled events, storing the event ID if it changed
+async fn sync_event(ctx: Context<'_>, session: Session) {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Id(session.campaign_id),
+    ) {
+        Some(campaign) => campaign,
+        None => return,
+    };
+
+    let event_id = events::sync_session_event(ctx.http(), &campaign, &session).await;
+
+    if event_id != session.event_id {
+        session_ops::update_session(
+            ctx,
+            Session {
+                event_id,
+                ..session
+            },
+        );
+    }
+}
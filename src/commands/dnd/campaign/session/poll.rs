@@ -0,0 +1,387 @@
+use crate::commands::dnd::campaign::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER};
+use crate::{
+    models::{NewPoll, NewPollVote, NewResponse, NewSession, Poll, PollOption},
+    ops::{campaign_ops, member_ops, poll_ops, response_ops, session_ops},
+    responses,
+    utils::{
+        autocompletes::autocomplete_campaign,
+        checks,
+        date::{get_long_date_week_day_timestamp, is_naive_date_in_future, parse_natural_date},
+        guilds::get_guild_id,
+        id::{channel_id_to_i64, guild_id_to_i64, i64_to_channel_id, user_id_to_i64},
+        provisioning,
+        users::get_timezone,
+    },
+    Context, Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const VOTE_PREFIX: &str = "poll_vote_";
+const MAX_OPTIONS: usize = 25;
+
+/// Polls the party for a session date (subcommand required) (DMs only)
+#[poise::command(slash_command, subcommands("create", "close"), subcommand_required)]
+pub async fn poll(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Creates a poll of candidate session dates for the party to vote on (DMs only)
+///
+/// Options are separated by semicolons (e.g. fri 7pm; sat 8pm; 2025-03-14 19:30)
+#[poise::command(slash_command)]
+pub async fn create(
+    ctx: Context<'_>,
+    #[autocomplete = autocomplete_campaign]
+    #[description = "Campaign to attribute the session to"]
+    campaign: String,
+    #[description = "Where to meet"] location: String,
+    #[description = "Candidate dates separated by semicolons (e.g. fri 7pm; sat 8pm)"]
+    options: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = match campaign_ops::get_campaign(
+        ctx,
+        guild_id,
+        campaign_ops::CampaignFilters::Name(campaign),
+    ) {
+        Some(campaign) => campaign,
+        None => return responses::failure(ctx, "Campaign not found.").await,
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    let tz = get_timezone(ctx).await;
+    let mut scheduled_dates: Vec<chrono::NaiveDateTime> = vec![];
+
+    for option in options.split(';').map(str::trim).filter(|o| !o.is_empty()) {
+        let scheduled_date = match parse_natural_date(option, tz) {
+            Some(scheduled_date) => scheduled_date,
+            None => return responses::failure(ctx, &format!("Invalid date: {}", option)).await,
+        };
+
+        if !is_naive_date_in_future(scheduled_date) {
+            return responses::failure(ctx, &format!("Date must be in the future: {}", option))
+                .await;
+        }
+
+        if !scheduled_dates.contains(&scheduled_date) {
+            scheduled_dates.push(scheduled_date);
+        }
+    }
+
+    if scheduled_dates.is_empty() || scheduled_dates.len() > MAX_OPTIONS {
+        return responses::failure(
+            ctx,
+            &format!("Polls must have between 1 and {} options.", MAX_OPTIONS),
+        )
+        .await;
+    }
+
+    let new_poll = NewPoll {
+        campaign_id: campaign.id,
+        author_id: user_id_to_i64(ctx.author().id).await,
+        location: Some(&location),
+        closed: false,
+        created_date: chrono::Utc::now().naive_utc(),
+    };
+
+    let poll = poll_ops::create_poll(ctx.data(), new_poll, scheduled_dates);
+    let tally = poll_ops::get_poll_tally(ctx.data(), poll.id).unwrap_or_default();
+
+    let reply = poise::CreateReply::default()
+        .embed(build_poll_embed(&campaign.name, &poll, &tally)?)
+        .components(build_poll_components(&tally));
+
+    let message = ctx.send(reply).await?.into_message().await?;
+
+    let poll = Poll {
+        channel_id: Some(channel_id_to_i64(message.channel_id).await),
+        message_id: Some(i64::try_from(message.id.get()).expect("Failed to convert ID to i64")),
+        ..poll
+    };
+
+    poll_ops::update_poll(ctx.data(), poll);
+
+    Ok(())
+}
+
+/// Closes a poll and creates a session for the slot with the best attendance (DMs only)
+#[poise::command(slash_command)]
+pub async fn close(
+    ctx: Context<'_>,
+    #[description = "The ID of the poll to close"] poll_id: i32,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let (poll, campaign) = match poll_ops::get_poll(ctx.data(), poll_id).and_then(|poll| {
+        campaign_ops::get_campaign(
+            ctx,
+            guild_id,
+            campaign_ops::CampaignFilters::Id(poll.campaign_id),
+        )
+        .map(|campaign| (poll, campaign))
+    }) {
+        Some(result) => result,
+        None => return responses::failure(ctx, "Poll not found.").await,
+    };
+
+    if !checks::campaign_check(ctx, &campaign).await? {
+        return Ok(());
+    }
+
+    if poll.closed {
+        return responses::failure(ctx, "Poll is already closed.").await;
+    }
+
+    let tally = poll_ops::get_poll_tally(ctx.data(), poll.id).unwrap_or_default();
+
+    let (option, voters) = match get_best_option(&tally) {
+        Some(best) => best,
+        None => return responses::failure(ctx, "No votes have been cast yet.").await,
+    };
+
+    if !is_naive_date_in_future(option.scheduled_date) {
+        return responses::failure(ctx, "The winning date has already passed.").await;
+    }
+
+    // Claims the poll so closing it twice at once can't create two sessions
+    if !poll_ops::close_poll(ctx.data(), poll.id) {
+        return responses::failure(ctx, "Poll is already closed.").await;
+    }
+
+    let new_session = NewSession {
+        campaign_id: poll.campaign_id,
+        author_id: user_id_to_i64(ctx.author().id).await,
+        location: poll.location.as_deref(),
+        status: super::STATUS_PENDING,
+        created_date: chrono::Utc::now().naive_utc(),
+        scheduled_date: Some(option.scheduled_date),
+        max_players: None,
+    };
+
+    let session = session_ops::create_session(ctx, new_session);
+    let session_id = session.id;
+
+    super::publish_session(ctx, session).await;
+
+    for voter_id in voters {
+        provisioning::grant_campaign_role(ctx.http(), &campaign, *voter_id).await;
+
+        response_ops::create_response(
+            ctx.data(),
+            NewResponse {
+                session_id,
+                respondee_id: *voter_id,
+                response: 1,
+                responded_date: chrono::Utc::now().naive_utc(),
+            },
+        );
+    }
+
+    let embed = build_poll_embed(&campaign.name, &poll, &tally)?;
+    let (channel_id, message_id) = (poll.channel_id, poll.message_id);
+
+    if let (Some(channel_id), Some(message_id)) = (channel_id, message_id) {
+        if let Err(e) = i64_to_channel_id(channel_id)
+            .await
+            .edit_message(
+                ctx,
+                serenity::MessageId::new(u64::try_from(message_id)?),
+                serenity::EditMessage::new()
+                    .embed(embed.title(format!("{} (closed)", campaign.name)))
+                    .components(vec![]),
+            )
+            .await
+        {
+            println!("Failed to update poll message: {:?}", e);
+        }
+    }
+
+    responses::success(
+        ctx,
+        &format!(
+            "Poll closed. Session ID {} created for {}.{}",
+            session_id,
+            get_long_date_week_day_timestamp(
+                &option.scheduled_date.format("%Y-%m-%d %H:%M").to_string()
+            )?,
+            super::member_pings(ctx, campaign.id)
+        ),
+    )
+    .await
+}
+
+/// Toggles the voter's availability for a poll option
+pub async fn handle_vote(
+    ctx: &serenity::Context,
+    interaction: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let poll_option_id = match interaction
+        .data
+        .custom_id
+        .strip_prefix(VOTE_PREFIX)
+        .and_then(|id| id.parse::<i32>().ok())
+    {
+        Some(poll_option_id) => poll_option_id,
+        None => return Ok(()),
+    };
+
+    let poll = match poll_ops::get_poll_from_option(data, poll_option_id) {
+        Some(poll) => poll,
+        None => return Ok(()),
+    };
+
+    if poll.closed {
+        interaction
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content("This poll is closed.")
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let voter_id = user_id_to_i64(interaction.user.id).await;
+
+    if member_ops::has_roster(data, poll.campaign_id)
+        && !member_ops::get_member_ids(
+            data,
+            poll.campaign_id,
+            &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM],
+        )
+        .contains(&voter_id)
+    {
+        interaction
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(
+                            "Only players in this campaign can vote. Use `/campaign join` to ask to join.",
+                        )
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    poll_ops::toggle_poll_vote(
+        data,
+        NewPollVote {
+            poll_option_id,
+            voter_id,
+            voted_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    let campaign_name = poll_ops::get_poll_campaign_name(data, poll.id).unwrap_or_default();
+    let tally = poll_ops::get_poll_tally(data, poll.id).unwrap_or_default();
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new().embed(build_poll_embed(
+                    &campaign_name,
+                    &poll,
+                    &tally,
+                )?),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Picks the option with the most voters, preferring the earliest on ties
+fn get_best_option(tally: &[(PollOption, Vec<i64>)]) -> Option<&(PollOption, Vec<i64>)> {
+    tally
+        .iter()
+        .filter(|(_, voters)| !voters.is_empty())
+        .fold(None, |best, option| match best {
+            Some(best) if best.1.len() >= option.1.len() => Some(best),
+            _ => Some(option),
+        })
+}
+
+fn build_poll_embed(
+    campaign_name: &str,
+    poll: &Poll,
+    tally: &[(PollOption, Vec<i64>)],
+) -> Result<serenity::CreateEmbed, Error> {
+    let best_option_id = get_best_option(tally).map(|(option, _)| option.id);
+    let mut embed = serenity::CreateEmbed::default()
+        .title(campaign_name)
+        .description(format!(
+            "When can you make it? Toggle every slot that works for you.\nLocation: {}",
+            poll.location.as_deref().unwrap_or("None")
+        ))
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Poll ID: {} | Created at {}",
+            poll.id, poll.created_date
+        )));
+
+    for (index, (option, voters)) in tally.iter().enumerate() {
+        let name = match best_option_id == Some(option.id) {
+            true => format!("⭐ Option {} (best attendance)", index + 1),
+            false => format!("Option {}", index + 1),
+        };
+
+        let available = match voters.is_empty() {
+            true => "None".to_string(),
+            false => voters
+                .iter()
+                .map(|voter_id| format!("<@{}>", voter_id))
+                .collect::<Vec<String>>()
+                .join(", "),
+        };
+
+        embed = embed.field(
+            name,
+            format!(
+                "{}\n{} available: {}",
+                get_long_date_week_day_timestamp(
+                    &option.scheduled_date.format("%Y-%m-%d %H:%M").to_string()
+                )?,
+                voters.len(),
+                available
+            ),
+            false,
+        );
+    }
+
+    Ok(embed)
+}
+
+fn build_poll_components(tally: &[(PollOption, Vec<i64>)]) -> Vec<serenity::CreateActionRow> {
+    tally
+        .chunks(5)
+        .enumerate()
+        .map(|(row, options)| {
+            serenity::CreateActionRow::Buttons(
+                options
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (option, _))| {
+                        serenity::CreateButton::new(format!("{}{}", VOTE_PREFIX, option.id))
+                            .label(format!("{}", row * 5 + index + 1))
+                            .style(serenity::ButtonStyle::Secondary)
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
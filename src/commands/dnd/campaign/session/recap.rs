@@ -0,0 +1,144 @@
+use crate::commands::dnd::campaign::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER};
+use crate::ops::{campaign_ops, member_ops, session_ops};
+use crate::utils::{
+    checks,
+    guilds::get_guild_id,
+    id::{guild_id_to_i64, user_id_to_i64},
+};
+use crate::{responses, ApplicationContext, Context, Error};
+
+#[derive(Debug, poise::Modal)]
+#[name = "Session Recap"]
+struct RecapModal {
+    #[name = "Recap"]
+    #[paragraph]
+    #[max_length = 4000]
+    recap: String,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Session Recap"]
+struct DmRecapModal {
+    #[name = "Recap"]
+    #[paragraph]
+    #[max_length = 4000]
+    recap: Option<String>,
+    #[name = "DM Notes (only visible to DMs)"]
+    #[paragraph]
+    #[max_length = 4000]
+    dm_notes: Option<String>,
+}
+
+/// Writes the recap of a D&D session
+///
+/// DMs can also keep private notes on the session
+/// Once a campaign has members, only its players and co-DMs can write recaps
+#[poise::command(slash_command)]
+pub async fn recap(
+    ctx: ApplicationContext<'_>,
+    #[description = "The ID of the session to recap"] session_id: i32,
+) -> Result<(), Error> {
+    use poise::Modal as _;
+
+    let session = match session_ops::get_session(poise::Context::Application(ctx), session_id) {
+        Some(session) => session,
+        None => {
+            return responses::failure(poise::Context::Application(ctx), "Session not found.").await
+        }
+    };
+
+    let guild_id = guild_id_to_i64(get_guild_id(poise::Context::Application(ctx)).await).await;
+    let author_id = user_id_to_i64(ctx.author().id).await;
+
+    // Sessions of other guilds are reported as missing, like any other unknown ID
+    let campaign = match campaign_ops::get_campaign(
+        poise::Context::Application(ctx),
+        guild_id,
+        campaign_ops::CampaignFilters::Id(session.campaign_id),
+    ) {
+        Some(campaign) => campaign,
+        None => {
+            return responses::failure(poise::Context::Application(ctx), "Session not found.").await
+        }
+    };
+
+    if checks::can_manage_campaign(ctx.data(), &campaign, author_id) {
+        let defaults = DmRecapModal {
+            recap: session.recap,
+            dm_notes: session.dm_notes,
+        };
+
+        let data = match DmRecapModal::execute_with_defaults(ctx, defaults).await? {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+
+        session_ops::set_recap(
+            poise::Context::Application(ctx),
+            session_id,
+            data.recap.as_deref(),
+        );
+        session_ops::set_dm_notes(
+            poise::Context::Application(ctx),
+            session_id,
+            data.dm_notes.as_deref(),
+        );
+    } else {
+        if member_ops::has_roster(ctx.data(), campaign.id)
+            && !member_ops::get_member_ids(
+                ctx.data(),
+                campaign.id,
+                &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM],
+            )
+            .contains(&author_id)
+        {
+            return responses::failure(
+                poise::Context::Application(ctx),
+                "Only players in this campaign can write recaps.",
+            )
+            .await;
+        }
+
+        let defaults = RecapModal {
+            recap: session.recap.unwrap_or_default(),
+        };
+
+        let data = match RecapModal::execute_with_defaults(ctx, defaults).await? {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+
+        session_ops::set_recap(
+            poise::Context::Application(ctx),
+            session_id,
+            Some(&data.recap),
+        );
+    }
+
+    responses::success(
+        poise::Context::Application(ctx),
+        &format!("Recap saved for session ID {}.", session_id),
+    )
+    .await
+}
+
+/// Shows the private DM notes of a D&D session (DMs only)
+#[poise::command(slash_command, ephemeral)]
+pub async fn notes(
+    ctx: Context<'_>,
+    #[description = "The ID of the session"] session_id: i32,
+) -> Result<(), Error> {
+    let session = match session_ops::get_session(ctx, session_id) {
+        Some(session) => session,
+        None => return responses::failure(ctx, "Session not found.").await,
+    };
+
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    match session.dm_notes {
+        Some(dm_notes) => responses::success(ctx, &dm_notes).await,
+        None => responses::failure(ctx, "This session has no DM notes.").await,
+    }
+}
@@ -1,9 +1,16 @@
-use crate::models::NewResponse;
-use crate::ops::{response_ops, session_ops};
+use crate::commands::dnd::campaign::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER};
+use crate::models::{NewAttendance, NewResponse};
+use crate::ops::response_ops::ResponseOutcome;
+use crate::ops::{
+    attendance_ops, campaign_ops, member_ops, response_ops, session_ops, voice_checkin_ops,
+};
 use crate::utils::checks;
-use crate::utils::id::user_id_to_i64;
+use crate::utils::guilds::get_guild_id;
+use crate::utils::id::{guild_id_to_i64, user_id_to_i64};
+use crate::utils::{notifications, provisioning, threads};
 use crate::{responses, Context, Error};
 use poise::serenity_prelude as serenity;
+use std::collections::BTreeMap;
 
 #[derive(poise::ChoiceParameter)]
 enum ResponseChoice {
@@ -12,14 +19,36 @@ enum ResponseChoice {
 }
 
 /// Responds to a D&D session
+///
+/// Once a campaign has members, only its players and co-DMs can respond
 #[poise::command(slash_command, check = "checks::dnd_check")]
 pub async fn respond(
     ctx: Context<'_>,
     #[description = "The ID of the session you're responding to"] session_id: i32,
     #[description = "Are you going?"] response: ResponseChoice,
 ) -> Result<(), Error> {
-    if !session_ops::does_session_exist(ctx, session_id) {
-        return responses::failure(ctx, "Session not found.").await;
+    ctx.defer().await?;
+
+    let session = match session_ops::get_session(ctx, session_id) {
+        Some(session) => session,
+        None => return responses::failure(ctx, "Session not found.").await,
+    };
+
+    let respondee_id = user_id_to_i64(ctx.author().id).await;
+
+    if member_ops::has_roster(ctx.data(), session.campaign_id)
+        && !member_ops::get_member_ids(
+            ctx.data(),
+            session.campaign_id,
+            &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM],
+        )
+        .contains(&respondee_id)
+    {
+        return responses::failure(
+            ctx,
+            "Only players in this campaign can respond. Use `/campaign join` to ask to join.",
+        )
+        .await;
     }
 
     let response = match response {
@@ -29,28 +58,34 @@ pub async fn respond(
 
     let new_response = NewResponse {
         session_id,
-        respondee_id: user_id_to_i64(ctx.author().id).await,
+        respondee_id,
         response,
         responded_date: chrono::Utc::now().naive_utc(),
     };
 
-    response_ops::create_response(ctx, new_response);
+    let outcome = response_ops::create_response(ctx.data(), new_response);
 
-    responses::success(ctx, "Response recorded.").await
+    reply_with_outcome(ctx, session_id, ctx.author().id, response, outcome).await
 }
 
 /// Allows a DM to respond to a D&D session for a player (DM only)
-#[poise::command(slash_command, check = "checks::dm_check")]
+#[poise::command(slash_command)]
 pub async fn dmrespond(
     ctx: Context<'_>,
     #[description = "The ID of the session you're responding to"] session_id: i32,
     #[description = "Who are you responding for?"] respondee: serenity::User,
     #[description = "Are they going?"] response: ResponseChoice,
 ) -> Result<(), Error> {
+    ctx.defer().await?;
+
     if !session_ops::does_session_exist(ctx, session_id) {
         return responses::failure(ctx, "Session not found.").await;
     }
 
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
     let response = match response {
         ResponseChoice::Yes => 1,
         ResponseChoice::No => 0,
@@ -63,7 +98,176 @@ pub async fn dmrespond(
         responded_date: chrono::Utc::now().naive_utc(),
     };
 
-    response_ops::create_response(ctx, new_response);
+    let outcome = response_ops::create_response(ctx.data(), new_response);
+
+    reply_with_outcome(ctx, session_id, respondee.id, response, outcome).await
+}
+
+async fn reply_with_outcome(
+    ctx: Context<'_>,
+    session_id: i32,
+    respondee: serenity::UserId,
+    response: i16,
+    outcome: ResponseOutcome,
+) -> Result<(), Error> {
+    notifications::notify_promoted(ctx.serenity_context(), session_id, &outcome.promoted).await;
+
+    if let Some(session) = session_ops::get_session(ctx, session_id) {
+        let mut msg = match (response, outcome.waitlist_position) {
+            (1, Some(position)) => format!(
+                "<@{}> joined the waitlist at position {}.",
+                respondee, position
+            ),
+            (1, None) => format!("<@{}> is going.", respondee),
+            _ => format!("<@{}> is not going.", respondee),
+        };
+
+        for promoted in &outcome.promoted {
+            msg.push_str(&format!(
+                "\n<@{}> moved off the waitlist and is going.",
+                promoted
+            ));
+        }
+
+        threads::post_to_session_thread(ctx.http(), &session, &msg).await;
+
+        if response == 1 {
+            let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+            if let Some(campaign) = campaign_ops::get_campaign(
+                ctx,
+                guild_id,
+                campaign_ops::CampaignFilters::Id(session.campaign_id),
+            ) {
+                provisioning::grant_campaign_role(
+                    ctx.http(),
+                    &campaign,
+                    user_id_to_i64(respondee).await,
+                )
+                .await;
+            }
+        }
+    }
+
+    match outcome.waitlist_position {
+        Some(position) => {
+            responses::success(
+                ctx,
+                &format!(
+                    "Session is full. Response recorded at position {} on the waitlist.",
+                    position
+                ),
+            )
+            .await
+        }
+        None => responses::success(ctx, "Response recorded.").await,
+    }
+}
+
+/// Marks whether a player actually attended a D&D session (DM only)
+///
+/// This is separate from the player's response and feeds `/campaign attendance`
+/// Players joining the campaign's voice channel during the session are marked automatically
+#[poise::command(slash_command)]
+pub async fn attendance(
+    ctx: Context<'_>,
+    #[description = "The ID of the session"] session_id: i32,
+    #[description = "The player to mark"] player: serenity::User,
+    #[description = "Did they attend?"] attended: bool,
+) -> Result<(), Error> {
+    if !session_ops::does_session_exist(ctx, session_id) {
+        return responses::failure(ctx, "Session not found.").await;
+    }
+
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let new_attendance = NewAttendance {
+        session_id,
+        player_id: user_id_to_i64(player.id).await,
+        attended,
+        marked_by: user_id_to_i64(ctx.author().id).await,
+        marked_date: chrono::Utc::now().naive_utc(),
+        late_minutes: None,
+    };
+
+    attendance_ops::set_attendance(ctx.data(), new_attendance);
+
+    responses::success(ctx, "Attendance recorded.").await
+}
+
+/// Shows who joined a D&D session's voice channel, when, and for how long (DM only)
+#[poise::command(slash_command)]
+pub async fn checkins(
+    ctx: Context<'_>,
+    #[description = "The ID of the session"] session_id: i32,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if !session_ops::does_session_exist(ctx, session_id) {
+        return responses::failure(ctx, "Session not found.").await;
+    }
+
+    if !checks::session_check(ctx, session_id).await? {
+        return Ok(());
+    }
+
+    let session_checkins =
+        voice_checkin_ops::get_checkins_for_session(ctx, session_id).unwrap_or_default();
+
+    if session_checkins.is_empty() {
+        return responses::failure(
+            ctx,
+            "Nobody has checked in to this session's voice channel.",
+        )
+        .await;
+    }
+
+    let session_attendance =
+        attendance_ops::get_attendance_for_sessions(ctx, &[session_id]).unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+
+    let mut players: BTreeMap<i64, (chrono::NaiveDateTime, i64)> = BTreeMap::new();
+
+    for checkin in &session_checkins {
+        let minutes = (checkin.left_date.unwrap_or(now) - checkin.joined_date).num_minutes();
+        let player = players
+            .entry(checkin.user_id)
+            .or_insert((checkin.joined_date, 0));
+
+        player.1 += minutes;
+    }
+
+    let lines = players
+        .iter()
+        .map(|(player_id, (joined_date, minutes))| {
+            let late = session_attendance
+                .iter()
+                .find(|a| a.player_id == *player_id)
+                .and_then(|a| a.late_minutes)
+                .map(|late_minutes| format!(" | {} min late", late_minutes))
+                .unwrap_or_default();
+
+            format!(
+                "<@{}>: joined <t:{}:t> | {}h {}m in voice{}",
+                player_id,
+                joined_date.and_utc().timestamp(),
+                minutes / 60,
+                minutes % 60,
+                late
+            )
+        })
+        .collect::<Vec<String>>();
+
+    ctx.send(
+        poise::CreateReply::default().embed(
+            serenity::CreateEmbed::new()
+                .title(format!("Session ID {} voice check-ins", session_id))
+                .description(lines.join("\n")),
+        ),
+    )
+    .await?;
 
-    responses::success(ctx, "Response recorded.").await
+    Ok(())
 }
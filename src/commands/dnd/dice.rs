@@ -58,7 +58,7 @@ pub async fn d100(ctx: Context<'_>) -> Result<(), Error> {
 }
 
 async fn roll_and_reply(ctx: Context<'_>, amount: i64) -> Result<(), Error> {
-    let mut result = rand::thread_rng().gen_range(1..=amount);
+    let mut result = rand::rng().random_range(1..=amount);
 
     if amount == 100 {
         result = numbers::round_to_nearest_10(result).await;
@@ -0,0 +1,33 @@
+use crate::{
+    models::NewUserSetting,
+    ops::user_settings_ops,
+    responses,
+    utils::{autocompletes::autocomplete_timezone, id::user_id_to_i64},
+    Context, Error,
+};
+use chrono_tz::Tz;
+
+/// Sets your timezone, used when entering session dates
+#[poise::command(slash_command, category = "Settings")]
+pub async fn timezone(
+    ctx: Context<'_>,
+    #[description = "Your timezone (e.g. America/New_York)"]
+    #[autocomplete = "autocomplete_timezone"]
+    timezone: String,
+) -> Result<(), Error> {
+    let tz = match timezone.parse::<Tz>() {
+        Ok(tz) => tz,
+        Err(_) => {
+            return responses::failure(ctx, &format!("Timezone {} does not exist.", timezone)).await
+        }
+    };
+
+    let user_settings = NewUserSetting {
+        user_id: user_id_to_i64(ctx.author().id).await,
+        timezone: tz.name(),
+    };
+
+    user_settings_ops::create_user_settings(ctx, user_settings);
+
+    responses::success(ctx, &format!("Timezone set to {}.", tz.name())).await
+}
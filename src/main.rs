@@ -5,17 +5,21 @@ use crate::commands::*;
 use anyhow::anyhow;
 use poise::serenity_prelude as serenity;
 use shuttle_runtime::SecretStore;
-use shuttle_serenity::ShuttleSerenity;
 
 pub mod commands;
 pub mod models;
 pub mod ops;
 pub mod responses;
 pub mod schema;
+pub mod service;
+pub mod tasks;
 pub mod utils;
+pub mod web;
 
+#[derive(Clone)]
 pub struct Data {
     db_pool: diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<diesel::PgConnection>>,
+    public_url: Option<String>,
 }
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -47,20 +51,67 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
 }
 
 async fn on_event(
-    _ctx: &serenity::Context,
+    ctx: &serenity::Context,
     event: &serenity::FullEvent,
     _framework: poise::FrameworkContext<'_, Data, Error>,
-    _data: &Data,
+    data: &Data,
 ) -> Result<(), Error> {
-    if let serenity::FullEvent::Ready { data_about_bot } = event {
-        println!("{} is connected!", data_about_bot.user.name);
+    match event {
+        serenity::FullEvent::Ready { data_about_bot } => {
+            println!("{} is connected!", data_about_bot.user.name);
+        }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } if interaction
+            .data
+            .custom_id
+            .starts_with(dnd::campaign::session::poll::VOTE_PREFIX) =>
+        {
+            dnd::campaign::session::poll::handle_vote(ctx, interaction, data).await?;
+        }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } if interaction
+            .data
+            .custom_id
+            .starts_with(dnd::campaign::members::MEMBER_PREFIX) =>
+        {
+            dnd::campaign::members::handle_request(ctx, interaction, data).await?;
+        }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } if interaction
+            .data
+            .custom_id
+            .starts_with(dnd::campaign::npc::REVEAL_PREFIX) =>
+        {
+            dnd::campaign::npc::handle_reveal(ctx, interaction, data).await?;
+        }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } if interaction
+            .data
+            .custom_id
+            .starts_with(dnd::campaign::lfg::LFG_PREFIX) =>
+        {
+            dnd::campaign::lfg::handle_signup(ctx, interaction, data).await?;
+        }
+        serenity::FullEvent::GuildScheduledEventUserAdd { subscribed } => {
+            utils::events::handle_event_interest(ctx, data, subscribed).await?;
+        }
+        serenity::FullEvent::VoiceStateUpdate { new, .. } => {
+            utils::voice::handle_voice_state(ctx, data, new).await?;
+        }
+        _ => {}
     }
 
     Ok(())
 }
 
 #[shuttle_runtime::main]
-pub async fn poise(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> ShuttleSerenity {
+pub async fn poise(
+    #[shuttle_runtime::Secrets] secret_store: SecretStore,
+) -> Result<service::BotService, shuttle_runtime::Error> {
     let token = if let Some(token) = secret_store.get("DISCORD_TOKEN") {
         token
     } else {
@@ -73,11 +124,26 @@ pub async fn poise(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> Shu
         return Err(anyhow!("DATABASE_URL not found in secret store").into());
     };
 
+    let data = Data {
+        db_pool: utils::db::init_pool(&database_url),
+        public_url: secret_store.get("PUBLIC_URL"),
+    };
+    let router = web::router(data.clone());
+
     let commands = vec![
         help::help(),
         settings::settings(),
+        timezone::timezone(),
         dnd::campaign::session::session(),
         dnd::campaign::campaign(),
+        dnd::campaign::lore::lore(),
+        dnd::campaign::npc::npc(),
+        dnd::campaign::quest::quest(),
+        dnd::campaign::calendar::calendar(),
+        dnd::campaign::handout::handout(),
+        dnd::campaign::journal::journal(),
+        dnd::campaign::safety::xcard(),
+        dnd::campaign::lfg::lfg(),
         dnd::dice::roll(),
     ];
 
@@ -100,9 +166,10 @@ pub async fn poise(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> Shu
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                Ok(Data {
-                    db_pool: utils::db::init_pool(&database_url),
-                })
+
+                tasks::spawn_all(ctx, &data);
+
+                Ok(data)
             })
         })
         .build();
@@ -116,5 +183,8 @@ pub async fn poise(#[shuttle_runtime::Secrets] secret_store: SecretStore) -> Shu
         .await
         .map_err(shuttle_runtime::CustomError::new)?;
 
-    Ok(client.into())
+    Ok(service::BotService {
+        discord: client.into(),
+        http: router.into(),
+    })
 }
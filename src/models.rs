@@ -1,4 +1,10 @@
-use crate::schema::{campaigns, characters, responses, sessions, settings};
+use crate::schema::{
+    attendance, calendar_events, calendar_holidays, calendar_seasons, calendars, campaign_members,
+    campaigns, characters, handout_recipients, handouts, journal_entries, lfg_posts, lore_entries,
+    npcs, poll_options, poll_votes, polls, quest_objectives, questionnaire_answers,
+    questionnaire_questions, quests, responses, safety_entries, sessions, settings, user_settings,
+    voice_checkins,
+};
 
 #[derive(Insertable)]
 #[diesel(table_name = campaigns)]
@@ -13,6 +19,7 @@ pub struct NewCampaign<'a> {
 }
 
 #[derive(Debug, Queryable, AsChangeset)]
+#[diesel(treat_none_as_null = true)]
 pub struct Campaign {
     pub id: i32,
     pub guild_id: i64,
@@ -22,6 +29,19 @@ pub struct Campaign {
     pub link: Option<String>,
     pub deleted: bool,
     pub created_date: chrono::NaiveDateTime,
+    pub min_players: Option<i16>,
+    pub quorum_deadline_hours: i32,
+    pub quorum_action: i16,
+    pub import_event_interest: bool,
+    pub calendar_token: String,
+    pub channel_id: Option<i64>,
+    pub voice_channel_id: Option<i64>,
+    pub role_id: Option<i64>,
+    pub category_id: Option<i64>,
+    pub deleted_date: Option<chrono::NaiveDateTime>,
+    pub provisioned_channel_id: Option<i64>,
+    pub provisioned_voice_channel_id: Option<i64>,
+    pub archived: bool,
 }
 
 #[derive(Insertable)]
@@ -53,9 +73,11 @@ pub struct NewSession<'a> {
     pub status: i16,
     pub created_date: chrono::NaiveDateTime,
     pub scheduled_date: Option<chrono::NaiveDateTime>,
+    pub max_players: Option<i16>,
 }
 
 #[derive(Debug, Queryable, AsChangeset)]
+#[diesel(treat_none_as_null = true)]
 pub struct Session {
     pub id: i32,
     pub campaign_id: i32,
@@ -64,6 +86,14 @@ pub struct Session {
     pub status: i16,
     pub created_date: chrono::NaiveDateTime,
     pub scheduled_date: Option<chrono::NaiveDateTime>,
+    pub quorum_notified: bool,
+    pub event_id: Option<i64>,
+    pub recap: Option<String>,
+    pub dm_notes: Option<String>,
+    pub max_players: Option<i16>,
+    pub thread_id: Option<i64>,
+    pub ingame_start_day: Option<i32>,
+    pub ingame_end_day: Option<i32>,
 }
 
 #[derive(Insertable, AsChangeset)]
@@ -82,6 +112,7 @@ pub struct Response {
     pub respondee_id: i64,
     pub response: i16,
     pub responded_date: chrono::NaiveDateTime,
+    pub waitlisted: bool,
 }
 
 #[derive(Insertable, AsChangeset)]
@@ -98,3 +129,470 @@ pub struct Setting {
     pub dnd_role_id: Option<i64>,
     pub dm_role_id: Option<i64>,
 }
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = user_settings)]
+pub struct NewUserSetting<'a> {
+    pub user_id: i64,
+    pub timezone: &'a str,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+pub struct UserSetting {
+    pub user_id: i64,
+    pub timezone: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = polls)]
+pub struct NewPoll<'a> {
+    pub campaign_id: i32,
+    pub author_id: i64,
+    pub location: Option<&'a str>,
+    pub closed: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+pub struct Poll {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub author_id: i64,
+    pub location: Option<String>,
+    pub channel_id: Option<i64>,
+    pub message_id: Option<i64>,
+    pub closed: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = poll_options)]
+pub struct NewPollOption {
+    pub poll_id: i32,
+    pub scheduled_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct PollOption {
+    pub id: i32,
+    pub poll_id: i32,
+    pub scheduled_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = poll_votes)]
+pub struct NewPollVote {
+    pub poll_option_id: i32,
+    pub voter_id: i64,
+    pub voted_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct PollVote {
+    pub id: i32,
+    pub poll_option_id: i32,
+    pub voter_id: i64,
+    pub voted_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = attendance)]
+pub struct NewAttendance {
+    pub session_id: i32,
+    pub player_id: i64,
+    pub attended: bool,
+    pub marked_by: i64,
+    pub marked_date: chrono::NaiveDateTime,
+    pub late_minutes: Option<i32>,
+}
+
+#[derive(Debug, Queryable)]
+pub struct Attendance {
+    pub id: i32,
+    pub session_id: i32,
+    pub player_id: i64,
+    pub attended: bool,
+    pub marked_by: i64,
+    pub marked_date: chrono::NaiveDateTime,
+    pub late_minutes: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = voice_checkins)]
+pub struct NewVoiceCheckin {
+    pub session_id: i32,
+    pub user_id: i64,
+    pub joined_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct VoiceCheckin {
+    pub id: i32,
+    pub session_id: i32,
+    pub user_id: i64,
+    pub joined_date: chrono::NaiveDateTime,
+    pub left_date: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = campaign_members)]
+pub struct NewCampaignMember {
+    pub campaign_id: i32,
+    pub user_id: i64,
+    pub role: i16,
+    pub status: i16,
+    pub added_by: i64,
+    pub joined_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct CampaignMember {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub user_id: i64,
+    pub role: i16,
+    pub status: i16,
+    pub added_by: i64,
+    pub joined_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = lore_entries)]
+pub struct NewLoreEntry<'a> {
+    pub campaign_id: i32,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub tags: Vec<String>,
+    pub dm_only: bool,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+#[diesel(table_name = lore_entries)]
+pub struct LoreEntry {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    pub dm_only: bool,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = npcs)]
+pub struct NewNpc<'a> {
+    pub campaign_id: i32,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub location: Option<&'a str>,
+    pub faction: Option<&'a str>,
+    pub attitude: i16,
+    pub status: i16,
+    pub secrets: Option<&'a str>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+    pub portrait_name: Option<&'a str>,
+    pub portrait_data: Option<&'a [u8]>,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+#[diesel(table_name = npcs)]
+pub struct Npc {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub faction: Option<String>,
+    pub attitude: i16,
+    pub status: i16,
+    pub secrets: Option<String>,
+    pub revealed: Vec<String>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+    pub portrait_name: Option<String>,
+    pub portrait_data: Option<Vec<u8>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quests)]
+pub struct NewQuest<'a> {
+    pub campaign_id: i32,
+    pub title: &'a str,
+    pub giver_npc_id: Option<i32>,
+    pub description: Option<&'a str>,
+    pub reward: Option<&'a str>,
+    pub status: i16,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+#[diesel(table_name = quests)]
+#[diesel(treat_none_as_null = true)]
+pub struct Quest {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub title: String,
+    pub giver_npc_id: Option<i32>,
+    pub description: Option<String>,
+    pub reward: Option<String>,
+    pub status: i16,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quest_objectives)]
+pub struct NewQuestObjective<'a> {
+    pub quest_id: i32,
+    pub description: &'a str,
+    pub completed: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct QuestObjective {
+    pub id: i32,
+    pub quest_id: i32,
+    pub description: String,
+    pub completed: bool,
+    pub created_date: chrono::NaiveDateTime,
+    pub completed_date: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = calendars)]
+pub struct Calendar {
+    pub campaign_id: i32,
+    pub name: String,
+    pub month_names: Vec<String>,
+    pub month_lengths: Vec<i32>,
+    pub weekdays: Vec<String>,
+    pub current_day: i32,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_holidays)]
+pub struct NewCalendarHoliday<'a> {
+    pub campaign_id: i32,
+    pub name: &'a str,
+    pub month: i16,
+    pub day: i16,
+}
+
+#[derive(Debug, Queryable)]
+pub struct CalendarHoliday {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub name: String,
+    pub month: i16,
+    pub day: i16,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_seasons)]
+pub struct NewCalendarSeason<'a> {
+    pub campaign_id: i32,
+    pub name: &'a str,
+    pub month: i16,
+    pub day: i16,
+}
+
+#[derive(Debug, Queryable)]
+pub struct CalendarSeason {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub name: String,
+    pub month: i16,
+    pub day: i16,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_events)]
+pub struct NewCalendarEvent<'a> {
+    pub campaign_id: i32,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub day: i32,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct CalendarEvent {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub day: i32,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = handouts)]
+pub struct NewHandout<'a> {
+    pub campaign_id: i32,
+    pub title: &'a str,
+    pub content: Option<&'a str>,
+    pub file_name: Option<&'a str>,
+    pub file_data: Option<&'a [u8]>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct Handout {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub title: String,
+    pub content: Option<String>,
+    pub file_name: Option<String>,
+    pub file_data: Option<Vec<u8>>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = handout_recipients)]
+pub struct NewHandoutRecipient {
+    pub handout_id: i32,
+    pub user_id: i64,
+    pub revealed_by: i64,
+    pub revealed_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct HandoutRecipient {
+    pub id: i32,
+    pub handout_id: i32,
+    pub user_id: i64,
+    pub revealed_by: i64,
+    pub revealed_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = journal_entries)]
+pub struct NewJournalEntry<'a> {
+    pub campaign_id: i32,
+    pub author_id: i64,
+    pub session_id: Option<i32>,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub shared: bool,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, AsChangeset)]
+#[diesel(table_name = journal_entries)]
+pub struct JournalEntry {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub author_id: i64,
+    pub session_id: Option<i32>,
+    pub title: String,
+    pub content: String,
+    pub shared: bool,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = safety_entries)]
+pub struct NewSafetyEntry<'a> {
+    pub campaign_id: i32,
+    pub kind: i16,
+    pub topic: &'a str,
+    pub created_date: chrono::NaiveDate,
+}
+
+#[derive(Debug, Queryable)]
+pub struct SafetyEntry {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub kind: i16,
+    pub topic: String,
+    pub created_date: chrono::NaiveDate,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = questionnaire_questions)]
+pub struct NewQuestionnaireQuestion<'a> {
+    pub campaign_id: i32,
+    pub position: i32,
+    pub prompt: &'a str,
+    pub placeholder: Option<&'a str>,
+    pub paragraph: bool,
+    pub required: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestionnaireQuestion {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub position: i32,
+    pub prompt: String,
+    pub placeholder: Option<String>,
+    pub paragraph: bool,
+    pub required: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = questionnaire_answers)]
+pub struct NewQuestionnaireAnswer<'a> {
+    pub question_id: i32,
+    pub user_id: i64,
+    pub answer: &'a str,
+    pub answered_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct QuestionnaireAnswer {
+    pub id: i32,
+    pub question_id: i32,
+    pub user_id: i64,
+    pub answer: String,
+    pub answered_date: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = lfg_posts)]
+pub struct NewLfgPost<'a> {
+    pub campaign_id: i32,
+    pub session_id: i32,
+    pub system: &'a str,
+    pub min_level: i16,
+    pub max_level: i16,
+    pub content_warnings: Option<&'a str>,
+    pub channel_id: Option<i64>,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct LfgPost {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub session_id: i32,
+    pub system: String,
+    pub min_level: i16,
+    pub max_level: i16,
+    pub content_warnings: Option<String>,
+    pub channel_id: Option<i64>,
+    pub message_id: Option<i64>,
+    pub closed: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
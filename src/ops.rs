@@ -1,5 +1,20 @@
+pub mod attendance_ops;
+pub mod calendar_ops;
 pub mod campaign_ops;
 pub mod character_ops;
+pub mod export_ops;
+pub mod handout_ops;
+pub mod journal_ops;
+pub mod lfg_ops;
+pub mod lore_ops;
+pub mod member_ops;
+pub mod npc_ops;
+pub mod poll_ops;
+pub mod quest_ops;
+pub mod questionnaire_ops;
 pub mod response_ops;
+pub mod safety_ops;
 pub mod session_ops;
 pub mod settings_ops;
+pub mod user_settings_ops;
+pub mod voice_checkin_ops;
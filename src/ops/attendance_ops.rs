@@ -0,0 +1,46 @@
+use crate::models::{Attendance, NewAttendance};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+
+pub fn set_attendance(data: &Data, new_attendance: NewAttendance) {
+    use crate::schema::attendance::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::insert_into(attendance)
+        .values(&new_attendance)
+        .on_conflict((session_id, player_id))
+        .do_update()
+        .set(&new_attendance)
+        .execute(conn)
+        .expect("Error saving attendance");
+}
+
+/// Records attendance from a voice check-in, keeping anything already marked for the player
+pub fn record_checkin_attendance(data: &Data, new_attendance: NewAttendance) {
+    use crate::schema::attendance::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::insert_into(attendance)
+        .values(&new_attendance)
+        .on_conflict((session_id, player_id))
+        .do_nothing()
+        .execute(conn)
+        .expect("Error saving attendance");
+}
+
+pub fn get_attendance_for_sessions(
+    ctx: Context<'_>,
+    session_ids: &[i32],
+) -> Option<Vec<Attendance>> {
+    use crate::schema::attendance::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    attendance
+        .filter(session_id.eq_any(session_ids))
+        .load::<Attendance>(conn)
+        .ok()
+}
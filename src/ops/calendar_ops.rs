@@ -0,0 +1,246 @@
+use crate::models::{
+    Calendar, CalendarEvent, CalendarHoliday, CalendarSeason, NewCalendarEvent, NewCalendarHoliday,
+    NewCalendarSeason,
+};
+use crate::utils::calendar::CalendarPreset;
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::prelude::*;
+
+const UPCOMING_EVENTS_LIMIT: i64 = 5;
+
+/// Creates or replaces a campaign's calendar
+pub fn save_calendar(ctx: Context<'_>, calendar: &Calendar) {
+    use crate::schema::calendars::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(calendars)
+        .values(calendar)
+        .on_conflict(campaign_id)
+        .do_update()
+        .set(calendar)
+        .execute(conn)
+        .expect("Error saving calendar");
+}
+
+pub fn get_calendar(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Calendar> {
+    use crate::schema::calendars::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendars.find(campaign_id_i32).first::<Calendar>(conn).ok()
+}
+
+/// Replaces a campaign's holidays and seasons with those of a preset
+pub fn apply_preset(ctx: Context<'_>, campaign_id_i32: i32, preset: &CalendarPreset) {
+    use crate::schema::{calendar_holidays, calendar_seasons};
+
+    let conn = &mut get_conn(ctx);
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::delete(
+            calendar_holidays::table.filter(calendar_holidays::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            calendar_seasons::table.filter(calendar_seasons::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+
+        diesel::insert_into(calendar_holidays::table)
+            .values(
+                preset
+                    .holidays
+                    .iter()
+                    .map(|(name, month, day)| NewCalendarHoliday {
+                        campaign_id: campaign_id_i32,
+                        name,
+                        month: *month,
+                        day: *day,
+                    })
+                    .collect::<Vec<NewCalendarHoliday>>(),
+            )
+            .execute(conn)?;
+        diesel::insert_into(calendar_seasons::table)
+            .values(
+                preset
+                    .seasons
+                    .iter()
+                    .map(|(name, month, day)| NewCalendarSeason {
+                        campaign_id: campaign_id_i32,
+                        name,
+                        month: *month,
+                        day: *day,
+                    })
+                    .collect::<Vec<NewCalendarSeason>>(),
+            )
+            .execute(conn)?;
+
+        Ok(())
+    })
+    .expect("Error applying calendar preset");
+}
+
+/// Adds a holiday, returning `false` if the campaign already has one with that name
+pub fn add_holiday(ctx: Context<'_>, holiday: NewCalendarHoliday) -> bool {
+    use crate::schema::calendar_holidays::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(calendar_holidays)
+        .values(&holiday)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving holiday")
+        > 0
+}
+
+pub fn delete_holiday(ctx: Context<'_>, campaign_id_i32: i32, name_str: &str) -> bool {
+    use crate::schema::calendar_holidays::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        calendar_holidays
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(name.eq(name_str)),
+    )
+    .execute(conn)
+    .expect("Error deleting holiday")
+        > 0
+}
+
+pub fn get_holidays(ctx: Context<'_>, campaign_id_i32: i32) -> Vec<CalendarHoliday> {
+    use crate::schema::calendar_holidays::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendar_holidays
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((month, day))
+        .load::<CalendarHoliday>(conn)
+        .unwrap_or_default()
+}
+
+/// Adds a season, returning `false` if the campaign already has one with that name
+pub fn add_season(ctx: Context<'_>, season: NewCalendarSeason) -> bool {
+    use crate::schema::calendar_seasons::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(calendar_seasons)
+        .values(&season)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving season")
+        > 0
+}
+
+pub fn delete_season(ctx: Context<'_>, campaign_id_i32: i32, name_str: &str) -> bool {
+    use crate::schema::calendar_seasons::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        calendar_seasons
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(name.eq(name_str)),
+    )
+    .execute(conn)
+    .expect("Error deleting season")
+        > 0
+}
+
+pub fn get_seasons(ctx: Context<'_>, campaign_id_i32: i32) -> Vec<CalendarSeason> {
+    use crate::schema::calendar_seasons::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendar_seasons
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((month, day))
+        .load::<CalendarSeason>(conn)
+        .unwrap_or_default()
+}
+
+/// Adds an in-world event, returning `false` if the campaign already has one with that name
+pub fn add_event(ctx: Context<'_>, event: NewCalendarEvent) -> bool {
+    use crate::schema::calendar_events::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(calendar_events)
+        .values(&event)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving calendar event")
+        > 0
+}
+
+pub fn delete_event(ctx: Context<'_>, campaign_id_i32: i32, name_str: &str) -> bool {
+    use crate::schema::calendar_events::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        calendar_events
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(name.eq(name_str)),
+    )
+    .execute(conn)
+    .expect("Error deleting calendar event")
+        > 0
+}
+
+/// Gets the events that happen after one day, up to and including another
+pub fn get_events_between(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    after: i32,
+    until: i32,
+) -> Vec<CalendarEvent> {
+    use crate::schema::calendar_events::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendar_events
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(day.gt(after))
+        .filter(day.le(until))
+        .order_by((day, name))
+        .load::<CalendarEvent>(conn)
+        .unwrap_or_default()
+}
+
+/// Gets the next few events from a day onwards
+pub fn get_upcoming_events(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    from: i32,
+) -> Vec<CalendarEvent> {
+    use crate::schema::calendar_events::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendar_events
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(day.ge(from))
+        .order_by((day, name))
+        .limit(UPCOMING_EVENTS_LIMIT)
+        .load::<CalendarEvent>(conn)
+        .unwrap_or_default()
+}
+
+pub fn get_event_names(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<String>> {
+    use crate::schema::calendar_events::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    calendar_events
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(name)
+        .order_by(day)
+        .load::<String>(conn)
+        .ok()
+}
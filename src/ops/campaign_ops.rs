@@ -1,6 +1,6 @@
 use crate::models::{Campaign, NewCampaign};
-use crate::utils::db::get_conn;
-use crate::Context;
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
 use diesel::prelude::*;
 
 pub enum CampaignFilters {
@@ -9,15 +9,15 @@ pub enum CampaignFilters {
     DmId(i64),
 }
 
-pub fn create_campaign(ctx: Context<'_>, campaign: NewCampaign) {
+pub fn create_campaign(ctx: Context<'_>, campaign: NewCampaign) -> Campaign {
     use crate::schema::campaigns::dsl::*;
 
     let conn = &mut get_conn(ctx);
 
     diesel::insert_into(campaigns)
         .values(&campaign)
-        .execute(conn)
-        .expect("Error saving new campaign");
+        .get_result::<Campaign>(conn)
+        .expect("Error saving new campaign")
 }
 
 pub fn update_campaign(ctx: Context<'_>, campaign: Campaign) {
@@ -94,6 +94,7 @@ pub fn get_id_from_name(ctx: Context<'_>, name_str: &str, guild_id_i64: i64) ->
     campaigns
         .filter(name.eq(name_str))
         .filter(guild_id.eq(guild_id_i64))
+        .filter(deleted.eq(false))
         .select(id)
         .first::<i32>(conn)
         .ok()
@@ -120,7 +121,183 @@ pub fn does_campaign_exist(ctx: Context<'_>, name_str: &str, guild_id_i64: i64)
     campaigns
         .filter(name.eq(name_str))
         .filter(guild_id.eq(guild_id_i64))
+        .filter(deleted.eq(false))
         .select(id)
         .first::<i32>(conn)
         .is_ok()
 }
+
+pub fn get_campaign_by_calendar_token(data: &Data, token: &str) -> Option<Campaign> {
+    use crate::schema::campaigns::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    campaigns
+        .filter(calendar_token.eq(token))
+        .filter(deleted.eq(false))
+        .first::<Campaign>(conn)
+        .ok()
+}
+
+/// Gets the most recently archived or deleted campaign with a name
+pub fn get_archived_campaign(
+    ctx: Context<'_>,
+    guild_id_i64: i64,
+    name_str: &str,
+) -> Option<Campaign> {
+    use crate::schema::campaigns::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    campaigns
+        .filter(guild_id.eq(guild_id_i64))
+        .filter(name.eq(name_str))
+        .filter(deleted.eq(true))
+        .order_by(deleted_date.desc())
+        .first::<Campaign>(conn)
+        .ok()
+}
+
+pub fn get_archived_campaign_names(ctx: Context<'_>, guild_id_i64: i64) -> Option<Vec<String>> {
+    use crate::schema::campaigns::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    campaigns
+        .filter(guild_id.eq(guild_id_i64))
+        .filter(deleted.eq(true))
+        .select(name)
+        .distinct()
+        .load::<String>(conn)
+        .ok()
+}
+
+/// Gets the campaigns that were deleted before a date, in every guild
+///
+/// Archived campaigns are kept until they're restored or deleted
+pub fn get_expired_campaigns(data: &Data, deleted_before: chrono::NaiveDateTime) -> Vec<Campaign> {
+    use crate::schema::campaigns::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    campaigns
+        .filter(deleted.eq(true))
+        .filter(archived.eq(false))
+        .filter(deleted_date.lt(deleted_before))
+        .load::<Campaign>(conn)
+        .unwrap_or_default()
+}
+
+/// Permanently deletes a campaign along with its characters, sessions and everything attached to them
+///
+/// Nothing is deleted if any part fails
+pub fn purge_campaign(data: &Data, campaign_id_i32: i32) -> QueryResult<()> {
+    use crate::schema::{
+        attendance, calendar_events, calendar_holidays, calendar_seasons, calendars,
+        campaign_members, campaigns, characters, handout_recipients, handouts, journal_entries,
+        lfg_posts, lore_entries, npcs, poll_options, poll_votes, polls, quest_objectives,
+        questionnaire_answers, questionnaire_questions, quests, responses, safety_entries,
+        sessions, voice_checkins,
+    };
+
+    let conn = &mut get_data_conn(data);
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let session_ids = sessions::table
+            .filter(sessions::campaign_id.eq(campaign_id_i32))
+            .select(sessions::id);
+        let poll_ids = polls::table
+            .filter(polls::campaign_id.eq(campaign_id_i32))
+            .select(polls::id);
+        let quest_ids = quests::table
+            .filter(quests::campaign_id.eq(campaign_id_i32))
+            .select(quests::id);
+        let poll_option_ids = poll_options::table
+            .filter(poll_options::poll_id.eq_any(poll_ids))
+            .select(poll_options::id);
+
+        diesel::delete(
+            poll_votes::table.filter(poll_votes::poll_option_id.eq_any(poll_option_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(poll_options::table.filter(poll_options::poll_id.eq_any(poll_ids)))
+            .execute(conn)?;
+        diesel::delete(polls::table.filter(polls::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(attendance::table.filter(attendance::session_id.eq_any(session_ids)))
+            .execute(conn)?;
+        diesel::delete(
+            voice_checkins::table.filter(voice_checkins::session_id.eq_any(session_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(responses::table.filter(responses::session_id.eq_any(session_ids)))
+            .execute(conn)?;
+        diesel::delete(
+            journal_entries::table.filter(journal_entries::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(lfg_posts::table.filter(lfg_posts::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(sessions::table.filter(sessions::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(characters::table.filter(characters::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        let handout_ids = handouts::table
+            .filter(handouts::campaign_id.eq(campaign_id_i32))
+            .select(handouts::id);
+
+        diesel::delete(
+            handout_recipients::table.filter(handout_recipients::handout_id.eq_any(handout_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(handouts::table.filter(handouts::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(lore_entries::table.filter(lore_entries::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(
+            quest_objectives::table.filter(quest_objectives::quest_id.eq_any(quest_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(quests::table.filter(quests::campaign_id.eq(campaign_id_i32)))
+            .execute(conn)?;
+        diesel::delete(
+            safety_entries::table.filter(safety_entries::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        let question_ids = questionnaire_questions::table
+            .filter(questionnaire_questions::campaign_id.eq(campaign_id_i32))
+            .select(questionnaire_questions::id);
+
+        diesel::delete(
+            questionnaire_answers::table
+                .filter(questionnaire_answers::question_id.eq_any(question_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            questionnaire_questions::table
+                .filter(questionnaire_questions::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(npcs::table.filter(npcs::campaign_id.eq(campaign_id_i32))).execute(conn)?;
+        diesel::delete(
+            calendar_events::table.filter(calendar_events::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            calendar_holidays::table.filter(calendar_holidays::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            calendar_seasons::table.filter(calendar_seasons::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(calendars::table.find(campaign_id_i32)).execute(conn)?;
+        diesel::delete(
+            campaign_members::table.filter(campaign_members::campaign_id.eq(campaign_id_i32)),
+        )
+        .execute(conn)?;
+        diesel::delete(campaigns::table.find(campaign_id_i32)).execute(conn)?;
+
+        Ok(())
+    })
+}
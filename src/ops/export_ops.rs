@@ -0,0 +1,838 @@
+use crate::models::{
+    Attendance, Calendar, CalendarEvent, CalendarHoliday, CalendarSeason, Campaign, CampaignMember,
+    Character, HandoutRecipient, JournalEntry, LfgPost, LoreEntry, Npc, Poll, PollOption, PollVote,
+    Quest, QuestObjective, QuestionnaireAnswer, QuestionnaireQuestion, Response, SafetyEntry,
+    Session, VoiceCheckin,
+};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::utils::export::*;
+use crate::{Context, Data};
+use diesel::prelude::*;
+use std::collections::BTreeSet;
+
+/// Gathers a campaign and everything attached to it into an export
+pub fn export_campaign(ctx: Context<'_>, campaign: &Campaign) -> Option<CampaignExport> {
+    use crate::schema::{
+        attendance, calendar_events, calendar_holidays, calendar_seasons, calendars,
+        campaign_members, characters, handout_recipients, handouts, journal_entries, lfg_posts,
+        lore_entries, npcs, poll_options, poll_votes, polls, quest_objectives,
+        questionnaire_answers, questionnaire_questions, quests, responses, safety_entries,
+        sessions, voice_checkins,
+    };
+
+    let conn = &mut get_conn(ctx);
+
+    let members = campaign_members::table
+        .filter(campaign_members::campaign_id.eq(campaign.id))
+        .load::<CampaignMember>(conn)
+        .ok()?;
+    let campaign_characters = characters::table
+        .filter(characters::campaign_id.eq(campaign.id))
+        .load::<Character>(conn)
+        .ok()?;
+    let campaign_sessions = sessions::table
+        .filter(sessions::campaign_id.eq(campaign.id))
+        .order_by(sessions::created_date)
+        .load::<Session>(conn)
+        .ok()?;
+    let session_ids = campaign_sessions.iter().map(|s| s.id).collect::<Vec<i32>>();
+    let session_responses = responses::table
+        .filter(responses::session_id.eq_any(&session_ids))
+        .load::<Response>(conn)
+        .ok()?;
+    let session_attendance = attendance::table
+        .filter(attendance::session_id.eq_any(&session_ids))
+        .load::<Attendance>(conn)
+        .ok()?;
+    let session_checkins = voice_checkins::table
+        .filter(voice_checkins::session_id.eq_any(&session_ids))
+        .load::<VoiceCheckin>(conn)
+        .ok()?;
+    let campaign_polls = polls::table
+        .filter(polls::campaign_id.eq(campaign.id))
+        .order_by(polls::created_date)
+        .load::<Poll>(conn)
+        .ok()?;
+    let poll_ids = campaign_polls.iter().map(|p| p.id).collect::<Vec<i32>>();
+    let options = poll_options::table
+        .filter(poll_options::poll_id.eq_any(&poll_ids))
+        .order_by(poll_options::scheduled_date)
+        .load::<PollOption>(conn)
+        .ok()?;
+    let option_ids = options.iter().map(|o| o.id).collect::<Vec<i32>>();
+    let votes = poll_votes::table
+        .filter(poll_votes::poll_option_id.eq_any(&option_ids))
+        .load::<PollVote>(conn)
+        .ok()?;
+    let entries = lore_entries::table
+        .filter(lore_entries::campaign_id.eq(campaign.id))
+        .order_by(lore_entries::title)
+        .load::<LoreEntry>(conn)
+        .ok()?;
+    let campaign_npcs = npcs::table
+        .filter(npcs::campaign_id.eq(campaign.id))
+        .order_by(npcs::name)
+        .load::<Npc>(conn)
+        .ok()?;
+    let campaign_quests = quests::table
+        .filter(quests::campaign_id.eq(campaign.id))
+        .order_by(quests::created_date)
+        .load::<Quest>(conn)
+        .ok()?;
+    let quest_ids = campaign_quests.iter().map(|q| q.id).collect::<Vec<i32>>();
+    let objectives = quest_objectives::table
+        .filter(quest_objectives::quest_id.eq_any(&quest_ids))
+        .order_by((quest_objectives::created_date, quest_objectives::id))
+        .load::<QuestObjective>(conn)
+        .ok()?;
+    let calendar = calendars::table
+        .find(campaign.id)
+        .first::<Calendar>(conn)
+        .optional()
+        .ok()?;
+    let holidays = calendar_holidays::table
+        .filter(calendar_holidays::campaign_id.eq(campaign.id))
+        .order_by((calendar_holidays::month, calendar_holidays::day))
+        .load::<CalendarHoliday>(conn)
+        .ok()?;
+    let seasons = calendar_seasons::table
+        .filter(calendar_seasons::campaign_id.eq(campaign.id))
+        .order_by((calendar_seasons::month, calendar_seasons::day))
+        .load::<CalendarSeason>(conn)
+        .ok()?;
+    let events = calendar_events::table
+        .filter(calendar_events::campaign_id.eq(campaign.id))
+        .order_by(calendar_events::day)
+        .load::<CalendarEvent>(conn)
+        .ok()?;
+    let campaign_handouts = handouts::table
+        .filter(handouts::campaign_id.eq(campaign.id))
+        .order_by(handouts::created_date)
+        .select((
+            handouts::id,
+            handouts::title,
+            handouts::content,
+            handouts::file_name,
+            handouts::author_id,
+            handouts::created_date,
+        ))
+        .load::<(
+            i32,
+            String,
+            Option<String>,
+            Option<String>,
+            i64,
+            chrono::NaiveDateTime,
+        )>(conn)
+        .ok()?;
+    let handout_ids = campaign_handouts
+        .iter()
+        .map(|(id, ..)| *id)
+        .collect::<Vec<i32>>();
+    let recipients = handout_recipients::table
+        .filter(handout_recipients::handout_id.eq_any(&handout_ids))
+        .order_by(handout_recipients::revealed_date)
+        .load::<HandoutRecipient>(conn)
+        .ok()?;
+    let shared_entries = journal_entries::table
+        .filter(journal_entries::campaign_id.eq(campaign.id))
+        .filter(journal_entries::shared.eq(true))
+        .order_by((journal_entries::created_date, journal_entries::id))
+        .load::<JournalEntry>(conn)
+        .ok()?;
+    let campaign_safety = safety_entries::table
+        .filter(safety_entries::campaign_id.eq(campaign.id))
+        .order_by((safety_entries::kind, safety_entries::topic))
+        .load::<SafetyEntry>(conn)
+        .ok()?;
+    let lfg_post = lfg_posts::table
+        .filter(lfg_posts::campaign_id.eq(campaign.id))
+        .first::<LfgPost>(conn)
+        .optional()
+        .ok()?;
+    let questions = questionnaire_questions::table
+        .filter(questionnaire_questions::campaign_id.eq(campaign.id))
+        .order_by((
+            questionnaire_questions::position,
+            questionnaire_questions::id,
+        ))
+        .load::<QuestionnaireQuestion>(conn)
+        .ok()?;
+    let question_ids = questions.iter().map(|q| q.id).collect::<Vec<i32>>();
+    let answers = questionnaire_answers::table
+        .filter(questionnaire_answers::question_id.eq_any(&question_ids))
+        .order_by(questionnaire_answers::answered_date)
+        .load::<QuestionnaireAnswer>(conn)
+        .ok()?;
+
+    let npc_name = |npc_id: Option<i32>| {
+        campaign_npcs
+            .iter()
+            .find(|n| Some(n.id) == npc_id)
+            .map(|n| n.name.clone())
+    };
+    let exported_quests = campaign_quests
+        .into_iter()
+        .map(|q| ExportedQuest {
+            giver: npc_name(q.giver_npc_id),
+            title: q.title,
+            description: q.description,
+            reward: q.reward,
+            status: q.status,
+            author_id: q.author_id,
+            created_date: q.created_date,
+            updated_date: q.updated_date,
+            objectives: objectives
+                .iter()
+                .filter(|o| o.quest_id == q.id)
+                .map(|o| ExportedQuestObjective {
+                    description: o.description.clone(),
+                    completed: o.completed,
+                    created_date: o.created_date,
+                    completed_date: o.completed_date,
+                })
+                .collect(),
+        })
+        .collect();
+    let exported_calendar = calendar.map(|c| ExportedCalendar {
+        name: c.name,
+        month_names: c.month_names,
+        month_lengths: c.month_lengths,
+        weekdays: c.weekdays,
+        current_day: c.current_day,
+        updated_date: c.updated_date,
+        holidays: holidays
+            .into_iter()
+            .map(|h| ExportedCalendarDay {
+                name: h.name,
+                month: h.month,
+                day: h.day,
+            })
+            .collect(),
+        seasons: seasons
+            .into_iter()
+            .map(|s| ExportedCalendarDay {
+                name: s.name,
+                month: s.month,
+                day: s.day,
+            })
+            .collect(),
+        events: events
+            .into_iter()
+            .map(|e| ExportedCalendarEvent {
+                name: e.name,
+                description: e.description,
+                day: e.day,
+                author_id: e.author_id,
+                created_date: e.created_date,
+            })
+            .collect(),
+    });
+
+    Some(CampaignExport {
+        version: EXPORT_VERSION,
+        exported_date: chrono::Utc::now().naive_utc(),
+        campaign: ExportedCampaign {
+            name: campaign.name.clone(),
+            description: campaign.description.clone(),
+            link: campaign.link.clone(),
+            dm_id: campaign.dm_id,
+            created_date: campaign.created_date,
+            min_players: campaign.min_players,
+            quorum_deadline_hours: campaign.quorum_deadline_hours,
+            quorum_action: campaign.quorum_action,
+            import_event_interest: campaign.import_event_interest,
+        },
+        members: members
+            .into_iter()
+            .map(|m| ExportedMember {
+                user_id: m.user_id,
+                role: m.role,
+                status: m.status,
+                added_by: m.added_by,
+                joined_date: m.joined_date,
+            })
+            .collect(),
+        characters: campaign_characters
+            .into_iter()
+            .map(|c| ExportedCharacter {
+                player_id: c.player_id,
+                name: c.name,
+                race: c.race,
+                class: c.class,
+            })
+            .collect(),
+        sessions: campaign_sessions
+            .into_iter()
+            .map(|s| ExportedSession {
+                author_id: s.author_id,
+                location: s.location,
+                status: s.status,
+                created_date: s.created_date,
+                scheduled_date: s.scheduled_date,
+                max_players: s.max_players,
+                recap: s.recap,
+                dm_notes: s.dm_notes,
+                ingame_start_day: s.ingame_start_day,
+                ingame_end_day: s.ingame_end_day,
+                responses: session_responses
+                    .iter()
+                    .filter(|r| r.session_id == s.id)
+                    .map(|r| ExportedResponse {
+                        respondee_id: r.respondee_id,
+                        response: r.response,
+                        responded_date: r.responded_date,
+                        waitlisted: r.waitlisted,
+                    })
+                    .collect(),
+                attendance: session_attendance
+                    .iter()
+                    .filter(|a| a.session_id == s.id)
+                    .map(|a| ExportedAttendance {
+                        player_id: a.player_id,
+                        attended: a.attended,
+                        marked_by: a.marked_by,
+                        marked_date: a.marked_date,
+                        late_minutes: a.late_minutes,
+                    })
+                    .collect(),
+                voice_checkins: session_checkins
+                    .iter()
+                    .filter(|c| c.session_id == s.id)
+                    .map(|c| ExportedVoiceCheckin {
+                        user_id: c.user_id,
+                        joined_date: c.joined_date,
+                        left_date: c.left_date,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        polls: campaign_polls
+            .into_iter()
+            .map(|p| ExportedPoll {
+                author_id: p.author_id,
+                location: p.location,
+                closed: p.closed,
+                created_date: p.created_date,
+                options: options
+                    .iter()
+                    .filter(|o| o.poll_id == p.id)
+                    .map(|o| ExportedPollOption {
+                        scheduled_date: o.scheduled_date,
+                        votes: votes
+                            .iter()
+                            .filter(|v| v.poll_option_id == o.id)
+                            .map(|v| ExportedPollVote {
+                                voter_id: v.voter_id,
+                                voted_date: v.voted_date,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        lore: entries
+            .into_iter()
+            .map(|l| ExportedLoreEntry {
+                title: l.title,
+                content: l.content,
+                tags: l.tags,
+                dm_only: l.dm_only,
+                author_id: l.author_id,
+                created_date: l.created_date,
+                updated_date: l.updated_date,
+            })
+            .collect(),
+        npcs: campaign_npcs
+            .into_iter()
+            .map(|n| ExportedNpc {
+                name: n.name,
+                description: n.description,
+                location: n.location,
+                faction: n.faction,
+                attitude: n.attitude,
+                status: n.status,
+                secrets: n.secrets,
+                revealed: n.revealed,
+                author_id: n.author_id,
+                created_date: n.created_date,
+                updated_date: n.updated_date,
+            })
+            .collect(),
+        quests: exported_quests,
+        calendar: exported_calendar,
+        journal_entries: shared_entries
+            .into_iter()
+            .map(|j| ExportedJournalEntry {
+                author_id: j.author_id,
+                session: session_ids.iter().position(|id| Some(*id) == j.session_id),
+                title: j.title,
+                content: j.content,
+                created_date: j.created_date,
+                updated_date: j.updated_date,
+            })
+            .collect(),
+        safety: campaign_safety
+            .into_iter()
+            .map(|s| ExportedSafetyEntry {
+                kind: s.kind,
+                topic: s.topic,
+                created_date: s.created_date,
+            })
+            .collect(),
+        lfg: lfg_post.and_then(|p| {
+            Some(ExportedLfgPost {
+                session: session_ids.iter().position(|id| *id == p.session_id)?,
+                system: p.system,
+                min_level: p.min_level,
+                max_level: p.max_level,
+                content_warnings: p.content_warnings,
+                closed: p.closed,
+                created_date: p.created_date,
+            })
+        }),
+        questionnaire: questions
+            .into_iter()
+            .map(|q| ExportedQuestion {
+                answers: answers
+                    .iter()
+                    .filter(|a| a.question_id == q.id)
+                    .map(|a| ExportedAnswer {
+                        user_id: a.user_id,
+                        answer: a.answer.clone(),
+                        answered_date: a.answered_date,
+                    })
+                    .collect(),
+                position: q.position,
+                prompt: q.prompt,
+                placeholder: q.placeholder,
+                paragraph: q.paragraph,
+                required: q.required,
+                created_date: q.created_date,
+            })
+            .collect(),
+        handouts: campaign_handouts
+            .into_iter()
+            .map(
+                |(handout_id, title, content, file_name, author_id, created_date)| {
+                    ExportedHandout {
+                        title,
+                        content,
+                        file_name,
+                        author_id,
+                        created_date,
+                        recipients: recipients
+                            .iter()
+                            .filter(|r| r.handout_id == handout_id)
+                            .map(|r| ExportedHandoutRecipient {
+                                user_id: r.user_id,
+                                revealed_by: r.revealed_by,
+                                revealed_date: r.revealed_date,
+                            })
+                            .collect(),
+                    }
+                },
+            )
+            .collect(),
+    })
+}
+
+/// Recreates an exported campaign in a guild under the given name
+///
+/// Users missing from the guild are dropped from the roster, and the importer takes their
+/// place wherever someone is required, like the DM or a session's author
+/// History such as responses and attendance keeps the original user IDs, while questionnaire
+/// answers and handout recipients are only kept for users who are in the guild
+///
+/// Nothing is imported if any part of the export can't be saved
+pub fn import_campaign(
+    data: &Data,
+    guild_id_i64: i64,
+    name_str: &str,
+    export: &CampaignExport,
+    present_user_ids: &BTreeSet<i64>,
+    importer_id: i64,
+) -> QueryResult<Campaign> {
+    use crate::schema::{
+        attendance, calendar_events, calendar_holidays, calendar_seasons, calendars,
+        campaign_members, campaigns, characters, handout_recipients, handouts, journal_entries,
+        lfg_posts, lore_entries, npcs, poll_options, poll_votes, polls, quest_objectives,
+        questionnaire_answers, questionnaire_questions, quests, responses, safety_entries,
+        sessions, voice_checkins,
+    };
+
+    let conn = &mut get_data_conn(data);
+
+    let required = |user_id: i64| {
+        if present_user_ids.contains(&user_id) {
+            user_id
+        } else {
+            importer_id
+        }
+    };
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let campaign = diesel::insert_into(campaigns::table)
+            .values((
+                campaigns::guild_id.eq(guild_id_i64),
+                campaigns::dm_id.eq(required(export.campaign.dm_id)),
+                campaigns::name.eq(name_str),
+                campaigns::description.eq(&export.campaign.description),
+                campaigns::link.eq(&export.campaign.link),
+                campaigns::created_date.eq(export.campaign.created_date),
+                campaigns::min_players.eq(export.campaign.min_players),
+                campaigns::quorum_deadline_hours.eq(export.campaign.quorum_deadline_hours),
+                campaigns::quorum_action.eq(export.campaign.quorum_action),
+                campaigns::import_event_interest.eq(export.campaign.import_event_interest),
+            ))
+            .get_result::<Campaign>(conn)?;
+
+        for member in export
+            .members
+            .iter()
+            .filter(|m| present_user_ids.contains(&m.user_id) && m.user_id != campaign.dm_id)
+        {
+            diesel::insert_into(campaign_members::table)
+                .values((
+                    campaign_members::campaign_id.eq(campaign.id),
+                    campaign_members::user_id.eq(member.user_id),
+                    campaign_members::role.eq(member.role),
+                    campaign_members::status.eq(member.status),
+                    campaign_members::added_by.eq(required(member.added_by)),
+                    campaign_members::joined_date.eq(member.joined_date),
+                ))
+                .execute(conn)?;
+        }
+
+        for character in &export.characters {
+            diesel::insert_into(characters::table)
+                .values((
+                    characters::campaign_id.eq(campaign.id),
+                    characters::player_id.eq(character.player_id),
+                    characters::name.eq(&character.name),
+                    characters::race.eq(&character.race),
+                    characters::class.eq(&character.class),
+                ))
+                .execute(conn)?;
+        }
+
+        let mut session_ids = vec![];
+
+        for session in &export.sessions {
+            let session_id = diesel::insert_into(sessions::table)
+                .values((
+                    sessions::campaign_id.eq(campaign.id),
+                    sessions::author_id.eq(required(session.author_id)),
+                    sessions::location.eq(&session.location),
+                    sessions::status.eq(session.status),
+                    sessions::created_date.eq(session.created_date),
+                    sessions::scheduled_date.eq(session.scheduled_date),
+                    sessions::max_players.eq(session.max_players),
+                    sessions::recap.eq(&session.recap),
+                    sessions::dm_notes.eq(&session.dm_notes),
+                    sessions::ingame_start_day.eq(session.ingame_start_day),
+                    sessions::ingame_end_day.eq(session.ingame_end_day),
+                ))
+                .returning(sessions::id)
+                .get_result::<i32>(conn)?;
+
+            session_ids.push(session_id);
+
+            for response in &session.responses {
+                diesel::insert_into(responses::table)
+                    .values((
+                        responses::session_id.eq(session_id),
+                        responses::respondee_id.eq(response.respondee_id),
+                        responses::response.eq(response.response),
+                        responses::responded_date.eq(response.responded_date),
+                        responses::waitlisted.eq(response.waitlisted),
+                    ))
+                    .execute(conn)?;
+            }
+
+            for marked in &session.attendance {
+                diesel::insert_into(attendance::table)
+                    .values((
+                        attendance::session_id.eq(session_id),
+                        attendance::player_id.eq(marked.player_id),
+                        attendance::attended.eq(marked.attended),
+                        attendance::marked_by.eq(marked.marked_by),
+                        attendance::marked_date.eq(marked.marked_date),
+                        attendance::late_minutes.eq(marked.late_minutes),
+                    ))
+                    .execute(conn)?;
+            }
+
+            for checkin in &session.voice_checkins {
+                diesel::insert_into(voice_checkins::table)
+                    .values((
+                        voice_checkins::session_id.eq(session_id),
+                        voice_checkins::user_id.eq(checkin.user_id),
+                        voice_checkins::joined_date.eq(checkin.joined_date),
+                        voice_checkins::left_date.eq(checkin.left_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        for poll in &export.polls {
+            let poll_id = diesel::insert_into(polls::table)
+                .values((
+                    polls::campaign_id.eq(campaign.id),
+                    polls::author_id.eq(required(poll.author_id)),
+                    polls::location.eq(&poll.location),
+                    polls::closed.eq(poll.closed),
+                    polls::created_date.eq(poll.created_date),
+                ))
+                .returning(polls::id)
+                .get_result::<i32>(conn)?;
+
+            for option in &poll.options {
+                let poll_option_id = diesel::insert_into(poll_options::table)
+                    .values((
+                        poll_options::poll_id.eq(poll_id),
+                        poll_options::scheduled_date.eq(option.scheduled_date),
+                    ))
+                    .returning(poll_options::id)
+                    .get_result::<i32>(conn)?;
+
+                for vote in &option.votes {
+                    diesel::insert_into(poll_votes::table)
+                        .values((
+                            poll_votes::poll_option_id.eq(poll_option_id),
+                            poll_votes::voter_id.eq(vote.voter_id),
+                            poll_votes::voted_date.eq(vote.voted_date),
+                        ))
+                        .execute(conn)?;
+                }
+            }
+        }
+
+        for entry in &export.lore {
+            diesel::insert_into(lore_entries::table)
+                .values((
+                    lore_entries::campaign_id.eq(campaign.id),
+                    lore_entries::title.eq(&entry.title),
+                    lore_entries::content.eq(&entry.content),
+                    lore_entries::tags.eq(&entry.tags),
+                    lore_entries::dm_only.eq(entry.dm_only),
+                    lore_entries::author_id.eq(required(entry.author_id)),
+                    lore_entries::created_date.eq(entry.created_date),
+                    lore_entries::updated_date.eq(entry.updated_date),
+                ))
+                .execute(conn)?;
+        }
+
+        let mut npc_ids = std::collections::HashMap::new();
+
+        for npc in &export.npcs {
+            let npc_id = diesel::insert_into(npcs::table)
+                .values((
+                    npcs::campaign_id.eq(campaign.id),
+                    npcs::name.eq(&npc.name),
+                    npcs::description.eq(&npc.description),
+                    npcs::location.eq(&npc.location),
+                    npcs::faction.eq(&npc.faction),
+                    npcs::attitude.eq(npc.attitude),
+                    npcs::status.eq(npc.status),
+                    npcs::secrets.eq(&npc.secrets),
+                    npcs::revealed.eq(&npc.revealed),
+                    npcs::author_id.eq(required(npc.author_id)),
+                    npcs::created_date.eq(npc.created_date),
+                    npcs::updated_date.eq(npc.updated_date),
+                ))
+                .returning(npcs::id)
+                .get_result::<i32>(conn)?;
+
+            npc_ids.insert(npc.name.as_str(), npc_id);
+        }
+
+        for quest in &export.quests {
+            let quest_id = diesel::insert_into(quests::table)
+                .values((
+                    quests::campaign_id.eq(campaign.id),
+                    quests::title.eq(&quest.title),
+                    quests::giver_npc_id.eq(quest
+                        .giver
+                        .as_deref()
+                        .and_then(|giver| npc_ids.get(giver).copied())),
+                    quests::description.eq(&quest.description),
+                    quests::reward.eq(&quest.reward),
+                    quests::status.eq(quest.status),
+                    quests::author_id.eq(required(quest.author_id)),
+                    quests::created_date.eq(quest.created_date),
+                    quests::updated_date.eq(quest.updated_date),
+                ))
+                .returning(quests::id)
+                .get_result::<i32>(conn)?;
+
+            for objective in &quest.objectives {
+                diesel::insert_into(quest_objectives::table)
+                    .values((
+                        quest_objectives::quest_id.eq(quest_id),
+                        quest_objectives::description.eq(&objective.description),
+                        quest_objectives::completed.eq(objective.completed),
+                        quest_objectives::created_date.eq(objective.created_date),
+                        quest_objectives::completed_date.eq(objective.completed_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        if let Some(calendar) = &export.calendar {
+            diesel::insert_into(calendars::table)
+                .values((
+                    calendars::campaign_id.eq(campaign.id),
+                    calendars::name.eq(&calendar.name),
+                    calendars::month_names.eq(&calendar.month_names),
+                    calendars::month_lengths.eq(&calendar.month_lengths),
+                    calendars::weekdays.eq(&calendar.weekdays),
+                    calendars::current_day.eq(calendar.current_day),
+                    calendars::updated_date.eq(calendar.updated_date),
+                ))
+                .execute(conn)?;
+
+            for holiday in &calendar.holidays {
+                diesel::insert_into(calendar_holidays::table)
+                    .values((
+                        calendar_holidays::campaign_id.eq(campaign.id),
+                        calendar_holidays::name.eq(&holiday.name),
+                        calendar_holidays::month.eq(holiday.month),
+                        calendar_holidays::day.eq(holiday.day),
+                    ))
+                    .execute(conn)?;
+            }
+
+            for season in &calendar.seasons {
+                diesel::insert_into(calendar_seasons::table)
+                    .values((
+                        calendar_seasons::campaign_id.eq(campaign.id),
+                        calendar_seasons::name.eq(&season.name),
+                        calendar_seasons::month.eq(season.month),
+                        calendar_seasons::day.eq(season.day),
+                    ))
+                    .execute(conn)?;
+            }
+
+            for event in &calendar.events {
+                diesel::insert_into(calendar_events::table)
+                    .values((
+                        calendar_events::campaign_id.eq(campaign.id),
+                        calendar_events::name.eq(&event.name),
+                        calendar_events::description.eq(&event.description),
+                        calendar_events::day.eq(event.day),
+                        calendar_events::author_id.eq(required(event.author_id)),
+                        calendar_events::created_date.eq(event.created_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        for entry in &export.journal_entries {
+            diesel::insert_into(journal_entries::table)
+                .values((
+                    journal_entries::campaign_id.eq(campaign.id),
+                    journal_entries::author_id.eq(required(entry.author_id)),
+                    journal_entries::session_id.eq(entry
+                        .session
+                        .and_then(|index| session_ids.get(index).copied())),
+                    journal_entries::title.eq(&entry.title),
+                    journal_entries::content.eq(&entry.content),
+                    journal_entries::shared.eq(true),
+                    journal_entries::created_date.eq(entry.created_date),
+                    journal_entries::updated_date.eq(entry.updated_date),
+                ))
+                .execute(conn)?;
+        }
+
+        for entry in &export.safety {
+            diesel::insert_into(safety_entries::table)
+                .values((
+                    safety_entries::campaign_id.eq(campaign.id),
+                    safety_entries::kind.eq(entry.kind),
+                    safety_entries::topic.eq(&entry.topic),
+                    safety_entries::created_date.eq(entry.created_date),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+        }
+
+        if let Some(post) = &export.lfg {
+            if let Some(session_id) = session_ids.get(post.session).copied() {
+                diesel::insert_into(lfg_posts::table)
+                    .values((
+                        lfg_posts::campaign_id.eq(campaign.id),
+                        lfg_posts::session_id.eq(session_id),
+                        lfg_posts::system.eq(&post.system),
+                        lfg_posts::min_level.eq(post.min_level),
+                        lfg_posts::max_level.eq(post.max_level),
+                        lfg_posts::content_warnings.eq(&post.content_warnings),
+                        lfg_posts::closed.eq(true),
+                        lfg_posts::created_date.eq(post.created_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        for question in &export.questionnaire {
+            let question_id = diesel::insert_into(questionnaire_questions::table)
+                .values((
+                    questionnaire_questions::campaign_id.eq(campaign.id),
+                    questionnaire_questions::position.eq(question.position),
+                    questionnaire_questions::prompt.eq(&question.prompt),
+                    questionnaire_questions::placeholder.eq(&question.placeholder),
+                    questionnaire_questions::paragraph.eq(question.paragraph),
+                    questionnaire_questions::required.eq(question.required),
+                    questionnaire_questions::created_date.eq(question.created_date),
+                ))
+                .returning(questionnaire_questions::id)
+                .get_result::<i32>(conn)?;
+
+            for answer in question
+                .answers
+                .iter()
+                .filter(|a| present_user_ids.contains(&a.user_id))
+            {
+                diesel::insert_into(questionnaire_answers::table)
+                    .values((
+                        questionnaire_answers::question_id.eq(question_id),
+                        questionnaire_answers::user_id.eq(answer.user_id),
+                        questionnaire_answers::answer.eq(&answer.answer),
+                        questionnaire_answers::answered_date.eq(answer.answered_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        for handout in &export.handouts {
+            let handout_id = diesel::insert_into(handouts::table)
+                .values((
+                    handouts::campaign_id.eq(campaign.id),
+                    handouts::title.eq(&handout.title),
+                    handouts::content.eq(&handout.content),
+                    handouts::file_name.eq(&handout.file_name),
+                    handouts::author_id.eq(required(handout.author_id)),
+                    handouts::created_date.eq(handout.created_date),
+                ))
+                .returning(handouts::id)
+                .get_result::<i32>(conn)?;
+
+            for recipient in handout
+                .recipients
+                .iter()
+                .filter(|r| present_user_ids.contains(&r.user_id))
+            {
+                diesel::insert_into(handout_recipients::table)
+                    .values((
+                        handout_recipients::handout_id.eq(handout_id),
+                        handout_recipients::user_id.eq(recipient.user_id),
+                        handout_recipients::revealed_by.eq(required(recipient.revealed_by)),
+                        handout_recipients::revealed_date.eq(recipient.revealed_date),
+                    ))
+                    .execute(conn)?;
+            }
+        }
+
+        Ok(campaign)
+    })
+}
@@ -0,0 +1,172 @@
+use crate::models::{Handout, HandoutRecipient, NewHandout, NewHandoutRecipient};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::prelude::*;
+
+pub fn create_handout(ctx: Context<'_>, handout: NewHandout) -> Handout {
+    use crate::schema::handouts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(handouts)
+        .values(&handout)
+        .get_result::<Handout>(conn)
+        .expect("Error saving handout")
+}
+
+pub fn get_handout(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> Option<Handout> {
+    use crate::schema::handouts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    handouts
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(title.eq(title_str))
+        .first::<Handout>(conn)
+        .ok()
+}
+
+pub fn does_handout_exist(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> bool {
+    use crate::schema::handouts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::select(diesel::dsl::exists(
+        handouts
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(title.eq(title_str)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+pub fn delete_handout(ctx: Context<'_>, handout_id: i32) {
+    use crate::schema::handouts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(handouts.find(handout_id))
+        .execute(conn)
+        .expect("Error deleting handout");
+}
+
+/// Gets the ID, title and file name of every handout of a campaign, without the files themselves
+pub fn get_handout_summaries(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+) -> Vec<(i32, String, Option<String>)> {
+    use crate::schema::handouts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    handouts
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select((id, title, file_name))
+        .order_by(title)
+        .load::<(i32, String, Option<String>)>(conn)
+        .unwrap_or_default()
+}
+
+/// Gets the titles of a campaign's handouts, or only those a user has received when one is given
+pub fn get_handout_titles(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    recipient_id: Option<i64>,
+) -> Option<Vec<String>> {
+    use crate::schema::{handout_recipients, handouts};
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = handouts::table
+        .filter(handouts::campaign_id.eq(campaign_id_i32))
+        .select(handouts::title)
+        .order_by(handouts::title)
+        .into_boxed();
+
+    if let Some(recipient_id) = recipient_id {
+        query = query.filter(
+            handouts::id.eq_any(
+                handout_recipients::table
+                    .filter(handout_recipients::user_id.eq(recipient_id))
+                    .select(handout_recipients::handout_id),
+            ),
+        );
+    }
+
+    query.load::<String>(conn).ok()
+}
+
+/// Records that users received a handout, returning how many hadn't received it before
+pub fn add_recipients(
+    ctx: Context<'_>,
+    handout_id_i32: i32,
+    user_ids: &[i64],
+    revealed_by_i64: i64,
+) -> usize {
+    use crate::schema::handout_recipients::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+    let now = chrono::Utc::now().naive_utc();
+
+    diesel::insert_into(handout_recipients)
+        .values(
+            user_ids
+                .iter()
+                .map(|recipient_id| NewHandoutRecipient {
+                    handout_id: handout_id_i32,
+                    user_id: *recipient_id,
+                    revealed_by: revealed_by_i64,
+                    revealed_date: now,
+                })
+                .collect::<Vec<NewHandoutRecipient>>(),
+        )
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving handout recipients")
+}
+
+pub fn get_recipients(ctx: Context<'_>, handout_ids: &[i32]) -> Vec<HandoutRecipient> {
+    use crate::schema::handout_recipients::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    handout_recipients
+        .filter(handout_id.eq_any(handout_ids))
+        .order_by(revealed_date)
+        .load::<HandoutRecipient>(conn)
+        .unwrap_or_default()
+}
+
+pub fn has_received(ctx: Context<'_>, handout_id_i32: i32, user_id_i64: i64) -> bool {
+    use crate::schema::handout_recipients::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::select(diesel::dsl::exists(
+        handout_recipients
+            .filter(handout_id.eq(handout_id_i32))
+            .filter(user_id.eq(user_id_i64)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+/// Gets the titles of the handouts a user has received in a campaign, with when they got each
+pub fn get_received_handouts(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    user_id_i64: i64,
+) -> Vec<(String, chrono::NaiveDateTime)> {
+    use crate::schema::{handout_recipients, handouts};
+
+    let conn = &mut get_conn(ctx);
+
+    handout_recipients::table
+        .inner_join(handouts::table)
+        .filter(handouts::campaign_id.eq(campaign_id_i32))
+        .filter(handout_recipients::user_id.eq(user_id_i64))
+        .select((handouts::title, handout_recipients::revealed_date))
+        .order_by(handout_recipients::revealed_date)
+        .load::<(String, chrono::NaiveDateTime)>(conn)
+        .unwrap_or_default()
+}
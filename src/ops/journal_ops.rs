@@ -0,0 +1,141 @@
+use crate::models::{JournalEntry, NewJournalEntry};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Float, Text};
+
+const SEARCH_LIMIT: i64 = 25;
+
+pub fn create_entry(ctx: Context<'_>, entry: NewJournalEntry) -> JournalEntry {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(journal_entries)
+        .values(&entry)
+        .get_result::<JournalEntry>(conn)
+        .expect("Error saving journal entry")
+}
+
+pub fn update_entry(ctx: Context<'_>, entry: JournalEntry) {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(journal_entries.find(entry.id))
+        .set(&entry)
+        .execute(conn)
+        .expect("Error updating journal entry");
+}
+
+pub fn delete_entry(ctx: Context<'_>, entry_id: i32) {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(journal_entries.find(entry_id))
+        .execute(conn)
+        .expect("Error deleting journal entry");
+}
+
+pub fn get_entry(ctx: Context<'_>, entry_id: i32) -> Option<JournalEntry> {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    journal_entries
+        .find(entry_id)
+        .first::<JournalEntry>(conn)
+        .ok()
+}
+
+/// Gets a user's entries in a campaign, oldest first
+///
+/// Entries other players shared with the party are included when `include_shared` is set
+pub fn get_entries(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    author_id_i64: i64,
+    include_shared: bool,
+) -> Vec<JournalEntry> {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = journal_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .into_boxed();
+
+    if include_shared {
+        query = query.filter(author_id.eq(author_id_i64).or(shared.eq(true)));
+    } else {
+        query = query.filter(author_id.eq(author_id_i64));
+    }
+
+    query
+        .order_by((created_date, id))
+        .load::<JournalEntry>(conn)
+        .unwrap_or_default()
+}
+
+/// Full-text searches the entries a user can read in a campaign, best matches first
+///
+/// These are their own entries and, if asked for, those shared with the party
+pub fn search_entries(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    author_id_i64: i64,
+    include_shared: bool,
+    search: &str,
+) -> Vec<JournalEntry> {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    journal_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(
+            author_id
+                .eq(author_id_i64)
+                .or(shared.eq(true).and(include_shared.into_sql::<Bool>())),
+        )
+        .filter(
+            sql::<Bool>("search_vector @@ websearch_to_tsquery('english', ")
+                .bind::<Text, _>(search.to_string())
+                .sql(")"),
+        )
+        .order_by(
+            sql::<Float>("ts_rank(search_vector, websearch_to_tsquery('english', ")
+                .bind::<Text, _>(search.to_string())
+                .sql("))")
+                .desc(),
+        )
+        .then_order_by(created_date)
+        .limit(SEARCH_LIMIT)
+        .load::<JournalEntry>(conn)
+        .unwrap_or_default()
+}
+
+/// Gets the entries about a session that a user can read, oldest first
+pub fn get_session_entries(
+    ctx: Context<'_>,
+    session_id_i32: i32,
+    author_id_i64: i64,
+    include_shared: bool,
+) -> Vec<JournalEntry> {
+    use crate::schema::journal_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    journal_entries
+        .filter(session_id.eq(session_id_i32))
+        .filter(
+            author_id
+                .eq(author_id_i64)
+                .or(shared.eq(true).and(include_shared.into_sql::<Bool>())),
+        )
+        .order_by((created_date, id))
+        .load::<JournalEntry>(conn)
+        .unwrap_or_default()
+}
@@ -0,0 +1,131 @@
+use crate::models::{Campaign, LfgPost, NewCampaign, NewLfgPost, NewSession, Session};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+
+/// Saves a one-shot campaign together with its session and post
+///
+/// The campaign and session IDs of the session and post are filled in here
+pub fn create_post(
+    ctx: Context<'_>,
+    campaign: NewCampaign,
+    session: NewSession,
+    post: NewLfgPost,
+) -> QueryResult<(Campaign, Session, LfgPost)> {
+    use crate::schema::{campaigns, lfg_posts, sessions};
+
+    let conn = &mut get_conn(ctx);
+
+    conn.transaction(|conn| {
+        let campaign = diesel::insert_into(campaigns::table)
+            .values(&campaign)
+            .get_result::<Campaign>(conn)?;
+
+        let session = diesel::insert_into(sessions::table)
+            .values(&NewSession {
+                campaign_id: campaign.id,
+                ..session
+            })
+            .get_result::<Session>(conn)?;
+
+        let post = diesel::insert_into(lfg_posts::table)
+            .values(&NewLfgPost {
+                campaign_id: campaign.id,
+                session_id: session.id,
+                ..post
+            })
+            .get_result::<LfgPost>(conn)?;
+
+        diesel::QueryResult::Ok((campaign, session, post))
+    })
+}
+
+pub fn set_message_id(ctx: Context<'_>, post_id: i32, message_id_i64: i64) {
+    use crate::schema::lfg_posts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(lfg_posts.find(post_id))
+        .set(message_id.eq(message_id_i64))
+        .execute(conn)
+        .expect("Error updating LFG post");
+}
+
+pub fn get_post(ctx: Context<'_>, campaign_id_i32: i32) -> Option<LfgPost> {
+    use crate::schema::lfg_posts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    lfg_posts
+        .filter(campaign_id.eq(campaign_id_i32))
+        .first::<LfgPost>(conn)
+        .ok()
+}
+
+/// Gets a post along with the one-shot campaign and session it advertises
+pub fn get_post_with_details(data: &Data, post_id: i32) -> Option<(LfgPost, Campaign, Session)> {
+    use crate::schema::{campaigns, lfg_posts, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    lfg_posts::table
+        .find(post_id)
+        .inner_join(campaigns::table)
+        .inner_join(sessions::table)
+        .select((
+            lfg_posts::all_columns,
+            campaigns::all_columns,
+            sessions::all_columns,
+        ))
+        .first::<(LfgPost, Campaign, Session)>(conn)
+        .ok()
+}
+
+/// Gets the open posts of a guild, soonest first
+pub fn get_open_posts(ctx: Context<'_>, guild_id_i64: i64) -> Vec<(LfgPost, Campaign, Session)> {
+    use crate::schema::{campaigns, lfg_posts, sessions};
+
+    let conn = &mut get_conn(ctx);
+
+    lfg_posts::table
+        .inner_join(campaigns::table)
+        .inner_join(sessions::table)
+        .filter(campaigns::guild_id.eq(guild_id_i64))
+        .filter(campaigns::deleted.eq(false))
+        .filter(lfg_posts::closed.eq(false))
+        .order_by(sessions::scheduled_date)
+        .select((
+            lfg_posts::all_columns,
+            campaigns::all_columns,
+            sessions::all_columns,
+        ))
+        .load::<(LfgPost, Campaign, Session)>(conn)
+        .unwrap_or_default()
+}
+
+pub fn get_open_post_names(ctx: Context<'_>, guild_id_i64: i64) -> Option<Vec<String>> {
+    use crate::schema::{campaigns, lfg_posts};
+
+    let conn = &mut get_conn(ctx);
+
+    lfg_posts::table
+        .inner_join(campaigns::table)
+        .filter(campaigns::guild_id.eq(guild_id_i64))
+        .filter(campaigns::deleted.eq(false))
+        .filter(lfg_posts::closed.eq(false))
+        .select(campaigns::name)
+        .order_by(campaigns::name)
+        .load::<String>(conn)
+        .ok()
+}
+
+pub fn close_post(ctx: Context<'_>, post_id: i32) {
+    use crate::schema::lfg_posts::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(lfg_posts.find(post_id))
+        .set(closed.eq(true))
+        .execute(conn)
+        .expect("Error closing LFG post");
+}
@@ -0,0 +1,144 @@
+use crate::models::{LoreEntry, NewLoreEntry};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Float, Text};
+
+const SEARCH_LIMIT: i64 = 25;
+
+pub fn create_entry(ctx: Context<'_>, entry: NewLoreEntry) -> LoreEntry {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(lore_entries)
+        .values(&entry)
+        .get_result::<LoreEntry>(conn)
+        .expect("Error saving lore entry")
+}
+
+pub fn update_entry(ctx: Context<'_>, entry: LoreEntry) {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(lore_entries.find(entry.id))
+        .set(&entry)
+        .execute(conn)
+        .expect("Error updating lore entry");
+}
+
+pub fn get_entry(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> Option<LoreEntry> {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    lore_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(title.eq(title_str))
+        .first::<LoreEntry>(conn)
+        .ok()
+}
+
+pub fn does_entry_exist(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> bool {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::select(diesel::dsl::exists(
+        lore_entries
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(title.eq(title_str)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+/// Gets a campaign's entries in alphabetical order, optionally only those with a tag
+///
+/// DM-only entries are left out unless `include_dm_only` is set
+pub fn get_entries(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    include_dm_only: bool,
+    tag: Option<String>,
+) -> Option<Vec<LoreEntry>> {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = lore_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .into_boxed();
+
+    if !include_dm_only {
+        query = query.filter(dm_only.eq(false));
+    }
+
+    if let Some(tag) = tag {
+        query = query.filter(tags.contains(vec![tag]));
+    }
+
+    query.order_by(title).load::<LoreEntry>(conn).ok()
+}
+
+pub fn get_entry_titles(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    include_dm_only: bool,
+) -> Option<Vec<String>> {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = lore_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(title)
+        .into_boxed();
+
+    if !include_dm_only {
+        query = query.filter(dm_only.eq(false));
+    }
+
+    query.order_by(title).load::<String>(conn).ok()
+}
+
+/// Full-text searches a campaign's entries, best matches first
+///
+/// The query uses web search syntax, so quoted phrases, `or` and `-word` all work
+pub fn search_entries(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    include_dm_only: bool,
+    search: &str,
+) -> Option<Vec<LoreEntry>> {
+    use crate::schema::lore_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = lore_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(
+            sql::<Bool>("search_vector @@ websearch_to_tsquery('english', ")
+                .bind::<Text, _>(search.to_string())
+                .sql(")"),
+        )
+        .into_boxed();
+
+    if !include_dm_only {
+        query = query.filter(dm_only.eq(false));
+    }
+
+    query
+        .order_by(
+            sql::<Float>("ts_rank(search_vector, websearch_to_tsquery('english', ")
+                .bind::<Text, _>(search.to_string())
+                .sql("))")
+                .desc(),
+        )
+        .then_order_by(title)
+        .limit(SEARCH_LIMIT)
+        .load::<LoreEntry>(conn)
+        .ok()
+}
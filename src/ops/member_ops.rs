@@ -0,0 +1,127 @@
+use crate::commands::dnd::campaign::members::MEMBER_STATUS_ACTIVE;
+use crate::models::{Campaign, CampaignMember, NewCampaignMember};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+
+/// Adds a member to a campaign, replacing their role and status if they're already on the roster
+pub fn add_member(ctx: Context<'_>, new_member: NewCampaignMember) -> CampaignMember {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(campaign_members)
+        .values(&new_member)
+        .on_conflict((campaign_id, user_id))
+        .do_update()
+        .set(&new_member)
+        .get_result::<CampaignMember>(conn)
+        .expect("Error saving campaign member")
+}
+
+pub fn get_member(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    user_id_i64: i64,
+) -> Option<CampaignMember> {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    campaign_members
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(user_id.eq(user_id_i64))
+        .first::<CampaignMember>(conn)
+        .ok()
+}
+
+pub fn get_member_with_campaign(data: &Data, member_id: i32) -> Option<(CampaignMember, Campaign)> {
+    use crate::schema::{campaign_members, campaigns};
+
+    let conn = &mut get_data_conn(data);
+
+    campaign_members::table
+        .inner_join(campaigns::table)
+        .filter(campaign_members::id.eq(member_id))
+        .select((campaign_members::all_columns, campaigns::all_columns))
+        .first::<(CampaignMember, Campaign)>(conn)
+        .ok()
+}
+
+pub fn get_members(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<CampaignMember>> {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    campaign_members
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((role, joined_date))
+        .load::<CampaignMember>(conn)
+        .ok()
+}
+
+/// Gets the IDs of a campaign's active members holding any of the given roles
+pub fn get_member_ids(data: &Data, campaign_id_i32: i32, roles: &[i16]) -> Vec<i64> {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    campaign_members
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(status.eq(MEMBER_STATUS_ACTIVE))
+        .filter(role.eq_any(roles))
+        .select(user_id)
+        .load::<i64>(conn)
+        .unwrap_or_default()
+}
+
+pub fn has_roster(data: &Data, campaign_id_i32: i32) -> bool {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::select(diesel::dsl::exists(
+        campaign_members
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(status.eq(MEMBER_STATUS_ACTIVE)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+pub fn approve_member(data: &Data, member_id: i32) {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(campaign_members.find(member_id))
+        .set(status.eq(MEMBER_STATUS_ACTIVE))
+        .execute(conn)
+        .expect("Error approving campaign member");
+}
+
+pub fn delete_member_by_id(data: &Data, member_id: i32) {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::delete(campaign_members.find(member_id))
+        .execute(conn)
+        .expect("Error deleting campaign member");
+}
+
+/// Removes a user from a campaign's roster, returning whether they were on it
+pub fn delete_member(ctx: Context<'_>, campaign_id_i32: i32, user_id_i64: i64) -> bool {
+    use crate::schema::campaign_members::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        campaign_members
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(user_id.eq(user_id_i64)),
+    )
+    .execute(conn)
+    .expect("Error deleting campaign member")
+        > 0
+}
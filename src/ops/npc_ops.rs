@@ -0,0 +1,127 @@
+use crate::models::{Campaign, NewNpc, Npc};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+
+pub fn create_npc(ctx: Context<'_>, npc: NewNpc) -> Npc {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(npcs)
+        .values(&npc)
+        .get_result::<Npc>(conn)
+        .expect("Error saving NPC")
+}
+
+pub fn update_npc(ctx: Context<'_>, npc: Npc) {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(npcs.find(npc.id))
+        .set(&npc)
+        .execute(conn)
+        .expect("Error updating NPC");
+}
+
+pub fn get_npc(ctx: Context<'_>, campaign_id_i32: i32, name_str: &str) -> Option<Npc> {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    npcs.filter(campaign_id.eq(campaign_id_i32))
+        .filter(name.eq(name_str))
+        .first::<Npc>(conn)
+        .ok()
+}
+
+pub fn get_npc_by_id(ctx: Context<'_>, npc_id: i32) -> Option<Npc> {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    npcs.find(npc_id).first::<Npc>(conn).ok()
+}
+
+pub fn get_npc_with_campaign(data: &Data, npc_id: i32) -> Option<(Npc, Campaign)> {
+    use crate::schema::{campaigns, npcs};
+
+    let conn = &mut get_data_conn(data);
+
+    npcs::table
+        .inner_join(campaigns::table)
+        .filter(npcs::id.eq(npc_id))
+        .select((npcs::all_columns, campaigns::all_columns))
+        .first::<(Npc, Campaign)>(conn)
+        .ok()
+}
+
+pub fn does_npc_exist(ctx: Context<'_>, campaign_id_i32: i32, name_str: &str) -> bool {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::select(diesel::dsl::exists(
+        npcs.filter(campaign_id.eq(campaign_id_i32))
+            .filter(name.eq(name_str)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+/// Gets a campaign's NPCs in alphabetical order
+///
+/// NPCs the party hasn't met yet are left out unless `include_hidden` is set
+pub fn get_npcs(ctx: Context<'_>, campaign_id_i32: i32, include_hidden: bool) -> Option<Vec<Npc>> {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = npcs.filter(campaign_id.eq(campaign_id_i32)).into_boxed();
+
+    if !include_hidden {
+        query = query.filter(revealed.contains(vec!["name"]));
+    }
+
+    query.order_by(name).load::<Npc>(conn).ok()
+}
+
+pub fn get_npc_names(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    include_hidden: bool,
+) -> Option<Vec<String>> {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = npcs
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(name)
+        .into_boxed();
+
+    if !include_hidden {
+        query = query.filter(revealed.contains(vec!["name"]));
+    }
+
+    query.order_by(name).load::<String>(conn).ok()
+}
+
+/// Marks a field of an NPC as known to the players, returning the updated NPC
+///
+/// Returns `None` if the NPC doesn't exist or the field was already revealed
+pub fn reveal_field(data: &Data, npc_id: i32, field: &str) -> Option<Npc> {
+    use crate::schema::npcs::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(
+        npcs.find(npc_id)
+            .filter(diesel::dsl::not(revealed.contains(vec![field]))),
+    )
+    .set(revealed.eq(diesel::dsl::array_append(revealed, field)))
+    .get_result::<Npc>(conn)
+    .optional()
+    .expect("Error revealing NPC field")
+}
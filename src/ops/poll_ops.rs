@@ -0,0 +1,153 @@
+use crate::models::{NewPoll, NewPollOption, NewPollVote, Poll, PollOption};
+use crate::utils::db::get_data_conn;
+use crate::Data;
+use diesel::prelude::*;
+
+pub fn create_poll(
+    data: &Data,
+    poll: NewPoll,
+    scheduled_dates: Vec<chrono::NaiveDateTime>,
+) -> Poll {
+    use crate::schema::{poll_options, polls};
+
+    let conn = &mut get_data_conn(data);
+
+    conn.transaction(|conn| {
+        let poll = diesel::insert_into(polls::table)
+            .values(&poll)
+            .get_result::<Poll>(conn)?;
+
+        let options = scheduled_dates
+            .into_iter()
+            .map(|scheduled_date| NewPollOption {
+                poll_id: poll.id,
+                scheduled_date,
+            })
+            .collect::<Vec<NewPollOption>>();
+
+        diesel::insert_into(poll_options::table)
+            .values(&options)
+            .execute(conn)?;
+
+        diesel::QueryResult::Ok(poll)
+    })
+    .expect("Error saving new poll")
+}
+
+pub fn update_poll(data: &Data, poll: Poll) {
+    use crate::schema::polls::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(polls.find(poll.id))
+        .set(&poll)
+        .execute(conn)
+        .expect("Error updating poll");
+}
+
+/// Marks a poll as closed, returning false if it already was
+pub fn close_poll(data: &Data, poll_id_i32: i32) -> bool {
+    use crate::schema::polls::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(polls.find(poll_id_i32).filter(closed.eq(false)))
+        .set(closed.eq(true))
+        .returning(id)
+        .get_result::<i32>(conn)
+        .optional()
+        .expect("Error closing poll")
+        .is_some()
+}
+
+pub fn get_poll(data: &Data, poll_id_i32: i32) -> Option<Poll> {
+    use crate::schema::polls::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    polls.filter(id.eq(poll_id_i32)).first::<Poll>(conn).ok()
+}
+
+pub fn get_poll_from_option(data: &Data, poll_option_id_i32: i32) -> Option<Poll> {
+    use crate::schema::{poll_options, polls};
+
+    let conn = &mut get_data_conn(data);
+
+    polls::table
+        .inner_join(poll_options::table)
+        .filter(poll_options::id.eq(poll_option_id_i32))
+        .select(polls::all_columns)
+        .first::<Poll>(conn)
+        .ok()
+}
+
+/// Gets each option of a poll alongside the IDs of everyone who voted for it
+pub fn get_poll_tally(data: &Data, poll_id_i32: i32) -> Option<Vec<(PollOption, Vec<i64>)>> {
+    use crate::schema::{poll_options, poll_votes};
+
+    let conn = &mut get_data_conn(data);
+
+    let options = poll_options::table
+        .filter(poll_options::poll_id.eq(poll_id_i32))
+        .order_by(poll_options::scheduled_date)
+        .load::<PollOption>(conn)
+        .ok()?;
+
+    let votes = poll_votes::table
+        .inner_join(poll_options::table)
+        .filter(poll_options::poll_id.eq(poll_id_i32))
+        .select((poll_votes::poll_option_id, poll_votes::voter_id))
+        .load::<(i32, i64)>(conn)
+        .ok()?;
+
+    Some(
+        options
+            .into_iter()
+            .map(|option| {
+                let voters = votes
+                    .iter()
+                    .filter(|(poll_option_id, _)| *poll_option_id == option.id)
+                    .map(|(_, voter_id)| *voter_id)
+                    .collect::<Vec<i64>>();
+
+                (option, voters)
+            })
+            .collect(),
+    )
+}
+
+/// Adds the vote if it does not exist, or removes it if it does
+pub fn toggle_poll_vote(data: &Data, vote: NewPollVote) {
+    use crate::schema::poll_votes::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    let removed = diesel::delete(
+        poll_votes
+            .filter(poll_option_id.eq(vote.poll_option_id))
+            .filter(voter_id.eq(vote.voter_id)),
+    )
+    .execute(conn)
+    .expect("Error removing poll vote");
+
+    if removed == 0 {
+        diesel::insert_into(poll_votes)
+            .values(&vote)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .expect("Error saving new poll vote");
+    }
+}
+
+pub fn get_poll_campaign_name(data: &Data, poll_id_i32: i32) -> Option<String> {
+    use crate::schema::{campaigns, polls};
+
+    let conn = &mut get_data_conn(data);
+
+    polls::table
+        .inner_join(campaigns::table)
+        .filter(polls::id.eq(poll_id_i32))
+        .select(campaigns::name)
+        .first::<String>(conn)
+        .ok()
+}
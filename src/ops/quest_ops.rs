@@ -0,0 +1,157 @@
+use crate::models::{NewQuest, NewQuestObjective, Quest, QuestObjective};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::prelude::*;
+
+pub fn create_quest(ctx: Context<'_>, quest: NewQuest) -> Quest {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(quests)
+        .values(&quest)
+        .get_result::<Quest>(conn)
+        .expect("Error saving quest")
+}
+
+pub fn update_quest(ctx: Context<'_>, quest: Quest) {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(quests.find(quest.id))
+        .set(&quest)
+        .execute(conn)
+        .expect("Error updating quest");
+}
+
+pub fn get_quest(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> Option<Quest> {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quests
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(title.eq(title_str))
+        .first::<Quest>(conn)
+        .ok()
+}
+
+pub fn does_quest_exist(ctx: Context<'_>, campaign_id_i32: i32, title_str: &str) -> bool {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::select(diesel::dsl::exists(
+        quests
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(title.eq(title_str)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+pub fn get_quests(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<Quest>> {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quests
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((status, title))
+        .load::<Quest>(conn)
+        .ok()
+}
+
+pub fn get_quest_titles(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<String>> {
+    use crate::schema::quests::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quests
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(title)
+        .order_by(title)
+        .load::<String>(conn)
+        .ok()
+}
+
+/// Adds an objective to a quest, or returns `None` if the quest already has one just like it
+pub fn add_objective(ctx: Context<'_>, objective: NewQuestObjective) -> Option<QuestObjective> {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(quest_objectives)
+        .values(&objective)
+        .on_conflict_do_nothing()
+        .get_result::<QuestObjective>(conn)
+        .optional()
+        .expect("Error saving quest objective")
+}
+
+pub fn get_objective(
+    ctx: Context<'_>,
+    quest_id_i32: i32,
+    description_str: &str,
+) -> Option<QuestObjective> {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quest_objectives
+        .filter(quest_id.eq(quest_id_i32))
+        .filter(description.eq(description_str))
+        .first::<QuestObjective>(conn)
+        .ok()
+}
+
+/// Gets the objectives of the given quests in the order they were added
+pub fn get_objectives(ctx: Context<'_>, quest_ids: &[i32]) -> Vec<QuestObjective> {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quest_objectives
+        .filter(quest_id.eq_any(quest_ids))
+        .order_by((created_date, id))
+        .load::<QuestObjective>(conn)
+        .unwrap_or_default()
+}
+
+pub fn get_objective_descriptions(ctx: Context<'_>, quest_id_i32: i32) -> Option<Vec<String>> {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    quest_objectives
+        .filter(quest_id.eq(quest_id_i32))
+        .select(description)
+        .order_by((created_date, id))
+        .load::<String>(conn)
+        .ok()
+}
+
+pub fn set_objective_completed(ctx: Context<'_>, objective_id: i32, completed_bool: bool) {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(quest_objectives.find(objective_id))
+        .set((
+            completed.eq(completed_bool),
+            completed_date.eq(completed_bool.then(|| chrono::Utc::now().naive_utc())),
+        ))
+        .execute(conn)
+        .expect("Error updating quest objective");
+}
+
+pub fn delete_objective(ctx: Context<'_>, objective_id: i32) {
+    use crate::schema::quest_objectives::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(quest_objectives.find(objective_id))
+        .execute(conn)
+        .expect("Error deleting quest objective");
+}
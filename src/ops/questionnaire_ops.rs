@@ -0,0 +1,156 @@
+use crate::models::{
+    NewQuestionnaireAnswer, NewQuestionnaireQuestion, QuestionnaireAnswer, QuestionnaireQuestion,
+};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+
+/// Adds questions to the end of a campaign's questionnaire, skipping those it already has
+///
+/// Returns how many questions were added
+pub fn add_questions(ctx: Context<'_>, questions: Vec<NewQuestionnaireQuestion>) -> usize {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(questionnaire_questions)
+        .values(&questions)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving questionnaire questions")
+}
+
+/// Gets the position after the last question of a campaign's questionnaire
+pub fn get_next_position(ctx: Context<'_>, campaign_id_i32: i32) -> i32 {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    questionnaire_questions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(diesel::dsl::max(position))
+        .first::<Option<i32>>(conn)
+        .ok()
+        .flatten()
+        .map_or(0, |last| last + 1)
+}
+
+pub fn get_questions(ctx: Context<'_>, campaign_id_i32: i32) -> Vec<QuestionnaireQuestion> {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    questionnaire_questions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((position, id))
+        .load::<QuestionnaireQuestion>(conn)
+        .unwrap_or_default()
+}
+
+pub fn get_question_prompts(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<String>> {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    questionnaire_questions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .select(prompt)
+        .order_by((position, id))
+        .load::<String>(conn)
+        .ok()
+}
+
+pub fn has_questions(data: &Data, campaign_id_i32: i32) -> bool {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::select(diesel::dsl::exists(
+        questionnaire_questions.filter(campaign_id.eq(campaign_id_i32)),
+    ))
+    .get_result(conn)
+    .unwrap_or(false)
+}
+
+/// Deletes a question along with everyone's answers to it
+pub fn delete_question(ctx: Context<'_>, campaign_id_i32: i32, prompt_str: &str) -> bool {
+    use crate::schema::questionnaire_questions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        questionnaire_questions
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(prompt.eq(prompt_str)),
+    )
+    .execute(conn)
+    .expect("Error deleting questionnaire question")
+        > 0
+}
+
+/// Saves a user's answers, replacing earlier ones and clearing those left blank
+pub fn save_answers(ctx: Context<'_>, user_id_i64: i64, answers: &[(i32, Option<String>)]) {
+    use crate::schema::questionnaire_answers::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+    let now = chrono::Utc::now().naive_utc();
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        for (question_id_i32, answer_str) in answers {
+            match answer_str {
+                Some(answer_str) => {
+                    diesel::insert_into(questionnaire_answers)
+                        .values(&NewQuestionnaireAnswer {
+                            question_id: *question_id_i32,
+                            user_id: user_id_i64,
+                            answer: answer_str,
+                            answered_date: now,
+                        })
+                        .on_conflict((question_id, user_id))
+                        .do_update()
+                        .set((
+                            answer.eq(excluded(answer)),
+                            answered_date.eq(excluded(answered_date)),
+                        ))
+                        .execute(conn)?;
+                }
+                None => {
+                    diesel::delete(
+                        questionnaire_answers
+                            .filter(question_id.eq(question_id_i32))
+                            .filter(user_id.eq(user_id_i64)),
+                    )
+                    .execute(conn)?;
+                }
+            }
+        }
+
+        Ok(())
+    })
+    .expect("Error saving questionnaire answers");
+}
+
+/// Gets the answers to some questions, from everyone or only from one user
+pub fn get_answers(
+    ctx: Context<'_>,
+    question_ids: &[i32],
+    user_id_i64: Option<i64>,
+) -> Vec<QuestionnaireAnswer> {
+    use crate::schema::questionnaire_answers::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = questionnaire_answers
+        .filter(question_id.eq_any(question_ids))
+        .into_boxed();
+
+    if let Some(user_id_i64) = user_id_i64 {
+        query = query.filter(user_id.eq(user_id_i64));
+    }
+
+    query
+        .order_by((user_id, question_id))
+        .load::<QuestionnaireAnswer>(conn)
+        .unwrap_or_default()
+}
@@ -1,20 +1,148 @@
 use crate::models::{NewResponse, Response};
-use crate::utils::db::get_conn;
-use crate::Context;
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
 use diesel::prelude::*;
 
-pub fn create_response(ctx: Context<'_>, new_response: NewResponse) {
+pub struct ResponseOutcome {
+    /// The respondee's place on the waitlist, if the session was full
+    pub waitlist_position: Option<usize>,
+    /// Players moved off the waitlist because a seat opened up
+    pub promoted: Vec<i64>,
+}
+
+/// Records a response, waitlisting players who say yes to a full session
+///
+/// The session row is locked while the response is recorded, so simultaneous responses
+/// can't fill more seats than the session has
+pub fn create_response(data: &Data, new_response: NewResponse) -> ResponseOutcome {
+    use crate::schema::{responses, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    conn.transaction(|conn| {
+        let max_players = sessions::table
+            .find(new_response.session_id)
+            .select(sessions::max_players)
+            .for_update()
+            .first::<Option<i16>>(conn)?;
+
+        let respondee = responses::table
+            .filter(responses::session_id.eq(new_response.session_id))
+            .filter(responses::respondee_id.eq(new_response.respondee_id));
+
+        let previous = respondee.first::<Response>(conn).optional()?;
+
+        if previous.as_ref().map(|r| r.response) != Some(new_response.response) {
+            // Counted before the upsert, so the respondee doesn't take up the seat they're asking for
+            let waitlisted = new_response.response == 1
+                && match max_players {
+                    Some(max_players) => {
+                        count_going(conn, new_response.session_id)? >= i64::from(max_players)
+                    }
+                    None => false,
+                };
+
+            diesel::insert_into(responses::table)
+                .values(&new_response)
+                .on_conflict((responses::session_id, responses::respondee_id))
+                .do_update()
+                .set(&new_response)
+                .execute(conn)?;
+
+            diesel::update(respondee)
+                .set(responses::waitlisted.eq(waitlisted))
+                .execute(conn)?;
+        }
+
+        let promoted = fill_open_seats(conn, new_response.session_id, max_players)?;
+
+        let waitlist = get_waitlist(conn, new_response.session_id)?;
+        let waitlist_position = waitlist
+            .iter()
+            .position(|r| r.respondee_id == new_response.respondee_id)
+            .map(|position| position + 1);
+
+        diesel::QueryResult::Ok(ResponseOutcome {
+            waitlist_position,
+            promoted,
+        })
+    })
+    .expect("Error saving new response")
+}
+
+/// Moves players off the waitlist of a session until its seats are filled
+///
+/// Returns the IDs of the promoted players
+pub fn promote_waitlisted(data: &Data, session_id_i32: i32) -> Vec<i64> {
+    use crate::schema::sessions;
+
+    let conn = &mut get_data_conn(data);
+
+    conn.transaction(|conn| {
+        let max_players = sessions::table
+            .find(session_id_i32)
+            .select(sessions::max_players)
+            .for_update()
+            .first::<Option<i16>>(conn)?;
+
+        fill_open_seats(conn, session_id_i32, max_players)
+    })
+    .expect("Error promoting waitlisted responses")
+}
+
+fn count_going(conn: &mut PgConnection, session_id_i32: i32) -> QueryResult<i64> {
     use crate::schema::responses::dsl::*;
 
-    let conn = &mut get_conn(ctx);
+    responses
+        .filter(session_id.eq(session_id_i32))
+        .filter(response.eq(1))
+        .filter(waitlisted.eq(false))
+        .count()
+        .get_result::<i64>(conn)
+}
 
-    diesel::insert_into(responses)
-        .values(&new_response)
-        .on_conflict((session_id, respondee_id))
-        .do_update()
-        .set(&new_response)
-        .execute(conn)
-        .expect("Error saving new response");
+fn get_waitlist(conn: &mut PgConnection, session_id_i32: i32) -> QueryResult<Vec<Response>> {
+    use crate::schema::responses::dsl::*;
+
+    responses
+        .filter(session_id.eq(session_id_i32))
+        .filter(response.eq(1))
+        .filter(waitlisted.eq(true))
+        .order_by((responded_date, id))
+        .load::<Response>(conn)
+}
+
+fn fill_open_seats(
+    conn: &mut PgConnection,
+    session_id_i32: i32,
+    max_players: Option<i16>,
+) -> QueryResult<Vec<i64>> {
+    use crate::schema::responses::dsl::*;
+
+    let waitlist = get_waitlist(conn, session_id_i32)?;
+    let open_seats = match max_players {
+        Some(max_players) => {
+            usize::try_from(i64::from(max_players) - count_going(conn, session_id_i32)?)
+                .unwrap_or_default()
+        }
+        None => waitlist.len(),
+    };
+
+    let promoted = waitlist
+        .into_iter()
+        .take(open_seats)
+        .map(|r| r.respondee_id)
+        .collect::<Vec<i64>>();
+
+    diesel::update(
+        responses
+            .filter(session_id.eq(session_id_i32))
+            .filter(respondee_id.eq_any(&promoted)),
+    )
+    .set(waitlisted.eq(false))
+    .execute(conn)?;
+
+    Ok(promoted)
 }
 
 pub fn update_response(ctx: Context<'_>, updated_response: Response) {
@@ -28,13 +156,24 @@ pub fn update_response(ctx: Context<'_>, updated_response: Response) {
         .expect("Error updating response");
 }
 
-pub fn get_responses_for_session(ctx: Context<'_>, session_id_i32: i32) -> Option<Vec<Response>> {
+pub fn get_responses_for_session(data: &Data, session_id_i32: i32) -> Option<Vec<Response>> {
     use crate::schema::responses::dsl::*;
 
-    let conn = &mut get_conn(ctx);
+    let conn = &mut get_data_conn(data);
 
     responses
         .filter(session_id.eq(session_id_i32))
         .load::<Response>(conn)
         .ok()
 }
+
+pub fn get_responses_for_sessions(ctx: Context<'_>, session_ids: &[i32]) -> Option<Vec<Response>> {
+    use crate::schema::responses::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    responses
+        .filter(session_id.eq_any(session_ids))
+        .load::<Response>(conn)
+        .ok()
+}
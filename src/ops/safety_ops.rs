@@ -0,0 +1,45 @@
+use crate::models::{NewSafetyEntry, SafetyEntry};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::prelude::*;
+
+/// Adds a line or veil, returning `false` if the campaign already has the same one
+pub fn add_entry(ctx: Context<'_>, entry: NewSafetyEntry) -> bool {
+    use crate::schema::safety_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(safety_entries)
+        .values(&entry)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving safety entry")
+        > 0
+}
+
+pub fn get_entries(ctx: Context<'_>, campaign_id_i32: i32) -> Vec<SafetyEntry> {
+    use crate::schema::safety_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    safety_entries
+        .filter(campaign_id.eq(campaign_id_i32))
+        .order_by((kind, topic))
+        .load::<SafetyEntry>(conn)
+        .unwrap_or_default()
+}
+
+pub fn delete_entry(ctx: Context<'_>, campaign_id_i32: i32, entry_id: i32) -> bool {
+    use crate::schema::safety_entries::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::delete(
+        safety_entries
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(id.eq(entry_id)),
+    )
+    .execute(conn)
+    .expect("Error deleting safety entry")
+        > 0
+}
@@ -1,17 +1,21 @@
-use crate::models::{NewSession, Session};
-use crate::utils::db::get_conn;
-use crate::Context;
+use crate::commands::dnd::campaign::session::{
+    STATUS_CANCELLED, STATUS_COMPLETED, STATUS_CONFIRMED, STATUS_PENDING,
+};
+use crate::models::{Campaign, NewSession, Session};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::utils::events::SESSION_LENGTH_HOURS;
+use crate::{Context, Data};
 use diesel::prelude::*;
 
-pub fn create_session(ctx: Context<'_>, session: NewSession) {
+pub fn create_session(ctx: Context<'_>, session: NewSession) -> Session {
     use crate::schema::sessions::dsl::*;
 
     let conn = &mut get_conn(ctx);
 
     diesel::insert_into(sessions)
         .values(&session)
-        .execute(conn)
-        .expect("Error saving new session");
+        .get_result::<Session>(conn)
+        .expect("Error saving new session")
 }
 
 pub fn update_session(ctx: Context<'_>, session: Session) {
@@ -25,6 +29,42 @@ pub fn update_session(ctx: Context<'_>, session: Session) {
         .expect("Error updating session");
 }
 
+pub fn set_recap(ctx: Context<'_>, session_id_i32: i32, recap_str: Option<&str>) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set(recap.eq(recap_str))
+        .execute(conn)
+        .expect("Error updating session recap");
+}
+
+pub fn set_ingame_days(ctx: Context<'_>, session_id_i32: i32, start_day: i32, end_day: i32) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set((
+            ingame_start_day.eq(Some(start_day)),
+            ingame_end_day.eq(Some(end_day)),
+        ))
+        .execute(conn)
+        .expect("Error updating session in-game days");
+}
+
+pub fn set_dm_notes(ctx: Context<'_>, session_id_i32: i32, dm_notes_str: Option<&str>) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set(dm_notes.eq(dm_notes_str))
+        .execute(conn)
+        .expect("Error updating session DM notes");
+}
+
 pub fn bulk_cancel_sessions(ctx: Context<'_>, guild_id_i64: i64) {
     use crate::schema::sessions::dsl::*;
 
@@ -40,7 +80,7 @@ pub fn bulk_cancel_sessions(ctx: Context<'_>, guild_id_i64: i64) {
 
     campaign_ids.into_iter().for_each(|campaign_id_i32| {
         diesel::update(sessions.filter(campaign_id.eq(campaign_id_i32)))
-            .set(status.eq(2))
+            .set(status.eq(STATUS_CANCELLED))
             .execute(conn)
             .expect("Error updating session");
     });
@@ -78,3 +118,235 @@ pub fn does_session_exist(ctx: Context<'_>, session_id_i32: i32) -> bool {
         .first::<Session>(conn)
         .is_ok()
 }
+
+/// Gets every pending upcoming session whose campaign requires a minimum number of players
+pub fn get_pending_quorum_sessions(data: &Data) -> Option<Vec<(Session, Campaign)>> {
+    use crate::schema::{campaigns, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    sessions::table
+        .inner_join(campaigns::table)
+        .filter(sessions::status.eq(STATUS_PENDING))
+        .filter(sessions::scheduled_date.gt(chrono::Utc::now().naive_utc()))
+        .filter(campaigns::deleted.eq(false))
+        .filter(campaigns::min_players.is_not_null())
+        .select((sessions::all_columns, campaigns::all_columns))
+        .load::<(Session, Campaign)>(conn)
+        .ok()
+}
+
+/// Gets the confirmed sessions currently being played in their campaign's voice channel
+pub fn get_voice_sessions(
+    data: &Data,
+    now: chrono::NaiveDateTime,
+) -> Option<Vec<(Session, Campaign)>> {
+    use crate::schema::{campaigns, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    sessions::table
+        .inner_join(campaigns::table)
+        .filter(sessions::status.eq(STATUS_CONFIRMED))
+        .filter(sessions::scheduled_date.le(now))
+        .filter(sessions::scheduled_date.gt(now - chrono::Duration::hours(SESSION_LENGTH_HOURS)))
+        .filter(campaigns::deleted.eq(false))
+        .filter(campaigns::voice_channel_id.is_not_null())
+        .select((sessions::all_columns, campaigns::all_columns))
+        .load::<(Session, Campaign)>(conn)
+        .ok()
+}
+
+/// Gets the confirmed session currently being played in a voice channel, if there is one
+pub fn get_voice_session(
+    data: &Data,
+    guild_id_i64: i64,
+    voice_channel_id_i64: i64,
+    now: chrono::NaiveDateTime,
+) -> Option<Session> {
+    use crate::schema::{campaigns, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    sessions::table
+        .inner_join(campaigns::table)
+        .filter(sessions::status.eq(STATUS_CONFIRMED))
+        .filter(sessions::scheduled_date.le(now))
+        .filter(sessions::scheduled_date.gt(now - chrono::Duration::hours(SESSION_LENGTH_HOURS)))
+        .filter(campaigns::deleted.eq(false))
+        .filter(campaigns::guild_id.eq(guild_id_i64))
+        .filter(campaigns::voice_channel_id.eq(voice_channel_id_i64))
+        .select(sessions::all_columns)
+        .first::<Session>(conn)
+        .ok()
+}
+
+/// Cancels a campaign's pending and confirmed sessions that haven't happened yet
+pub fn cancel_upcoming_sessions(data: &Data, campaign_id_i32: i32) -> Vec<Session> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(
+        sessions
+            .filter(campaign_id.eq(campaign_id_i32))
+            .filter(status.eq_any([STATUS_PENDING, STATUS_CONFIRMED]))
+            .filter(scheduled_date.gt(chrono::Utc::now().naive_utc())),
+    )
+    .set(status.eq(STATUS_CANCELLED))
+    .get_results::<Session>(conn)
+    .expect("Error cancelling sessions")
+}
+
+pub fn set_session_status(data: &Data, session_id_i32: i32, status_i16: i16) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set(status.eq(status_i16))
+        .execute(conn)
+        .expect("Error updating session");
+}
+
+/// Cancels a session and forgets its scheduled event, which the caller removes
+pub fn cancel_session(data: &Data, session_id_i32: i32) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set((status.eq(STATUS_CANCELLED), event_id.eq(None::<i64>)))
+        .execute(conn)
+        .expect("Error cancelling session");
+}
+
+pub fn set_quorum_notified(data: &Data, session_id_i32: i32) {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(sessions.find(session_id_i32))
+        .set(quorum_notified.eq(true))
+        .execute(conn)
+        .expect("Error updating session");
+}
+
+pub fn get_session_by_event_id(data: &Data, event_id_i64: i64) -> Option<(Session, Campaign)> {
+    use crate::schema::{campaigns, sessions};
+
+    let conn = &mut get_data_conn(data);
+
+    sessions::table
+        .inner_join(campaigns::table)
+        .filter(sessions::event_id.eq(event_id_i64))
+        .select((sessions::all_columns, campaigns::all_columns))
+        .first::<(Session, Campaign)>(conn)
+        .ok()
+}
+
+/// Gets every session of a campaign scheduled on or after the given date, including cancelled ones
+pub fn get_sessions_since(
+    data: &Data,
+    campaign_id_i32: i32,
+    since: chrono::NaiveDateTime,
+) -> Option<Vec<Session>> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    sessions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(scheduled_date.ge(since))
+        .order_by(scheduled_date)
+        .load::<Session>(conn)
+        .ok()
+}
+
+/// Gets the past sessions of a campaign, most recent first
+pub fn get_session_history(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    status_i16: Option<i16>,
+    from: Option<chrono::NaiveDateTime>,
+    to: Option<chrono::NaiveDateTime>,
+) -> Option<Vec<Session>> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = sessions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(scheduled_date.lt(chrono::Utc::now().naive_utc()))
+        .into_boxed();
+
+    if let Some(status_i16) = status_i16 {
+        query = query.filter(status.eq(status_i16));
+    }
+
+    if let Some(from) = from {
+        query = query.filter(scheduled_date.ge(from));
+    }
+
+    if let Some(to) = to {
+        query = query.filter(scheduled_date.le(to));
+    }
+
+    query
+        .order_by(scheduled_date.desc())
+        .load::<Session>(conn)
+        .ok()
+}
+
+/// Marks confirmed sessions as completed once they have ended
+pub fn complete_past_sessions(data: &Data, ended_before: chrono::NaiveDateTime) -> Vec<Session> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(
+        sessions
+            .filter(status.eq(STATUS_CONFIRMED))
+            .filter(scheduled_date.lt(ended_before)),
+    )
+    .set(status.eq(STATUS_COMPLETED))
+    .get_results::<Session>(conn)
+    .expect("Error completing sessions")
+}
+
+/// Gets the sessions of a campaign that have a recap, in the order they were played
+pub fn get_session_recaps(ctx: Context<'_>, campaign_id_i32: i32) -> Option<Vec<Session>> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    sessions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(recap.is_not_null())
+        .order_by(scheduled_date)
+        .load::<Session>(conn)
+        .ok()
+}
+
+/// Gets the sessions of a campaign that have been played, optionally only those since a date
+pub fn get_played_sessions(
+    ctx: Context<'_>,
+    campaign_id_i32: i32,
+    since: Option<chrono::NaiveDateTime>,
+) -> Option<Vec<Session>> {
+    use crate::schema::sessions::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    let mut query = sessions
+        .filter(campaign_id.eq(campaign_id_i32))
+        .filter(status.eq_any([STATUS_CONFIRMED, STATUS_COMPLETED]))
+        .filter(scheduled_date.lt(chrono::Utc::now().naive_utc()))
+        .into_boxed();
+
+    if let Some(since) = since {
+        query = query.filter(scheduled_date.ge(since));
+    }
+
+    query.order_by(scheduled_date).load::<Session>(conn).ok()
+}
@@ -0,0 +1,29 @@
+use crate::models::{NewUserSetting, UserSetting};
+use crate::utils::db::get_conn;
+use crate::Context;
+use diesel::prelude::*;
+
+pub fn create_user_settings(ctx: Context<'_>, new_user_settings: NewUserSetting) {
+    use crate::schema::user_settings::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    diesel::insert_into(user_settings)
+        .values(&new_user_settings)
+        .on_conflict(user_id)
+        .do_update()
+        .set(&new_user_settings)
+        .execute(conn)
+        .expect("Error saving new user settings");
+}
+
+pub fn get_user_settings(ctx: Context<'_>, user_id_i64: i64) -> Option<UserSetting> {
+    use crate::schema::user_settings::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    user_settings
+        .filter(user_id.eq(user_id_i64))
+        .first::<UserSetting>(conn)
+        .ok()
+}
@@ -0,0 +1,78 @@
+use crate::models::{NewVoiceCheckin, VoiceCheckin};
+use crate::utils::db::{get_conn, get_data_conn};
+use crate::{Context, Data};
+use diesel::prelude::*;
+
+/// Opens a check-in, returning false if the user is already checked in to the session
+pub fn create_checkin(data: &Data, new_checkin: NewVoiceCheckin) -> bool {
+    use crate::schema::voice_checkins::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::insert_into(voice_checkins)
+        .values(&new_checkin)
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("Error saving voice check-in")
+        > 0
+}
+
+/// Checks a user out of every session in a guild, except the one they are still in
+pub fn end_checkins(
+    data: &Data,
+    guild_id_i64: i64,
+    user_id_i64: i64,
+    current_session_id: Option<i32>,
+    ended: chrono::NaiveDateTime,
+) {
+    use crate::schema::{campaigns, sessions, voice_checkins};
+
+    let conn = &mut get_data_conn(data);
+
+    let guild_sessions = sessions::table
+        .inner_join(campaigns::table)
+        .filter(campaigns::guild_id.eq(guild_id_i64))
+        .filter(sessions::id.ne(current_session_id.unwrap_or(0)))
+        .select(sessions::id);
+
+    diesel::update(
+        voice_checkins::table
+            .filter(voice_checkins::user_id.eq(user_id_i64))
+            .filter(voice_checkins::left_date.is_null())
+            .filter(voice_checkins::session_id.eq_any(guild_sessions)),
+    )
+    .set(voice_checkins::left_date.eq(ended))
+    .execute(conn)
+    .expect("Error ending voice check-ins");
+}
+
+/// Checks everyone still in voice out of a session that has ended
+pub fn end_session_checkins(data: &Data, session_id_i32: i32, ended: chrono::NaiveDateTime) {
+    use crate::schema::voice_checkins::dsl::*;
+
+    let conn = &mut get_data_conn(data);
+
+    diesel::update(
+        voice_checkins
+            .filter(session_id.eq(session_id_i32))
+            .filter(left_date.is_null()),
+    )
+    .set(left_date.eq(ended))
+    .execute(conn)
+    .expect("Error ending voice check-ins");
+}
+
+pub fn get_checkins_for_session(
+    ctx: Context<'_>,
+    session_id_i32: i32,
+) -> Option<Vec<VoiceCheckin>> {
+    use crate::schema::voice_checkins::dsl::*;
+
+    let conn = &mut get_conn(ctx);
+
+    voice_checkins
+        .filter(session_id.eq(session_id_i32))
+        .order_by(joined_date)
+        .load::<VoiceCheckin>(conn)
+        .ok()
+}
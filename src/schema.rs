@@ -1,5 +1,73 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    attendance (id) {
+        id -> Int4,
+        session_id -> Int4,
+        player_id -> Int8,
+        attended -> Bool,
+        marked_by -> Int8,
+        marked_date -> Timestamp,
+        late_minutes -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    calendar_events (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        name -> Text,
+        description -> Nullable<Text>,
+        day -> Int4,
+        author_id -> Int8,
+        created_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    calendar_holidays (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        name -> Text,
+        month -> Int2,
+        day -> Int2,
+    }
+}
+
+diesel::table! {
+    calendar_seasons (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        name -> Text,
+        month -> Int2,
+        day -> Int2,
+    }
+}
+
+diesel::table! {
+    calendars (campaign_id) {
+        campaign_id -> Int4,
+        name -> Text,
+        month_names -> Array<Text>,
+        month_lengths -> Array<Int4>,
+        weekdays -> Array<Text>,
+        current_day -> Int4,
+        updated_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    campaign_members (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        user_id -> Int8,
+        role -> Int2,
+        status -> Int2,
+        added_by -> Int8,
+        joined_date -> Timestamp,
+    }
+}
+
 diesel::table! {
     campaigns (id) {
         id -> Int4,
@@ -10,6 +78,19 @@ diesel::table! {
         link -> Nullable<Text>,
         deleted -> Bool,
         created_date -> Timestamp,
+        min_players -> Nullable<Int2>,
+        quorum_deadline_hours -> Int4,
+        quorum_action -> Int2,
+        import_event_interest -> Bool,
+        calendar_token -> Text,
+        channel_id -> Nullable<Int8>,
+        voice_channel_id -> Nullable<Int8>,
+        role_id -> Nullable<Int8>,
+        category_id -> Nullable<Int8>,
+        deleted_date -> Nullable<Timestamp>,
+        provisioned_channel_id -> Nullable<Int8>,
+        provisioned_voice_channel_id -> Nullable<Int8>,
+        archived -> Bool,
     }
 }
 
@@ -24,6 +105,172 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    handout_recipients (id) {
+        id -> Int4,
+        handout_id -> Int4,
+        user_id -> Int8,
+        revealed_by -> Int8,
+        revealed_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    handouts (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        title -> Text,
+        content -> Nullable<Text>,
+        file_name -> Nullable<Text>,
+        file_data -> Nullable<Bytea>,
+        author_id -> Int8,
+        created_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    journal_entries (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        author_id -> Int8,
+        session_id -> Nullable<Int4>,
+        title -> Text,
+        content -> Text,
+        shared -> Bool,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    lfg_posts (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        session_id -> Int4,
+        system -> Text,
+        min_level -> Int2,
+        max_level -> Int2,
+        content_warnings -> Nullable<Text>,
+        channel_id -> Nullable<Int8>,
+        message_id -> Nullable<Int8>,
+        closed -> Bool,
+        created_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    lore_entries (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        title -> Text,
+        content -> Text,
+        tags -> Array<Text>,
+        dm_only -> Bool,
+        author_id -> Int8,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    npcs (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        name -> Text,
+        description -> Nullable<Text>,
+        location -> Nullable<Text>,
+        faction -> Nullable<Text>,
+        attitude -> Int2,
+        status -> Int2,
+        secrets -> Nullable<Text>,
+        revealed -> Array<Text>,
+        author_id -> Int8,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+        portrait_name -> Nullable<Text>,
+        portrait_data -> Nullable<Bytea>,
+    }
+}
+
+diesel::table! {
+    poll_options (id) {
+        id -> Int4,
+        poll_id -> Int4,
+        scheduled_date -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    poll_votes (id) {
+        id -> Int4,
+        poll_option_id -> Int4,
+        voter_id -> Int8,
+        voted_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    polls (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        author_id -> Int8,
+        location -> Nullable<Text>,
+        channel_id -> Nullable<Int8>,
+        message_id -> Nullable<Int8>,
+        closed -> Bool,
+        created_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    quest_objectives (id) {
+        id -> Int4,
+        quest_id -> Int4,
+        description -> Text,
+        completed -> Bool,
+        created_date -> Timestamp,
+        completed_date -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    questionnaire_answers (id) {
+        id -> Int4,
+        question_id -> Int4,
+        user_id -> Int8,
+        answer -> Text,
+        answered_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    questionnaire_questions (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        position -> Int4,
+        prompt -> Text,
+        placeholder -> Nullable<Text>,
+        paragraph -> Bool,
+        required -> Bool,
+        created_date -> Timestamp,
+    }
+}
+
+diesel::table! {
+    quests (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        title -> Text,
+        giver_npc_id -> Nullable<Int4>,
+        description -> Nullable<Text>,
+        reward -> Nullable<Text>,
+        status -> Int2,
+        author_id -> Int8,
+        created_date -> Timestamp,
+        updated_date -> Timestamp,
+    }
+}
+
 diesel::table! {
     responses (id) {
         id -> Int4,
@@ -31,6 +278,17 @@ diesel::table! {
         respondee_id -> Int8,
         response -> Int2,
         responded_date -> Timestamp,
+        waitlisted -> Bool,
+    }
+}
+
+diesel::table! {
+    safety_entries (id) {
+        id -> Int4,
+        campaign_id -> Int4,
+        kind -> Int2,
+        topic -> Text,
+        created_date -> Date,
     }
 }
 
@@ -43,6 +301,14 @@ diesel::table! {
         status -> Int2,
         created_date -> Timestamp,
         scheduled_date -> Nullable<Timestamptz>,
+        quorum_notified -> Bool,
+        event_id -> Nullable<Int8>,
+        recap -> Nullable<Text>,
+        dm_notes -> Nullable<Text>,
+        max_players -> Nullable<Int2>,
+        thread_id -> Nullable<Int8>,
+        ingame_start_day -> Nullable<Int4>,
+        ingame_end_day -> Nullable<Int4>,
     }
 }
 
@@ -54,14 +320,77 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_settings (user_id) {
+        user_id -> Int8,
+        timezone -> Text,
+    }
+}
+
+diesel::table! {
+    voice_checkins (id) {
+        id -> Int4,
+        session_id -> Int4,
+        user_id -> Int8,
+        joined_date -> Timestamp,
+        left_date -> Nullable<Timestamp>,
+    }
+}
+
+diesel::joinable!(attendance -> sessions (session_id));
+diesel::joinable!(calendar_events -> campaigns (campaign_id));
+diesel::joinable!(calendar_holidays -> campaigns (campaign_id));
+diesel::joinable!(calendar_seasons -> campaigns (campaign_id));
+diesel::joinable!(calendars -> campaigns (campaign_id));
+diesel::joinable!(campaign_members -> campaigns (campaign_id));
 diesel::joinable!(characters -> campaigns (campaign_id));
+diesel::joinable!(handout_recipients -> handouts (handout_id));
+diesel::joinable!(handouts -> campaigns (campaign_id));
+diesel::joinable!(journal_entries -> campaigns (campaign_id));
+diesel::joinable!(journal_entries -> sessions (session_id));
+diesel::joinable!(lfg_posts -> campaigns (campaign_id));
+diesel::joinable!(lfg_posts -> sessions (session_id));
+diesel::joinable!(lore_entries -> campaigns (campaign_id));
+diesel::joinable!(npcs -> campaigns (campaign_id));
+diesel::joinable!(poll_options -> polls (poll_id));
+diesel::joinable!(poll_votes -> poll_options (poll_option_id));
+diesel::joinable!(polls -> campaigns (campaign_id));
+diesel::joinable!(quest_objectives -> quests (quest_id));
+diesel::joinable!(questionnaire_answers -> questionnaire_questions (question_id));
+diesel::joinable!(questionnaire_questions -> campaigns (campaign_id));
+diesel::joinable!(quests -> campaigns (campaign_id));
+diesel::joinable!(quests -> npcs (giver_npc_id));
 diesel::joinable!(responses -> sessions (session_id));
+diesel::joinable!(safety_entries -> campaigns (campaign_id));
 diesel::joinable!(sessions -> campaigns (campaign_id));
+diesel::joinable!(voice_checkins -> sessions (session_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    attendance,
+    calendar_events,
+    calendar_holidays,
+    calendar_seasons,
+    calendars,
+    campaign_members,
     campaigns,
     characters,
+    handout_recipients,
+    handouts,
+    journal_entries,
+    lfg_posts,
+    lore_entries,
+    npcs,
+    poll_options,
+    poll_votes,
+    polls,
+    quest_objectives,
+    questionnaire_answers,
+    questionnaire_questions,
+    quests,
     responses,
+    safety_entries,
     sessions,
     settings,
+    user_settings,
+    voice_checkins,
 );
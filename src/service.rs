@@ -0,0 +1,19 @@
+use shuttle_axum::AxumService;
+use shuttle_serenity::SerenityService;
+use std::net::SocketAddr;
+
+/// Runs the Discord client alongside the HTTP server, stopping when either one does
+pub struct BotService {
+    pub discord: SerenityService,
+    pub http: AxumService,
+}
+
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for BotService {
+    async fn bind(mut self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        tokio::select! {
+            result = self.discord.bind(addr) => result,
+            result = self.http.bind(addr) => result,
+        }
+    }
+}
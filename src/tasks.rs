@@ -0,0 +1,15 @@
+use crate::Data;
+use poise::serenity_prelude as serenity;
+
+pub mod completion;
+pub mod quorum;
+pub mod retention;
+pub mod voice;
+
+/// Spawns every background task, each with its own handle to the bot and database
+pub fn spawn_all(ctx: &serenity::Context, data: &Data) {
+    tokio::spawn(completion::run(ctx.clone(), data.clone()));
+    tokio::spawn(quorum::run(ctx.clone(), data.clone()));
+    tokio::spawn(retention::run(ctx.clone(), data.clone()));
+    tokio::spawn(voice::run(ctx.clone(), data.clone()));
+}
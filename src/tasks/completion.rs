@@ -0,0 +1,31 @@
+use crate::{
+    ops::{session_ops, voice_checkin_ops},
+    utils::{events::SESSION_LENGTH_HOURS, threads},
+    Data,
+};
+use poise::serenity_prelude as serenity;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Marks confirmed sessions as completed once they have ended
+///
+/// Their threads are archived and anyone still in voice is checked out
+pub async fn run(ctx: serenity::Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let ended_before =
+            chrono::Utc::now().naive_utc() - chrono::Duration::hours(SESSION_LENGTH_HOURS);
+
+        for session in session_ops::complete_past_sessions(&data, ended_before) {
+            voice_checkin_ops::end_session_checkins(
+                &data,
+                session.id,
+                chrono::Utc::now().naive_utc(),
+            );
+            threads::archive_session_thread(&ctx.http, &session).await;
+        }
+    }
+}
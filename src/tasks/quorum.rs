@@ -0,0 +1,133 @@
+use crate::{
+    commands::dnd::campaign::{
+        members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER},
+        session::STATUS_CONFIRMED,
+        QUORUM_ACTION_CANCEL,
+    },
+    models::{Campaign, Session},
+    ops::{member_ops, response_ops, session_ops},
+    utils::{date::get_long_date_week_day_timestamp, events, notifications, threads},
+    Data,
+};
+use poise::serenity_prelude as serenity;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Confirms pending sessions once their campaign's quorum is met
+///
+/// Sessions still short of players at the decision deadline are either cancelled or flagged
+/// to the DM, depending on the campaign's quorum action
+pub async fn run(ctx: serenity::Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        for (session, campaign) in
+            session_ops::get_pending_quorum_sessions(&data).unwrap_or_default()
+        {
+            check_quorum(&ctx, &data, session, campaign).await;
+        }
+    }
+}
+
+async fn check_quorum(ctx: &serenity::Context, data: &Data, session: Session, campaign: Campaign) {
+    let (min_players, scheduled_date) = match (campaign.min_players, session.scheduled_date) {
+        (Some(min_players), Some(scheduled_date)) => (min_players, scheduled_date),
+        _ => return,
+    };
+
+    let responses = response_ops::get_responses_for_session(data, session.id).unwrap_or_default();
+    let going = responses
+        .iter()
+        .filter(|r| r.response == 1 && !r.waitlisted)
+        .map(|r| r.respondee_id)
+        .collect::<Vec<i64>>();
+    let mut respondees = responses
+        .iter()
+        .map(|r| r.respondee_id)
+        .collect::<Vec<i64>>();
+
+    if !respondees.contains(&campaign.dm_id) {
+        respondees.push(campaign.dm_id);
+    }
+
+    let when =
+        get_long_date_week_day_timestamp(&scheduled_date.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+    if going.len() >= usize::try_from(min_players).unwrap_or_default() {
+        session_ops::set_session_status(data, session.id, STATUS_CONFIRMED);
+
+        notifications::notify_users(
+            ctx,
+            &respondees,
+            &format!(
+                "{} session ID {} on {} has {} players going and is now confirmed.",
+                campaign.name,
+                session.id,
+                when,
+                going.len()
+            ),
+        )
+        .await;
+
+        return;
+    }
+
+    let deadline =
+        scheduled_date - chrono::Duration::hours(i64::from(campaign.quorum_deadline_hours));
+
+    // Sessions scheduled inside their own decision window never get a chance to fill up
+    if session.quorum_notified
+        || session.created_date >= deadline
+        || chrono::Utc::now().naive_utc() < deadline
+    {
+        return;
+    }
+
+    if campaign.quorum_action == QUORUM_ACTION_CANCEL {
+        events::remove_session_event(&ctx.http, campaign.guild_id, &session).await;
+        session_ops::cancel_session(data, session.id);
+        threads::post_to_session_thread(&ctx.http, &session, "This session has been cancelled.")
+            .await;
+
+        for member_id in
+            member_ops::get_member_ids(data, campaign.id, &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM])
+        {
+            if !respondees.contains(&member_id) {
+                respondees.push(member_id);
+            }
+        }
+
+        notifications::notify_users(
+            ctx,
+            &respondees,
+            &format!(
+                "{} session ID {} on {} has been cancelled. Only {} of the {} required players are going.",
+                campaign.name,
+                session.id,
+                when,
+                going.len(),
+                min_players
+            ),
+        )
+        .await;
+    } else {
+        session_ops::set_quorum_notified(data, session.id);
+
+        notifications::notify_user(
+            ctx,
+            campaign.dm_id,
+            &format!(
+                "{} session ID {} on {} only has {} of the {} required players going. Use `/session set` to confirm or cancel it.",
+                campaign.name,
+                session.id,
+                when,
+                going.len(),
+                min_players
+            ),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{
+    commands::dnd::campaign::DELETE_RETENTION_DAYS, ops::campaign_ops, utils::provisioning, Data,
+};
+use poise::serenity_prelude as serenity;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Permanently deletes campaigns that have been deleted for longer than the retention window
+pub async fn run(ctx: serenity::Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let deleted_before =
+            chrono::Utc::now().naive_utc() - chrono::Duration::days(DELETE_RETENTION_DAYS);
+
+        for campaign in campaign_ops::get_expired_campaigns(&data, deleted_before) {
+            // Purged first, so channels and roles are kept if the campaign can't be deleted
+            if let Err(e) = campaign_ops::purge_campaign(&data, campaign.id) {
+                println!("Failed to purge campaign {}: {:?}", campaign.id, e);
+                continue;
+            }
+
+            provisioning::remove_campaign_resources(&ctx.http, campaign).await;
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    ops::session_ops,
+    utils::{
+        id::{i64_to_channel_id, i64_to_guild_id, user_id_to_i64},
+        voice,
+    },
+    Data,
+};
+use poise::serenity_prelude as serenity;
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Checks in players who were already in a campaign's voice channel when its session started
+///
+/// Players joining or leaving afterwards are tracked from voice state events instead
+pub async fn run(ctx: serenity::Context, data: Data) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let now = chrono::Utc::now().naive_utc();
+
+        for (session, campaign) in session_ops::get_voice_sessions(&data, now).unwrap_or_default() {
+            let voice_channel_id = match campaign.voice_channel_id {
+                Some(voice_channel_id) => i64_to_channel_id(voice_channel_id).await,
+                None => continue,
+            };
+            let guild_id = i64_to_guild_id(campaign.guild_id).await;
+
+            let user_ids = match ctx.cache.guild(guild_id) {
+                Some(guild) => guild
+                    .voice_states
+                    .values()
+                    .filter(|state| state.channel_id == Some(voice_channel_id))
+                    .filter(|state| !state.member.as_ref().is_some_and(|m| m.user.bot))
+                    .map(|state| state.user_id)
+                    .collect::<Vec<serenity::UserId>>(),
+                None => continue,
+            };
+
+            for user_id in user_ids {
+                voice::check_in(&ctx, &data, &session, user_id_to_i64(user_id).await, now).await;
+            }
+        }
+    }
+}
@@ -1,8 +1,16 @@
 pub mod autocompletes;
+pub mod calendar;
 pub mod checks;
 pub mod date;
 pub mod db;
+pub mod events;
+pub mod export;
 pub mod guilds;
+pub mod ics;
 pub mod id;
+pub mod notifications;
 pub mod numbers;
+pub mod provisioning;
+pub mod threads;
 pub mod users;
+pub mod voice;
@@ -1,7 +1,24 @@
-use super::{guilds::get_guild_id, id::guild_id_to_i64};
-use crate::ops::campaign_ops::get_campaign_names;
+use super::date::{format_in_timezone, parse_natural_date};
+use super::users::get_timezone;
+use super::{
+    checks::can_manage_campaign,
+    guilds::get_guild_id,
+    id::{guild_id_to_i64, user_id_to_i64},
+};
+use crate::models::Campaign;
+use crate::ops::calendar_ops::{get_calendar, get_event_names, get_holidays, get_seasons};
+use crate::ops::campaign_ops::{
+    self, get_archived_campaign_names, get_campaign_names, CampaignFilters,
+};
+use crate::ops::handout_ops::get_handout_titles;
+use crate::ops::lfg_ops::get_open_post_names;
+use crate::ops::lore_ops::get_entry_titles;
+use crate::ops::npc_ops::get_npc_names;
+use crate::ops::quest_ops::{get_objective_descriptions, get_quest, get_quest_titles};
+use crate::ops::questionnaire_ops::get_question_prompts;
 use crate::Context;
 use futures::{Stream, StreamExt};
+use poise::serenity_prelude as serenity;
 
 pub async fn autocomplete_campaign<'a>(
     ctx: Context<'_>,
@@ -9,12 +26,265 @@ pub async fn autocomplete_campaign<'a>(
 ) -> impl Stream<Item = String> + 'a {
     let guild_id_i64 = guild_id_to_i64(get_guild_id(ctx).await).await;
 
-    let results = match get_campaign_names(ctx, guild_id_i64) {
-        Some(campaigns) => campaigns,
-        None => vec![],
-    };
+    let results = get_campaign_names(ctx, guild_id_i64).unwrap_or_default();
+
+    futures::stream::iter(results)
+        .filter(move |c| futures::future::ready(c.starts_with(partial)))
+        .map(|c| c.to_string())
+}
+
+pub async fn autocomplete_archived_campaign<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let guild_id_i64 = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let results = get_archived_campaign_names(ctx, guild_id_i64).unwrap_or_default();
 
     futures::stream::iter(results)
         .filter(move |c| futures::future::ready(c.starts_with(partial)))
         .map(|c| c.to_string())
 }
+
+/// Completes the titles of lore entries in the campaign picked earlier in the command
+///
+/// Players only see entries that aren't DM-only
+pub async fn autocomplete_lore_title<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let user_id_i64 = user_id_to_i64(ctx.author().id).await;
+
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| {
+            get_entry_titles(
+                ctx,
+                campaign.id,
+                can_manage_campaign(ctx.data(), &campaign, user_id_i64),
+            )
+        })
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the titles of handouts in the campaign picked earlier in the command
+///
+/// Players only see handouts they have received
+pub async fn autocomplete_handout<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let user_id_i64 = user_id_to_i64(ctx.author().id).await;
+
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| {
+            let recipient_id = if can_manage_campaign(ctx.data(), &campaign, user_id_i64) {
+                None
+            } else {
+                Some(user_id_i64)
+            };
+
+            get_handout_titles(ctx, campaign.id, recipient_id)
+        })
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the names of one-shots with open signups
+pub async fn autocomplete_lfg<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let guild_id_i64 = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let results = get_open_post_names(ctx, guild_id_i64).unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the names of NPCs in the campaign picked earlier in the command
+///
+/// Players only see NPCs the party has met
+pub async fn autocomplete_npc<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let user_id_i64 = user_id_to_i64(ctx.author().id).await;
+
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| {
+            get_npc_names(
+                ctx,
+                campaign.id,
+                can_manage_campaign(ctx.data(), &campaign, user_id_i64),
+            )
+        })
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the titles of quests in the campaign picked earlier in the command
+pub async fn autocomplete_quest<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| get_quest_titles(ctx, campaign.id))
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the questions of the questionnaire of the campaign picked earlier in the command
+pub async fn autocomplete_question<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| get_question_prompts(ctx, campaign.id))
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the objectives of the quest picked earlier in the command
+pub async fn autocomplete_objective<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let quest_title = selected_option(ctx, "quest");
+
+    let results = selected_campaign(ctx)
+        .await
+        .zip(quest_title)
+        .and_then(|(campaign, quest_title)| get_quest(ctx, campaign.id, &quest_title))
+        .and_then(|quest| get_objective_descriptions(ctx, quest.id))
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the month names of the calendar of the campaign picked earlier in the command
+pub async fn autocomplete_month<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| get_calendar(ctx, campaign.id))
+        .map(|calendar| calendar.month_names)
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Completes the holidays of the campaign picked earlier in the command
+pub async fn autocomplete_holiday<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = match selected_campaign(ctx).await {
+        Some(campaign) => get_holidays(ctx, campaign.id)
+            .into_iter()
+            .map(|h| h.name)
+            .collect(),
+        None => vec![],
+    };
+
+    filter_names(results, partial)
+}
+
+/// Completes the seasons of the campaign picked earlier in the command
+pub async fn autocomplete_season<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = match selected_campaign(ctx).await {
+        Some(campaign) => get_seasons(ctx, campaign.id)
+            .into_iter()
+            .map(|s| s.name)
+            .collect(),
+        None => vec![],
+    };
+
+    filter_names(results, partial)
+}
+
+/// Completes the in-world events of the campaign picked earlier in the command
+pub async fn autocomplete_calendar_event<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let results = selected_campaign(ctx)
+        .await
+        .and_then(|campaign| get_event_names(ctx, campaign.id))
+        .unwrap_or_default();
+
+    filter_names(results, partial)
+}
+
+/// Gets the campaign named in the `campaign` option of the command being completed
+async fn selected_campaign(ctx: Context<'_>) -> Option<Campaign> {
+    let guild_id_i64 = guild_id_to_i64(get_guild_id(ctx).await).await;
+    let campaign_name = selected_option(ctx, "campaign")?;
+
+    campaign_ops::get_campaign(ctx, guild_id_i64, CampaignFilters::Name(campaign_name))
+}
+
+/// Gets the value of a string option the user already filled in on the command being completed
+fn selected_option(ctx: Context<'_>, name: &str) -> Option<String> {
+    match ctx {
+        poise::Context::Application(actx) => actx.args.iter().find_map(|arg| match arg.value {
+            serenity::ResolvedValue::String(value) if arg.name == name => Some(value.to_string()),
+            _ => None,
+        }),
+        poise::Context::Prefix(_) => None,
+    }
+}
+
+fn filter_names<'a>(names: Vec<String>, partial: &'a str) -> impl Stream<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+
+    futures::stream::iter(names)
+        .filter(move |n| futures::future::ready(n.to_lowercase().contains(&partial)))
+        .take(25)
+}
+
+pub async fn autocomplete_date(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    let tz = get_timezone(ctx).await;
+
+    match parse_natural_date(partial, tz) {
+        Some(date) => {
+            let resolved = format_in_timezone(date, tz);
+
+            vec![serenity::AutocompleteChoice::new(
+                format!("{} ({})", resolved, tz.name()),
+                resolved,
+            )]
+        }
+        None => vec![],
+    }
+}
+
+pub async fn autocomplete_timezone<'a>(
+    _ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+
+    futures::stream::iter(chrono_tz::TZ_VARIANTS)
+        .filter(move |tz| futures::future::ready(tz.name().to_lowercase().contains(&partial)))
+        .map(|tz| tz.name().to_string())
+        .take(25)
+}
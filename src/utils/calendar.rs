@@ -0,0 +1,308 @@
+use crate::models::{Calendar, CalendarHoliday, CalendarSeason};
+
+/// A ready-made calendar that can be used instead of defining every month by hand
+pub struct CalendarPreset {
+    pub name: &'static str,
+    pub months: &'static [(&'static str, i32)],
+    pub weekdays: &'static [&'static str],
+    pub holidays: &'static [(&'static str, i16, i16)],
+    pub seasons: &'static [(&'static str, i16, i16)],
+}
+
+/// The Calendar of Harptos used in the Forgotten Realms
+///
+/// Festival days are modelled as one-day months between the regular ones, and Shieldmeet is left out
+pub const HARPTOS: CalendarPreset = CalendarPreset {
+    name: "Calendar of Harptos",
+    months: &[
+        ("Hammer", 30),
+        ("Midwinter", 1),
+        ("Alturiak", 30),
+        ("Ches", 30),
+        ("Tarsakh", 30),
+        ("Greengrass", 1),
+        ("Mirtul", 30),
+        ("Kythorn", 30),
+        ("Flamerule", 30),
+        ("Midsummer", 1),
+        ("Eleasis", 30),
+        ("Eleint", 30),
+        ("Highharvestide", 1),
+        ("Marpenoth", 30),
+        ("Uktar", 30),
+        ("Feast of the Moon", 1),
+        ("Nightal", 30),
+    ],
+    weekdays: &[
+        "First-day",
+        "Second-day",
+        "Third-day",
+        "Fourth-day",
+        "Fifth-day",
+        "Sixth-day",
+        "Seventh-day",
+        "Eighth-day",
+        "Ninth-day",
+        "Tenth-day",
+    ],
+    holidays: &[
+        ("Midwinter", 2, 1),
+        ("Greengrass", 6, 1),
+        ("Midsummer", 10, 1),
+        ("Highharvestide", 13, 1),
+        ("Feast of the Moon", 16, 1),
+    ],
+    seasons: &[
+        ("Spring", 4, 19),
+        ("Summer", 8, 20),
+        ("Autumn", 12, 21),
+        ("Winter", 17, 20),
+    ],
+};
+
+/// The Gregorian calendar, without leap years
+pub const GREGORIAN: CalendarPreset = CalendarPreset {
+    name: "Gregorian calendar",
+    months: &[
+        ("January", 31),
+        ("February", 28),
+        ("March", 31),
+        ("April", 30),
+        ("May", 31),
+        ("June", 30),
+        ("July", 31),
+        ("August", 31),
+        ("September", 30),
+        ("October", 31),
+        ("November", 30),
+        ("December", 31),
+    ],
+    weekdays: &[
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+    holidays: &[],
+    seasons: &[
+        ("Spring", 3, 20),
+        ("Summer", 6, 21),
+        ("Autumn", 9, 22),
+        ("Winter", 12, 21),
+    ],
+};
+
+/// A date in a campaign's calendar, with the month and day counted from 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InGameDate {
+    pub year: i32,
+    pub month: i16,
+    pub day: i16,
+}
+
+pub fn year_length(calendar: &Calendar) -> i32 {
+    calendar.month_lengths.iter().sum()
+}
+
+/// Converts a day count, where day 0 is the first day of year 0, into a date
+pub fn to_date(calendar: &Calendar, day: i32) -> InGameDate {
+    let length = year_length(calendar).max(1);
+    let mut day_of_year = day.rem_euclid(length);
+    let mut month = 0;
+
+    for (index, month_length) in calendar.month_lengths.iter().enumerate() {
+        month = index;
+
+        if day_of_year < *month_length {
+            break;
+        }
+
+        day_of_year -= month_length;
+    }
+
+    InGameDate {
+        year: day.div_euclid(length),
+        month: month as i16 + 1,
+        day: day_of_year as i16 + 1,
+    }
+}
+
+/// Converts a date into a day count, or `None` if the month or day doesn't exist
+pub fn to_day(calendar: &Calendar, date: InGameDate) -> Option<i32> {
+    let month_index = usize::try_from(date.month - 1).ok()?;
+    let month_length = *calendar.month_lengths.get(month_index)?;
+
+    if date.day < 1 || i32::from(date.day) > month_length {
+        return None;
+    }
+
+    let days_before_month: i32 = calendar.month_lengths[..month_index].iter().sum();
+
+    date.year
+        .checked_mul(year_length(calendar))?
+        .checked_add(days_before_month + i32::from(date.day) - 1)
+}
+
+pub fn weekday(calendar: &Calendar, day: i32) -> Option<&str> {
+    if calendar.weekdays.is_empty() {
+        return None;
+    }
+
+    let index = day.rem_euclid(calendar.weekdays.len() as i32) as usize;
+
+    calendar.weekdays.get(index).map(String::as_str)
+}
+
+/// Formats a day like `15 Mirtul 1491`, leaving out the day for one-day months like festivals
+pub fn format_day(calendar: &Calendar, day: i32) -> String {
+    let date = to_date(calendar, day);
+    let month_index = (date.month - 1) as usize;
+    let month_name = &calendar.month_names[month_index];
+
+    if calendar.month_lengths[month_index] == 1 {
+        format!("{} {}", month_name, date.year)
+    } else {
+        format!("{} {} {}", date.day, month_name, date.year)
+    }
+}
+
+/// Formats a day with its weekday, like `Tenth-day, 15 Mirtul 1491`
+pub fn format_day_long(calendar: &Calendar, day: i32) -> String {
+    match weekday(calendar, day) {
+        Some(weekday) => format!("{}, {}", weekday, format_day(calendar, day)),
+        None => format_day(calendar, day),
+    }
+}
+
+pub fn format_span(calendar: &Calendar, start: i32, end: i32) -> String {
+    if start == end {
+        format_day(calendar, start)
+    } else {
+        format!(
+            "{} – {}",
+            format_day(calendar, start),
+            format_day(calendar, end)
+        )
+    }
+}
+
+/// Finds a month by name, ignoring case, returning its number counted from 1
+pub fn find_month(calendar: &Calendar, name: &str) -> Option<i16> {
+    calendar
+        .month_names
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(name.trim()))
+        .map(|index| index as i16 + 1)
+}
+
+/// Parses a date like `15 Mirtul 1491`, `15 Mirtul` or `Midwinter 1491` into a day count
+///
+/// The day defaults to the first of the month and the year to the calendar's current year
+/// `today` resolves to the current day
+pub fn parse_day(calendar: &Calendar, input: &str) -> Option<i32> {
+    let input = input.trim();
+
+    if input.eq_ignore_ascii_case("today") {
+        return Some(calendar.current_day);
+    }
+
+    let mut tokens = input.split_whitespace().collect::<Vec<&str>>();
+
+    let day = match tokens.first().and_then(|t| t.parse::<i16>().ok()) {
+        Some(day) => {
+            tokens.remove(0);
+            day
+        }
+        None => 1,
+    };
+
+    let year = match tokens.last().and_then(|t| t.parse::<i32>().ok()) {
+        Some(year) => {
+            tokens.pop();
+            year
+        }
+        None => to_date(calendar, calendar.current_day).year,
+    };
+
+    let month = find_month(calendar, &tokens.join(" "))?;
+
+    to_day(calendar, InGameDate { year, month, day })
+}
+
+/// Parses months written like `Hammer:30, Midwinter:1, Alturiak:30`
+pub fn parse_months(input: &str) -> Result<(Vec<String>, Vec<i32>), String> {
+    let mut names = vec![];
+    let mut lengths = vec![];
+
+    for month in input.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+        let (name, length) = month
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Month {} needs a length, like {}:30.", month, month))?;
+        let name = name.trim();
+        let length = length
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|l| (1..=1000).contains(l))
+            .ok_or_else(|| format!("Month {} needs a length between 1 and 1000.", name))?;
+
+        if name.is_empty() || names.iter().any(|n: &String| n.eq_ignore_ascii_case(name)) {
+            return Err(format!(
+                "Month names must be unique and not empty: {}",
+                month
+            ));
+        }
+
+        names.push(name.to_string());
+        lengths.push(length);
+    }
+
+    if names.is_empty() {
+        return Err(String::from("A calendar needs at least one month."));
+    }
+
+    Ok((names, lengths))
+}
+
+pub fn parse_weekdays(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Gets the season a day falls in, which is the last one to have started
+///
+/// Seasons wrap around the year, so days before the first season of the year belong to the last
+pub fn season_on<'a>(
+    calendar: &Calendar,
+    seasons: &'a [CalendarSeason],
+    day: i32,
+) -> Option<&'a CalendarSeason> {
+    let date = to_date(calendar, day);
+    let key = |s: &CalendarSeason| (s.month, s.day);
+
+    seasons
+        .iter()
+        .filter(|s| key(s) <= (date.month, date.day))
+        .max_by_key(|s| key(s))
+        .or_else(|| seasons.iter().max_by_key(|s| key(s)))
+}
+
+pub fn holidays_on<'a>(
+    calendar: &Calendar,
+    holidays: &'a [CalendarHoliday],
+    day: i32,
+) -> Vec<&'a CalendarHoliday> {
+    let date = to_date(calendar, day);
+
+    holidays
+        .iter()
+        .filter(|h| h.month == date.month && h.day == date.day)
+        .collect()
+}
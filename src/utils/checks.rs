@@ -1,5 +1,10 @@
+use super::guilds::get_guild_id;
+use super::id::{guild_id_to_i64, user_id_to_i64};
 use super::users;
-use crate::{responses::invalid_permissions, Context, Error};
+use crate::commands::dnd::campaign::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER};
+use crate::models::Campaign;
+use crate::ops::{campaign_ops, member_ops, session_ops};
+use crate::{responses::invalid_permissions, Context, Data, Error};
 
 pub async fn dnd_check(ctx: Context<'_>) -> Result<bool, Error> {
     if !users::has_dnd_role(ctx).await? {
@@ -18,3 +23,59 @@ pub async fn dm_check(ctx: Context<'_>) -> Result<bool, Error> {
 
     Ok(true)
 }
+
+/// Whether a user owns a campaign or is one of its co-DMs
+pub fn can_manage_campaign(data: &Data, campaign: &Campaign, user_id: i64) -> bool {
+    campaign.dm_id == user_id
+        || member_ops::get_member_ids(data, campaign.id, &[MEMBER_ROLE_CO_DM]).contains(&user_id)
+}
+
+/// Whether a user runs a campaign or plays in it
+pub fn is_campaign_member(data: &Data, campaign: &Campaign, user_id: i64) -> bool {
+    campaign.dm_id == user_id
+        || member_ops::get_member_ids(data, campaign.id, &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM])
+            .contains(&user_id)
+}
+
+/// Checks that the author owns a campaign or is one of its co-DMs
+pub async fn campaign_check(ctx: Context<'_>, campaign: &Campaign) -> Result<bool, Error> {
+    if !can_manage_campaign(ctx.data(), campaign, user_id_to_i64(ctx.author().id).await) {
+        invalid_permissions(ctx).await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Checks that the author owns a campaign
+///
+/// Used for actions co-DMs can't take, like deleting the campaign or handing it over
+pub async fn campaign_owner_check(ctx: Context<'_>, campaign: &Campaign) -> Result<bool, Error> {
+    if campaign.dm_id != user_id_to_i64(ctx.author().id).await {
+        invalid_permissions(ctx).await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Checks that the author manages the campaign a session in this guild belongs to
+pub async fn session_check(ctx: Context<'_>, session_id: i32) -> Result<bool, Error> {
+    let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
+
+    let campaign = session_ops::get_session(ctx, session_id).and_then(|session| {
+        campaign_ops::get_campaign(
+            ctx,
+            guild_id,
+            campaign_ops::CampaignFilters::Id(session.campaign_id),
+        )
+    });
+
+    match campaign {
+        Some(campaign) => campaign_check(ctx, &campaign).await,
+        None => {
+            invalid_permissions(ctx).await?;
+            Ok(false)
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use chrono::{Local, NaiveDateTime, TimeZone};
+use chrono::{
+    Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
 
 pub fn is_date_in_future(date: &str) -> bool {
     let current_date = Local::now();
@@ -17,6 +21,128 @@ pub fn is_date_format_valid(date: &str) -> bool {
     NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").is_ok()
 }
 
+pub fn is_naive_date_in_future(date: NaiveDateTime) -> bool {
+    date > Utc::now().naive_utc()
+}
+
+/// Parses an absolute (`YYYY-MM-DD HH:MM`) or natural-language date in the given timezone
+///
+/// Accepts inputs such as `next friday 7pm`, `tomorrow 18:00`, `in 2 weeks at 19:30` and
+/// `sat 8pm`. Returns the resolved date in UTC.
+pub fn parse_natural_date(date: &str, tz: Tz) -> Option<NaiveDateTime> {
+    let now = Utc::now().with_timezone(&tz).naive_local();
+    let resolved = resolve_natural_date(date, now)?;
+
+    tz.from_local_datetime(&resolved)
+        .earliest()
+        .map(|date| date.naive_utc())
+}
+
+/// Formats a UTC date in the given timezone using the `YYYY-MM-DD HH:MM` format
+pub fn format_in_timezone(date: NaiveDateTime, tz: Tz) -> String {
+    Utc.from_utc_datetime(&date)
+        .with_timezone(&tz)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+fn resolve_natural_date(date: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let date = date.trim().to_lowercase();
+
+    if is_date_format_valid(&date) {
+        return NaiveDateTime::parse_from_str(&date, "%Y-%m-%d %H:%M").ok();
+    }
+
+    let tokens: Vec<&str> = date
+        .split_whitespace()
+        .filter(|token| !matches!(*token, "at" | "on"))
+        .collect();
+
+    match tokens.as_slice() {
+        ["today", time @ ..] => at_time(now.date(), time),
+        ["tomorrow", time @ ..] => at_time(now.date() + Duration::days(1), time),
+        ["next", weekday, time @ ..] => {
+            let weekday = weekday.parse::<Weekday>().ok()?;
+            let days = days_until(now.weekday(), weekday);
+            let days = if days == 0 { 7 } else { days };
+
+            at_time(now.date() + Duration::days(days), time)
+        }
+        ["in", amount, unit, time @ ..] => {
+            let amount = amount.parse::<i64>().ok()?;
+            let duration = match unit.trim_end_matches('s') {
+                "minute" | "min" => Duration::try_minutes(amount)?,
+                "hour" | "hr" => Duration::try_hours(amount)?,
+                "day" => Duration::try_days(amount)?,
+                "week" => Duration::try_weeks(amount)?,
+                _ => return None,
+            };
+            let shifted = now.checked_add_signed(duration)?;
+
+            match time.is_empty() {
+                true => shifted.with_second(0)?.with_nanosecond(0),
+                false if duration < Duration::days(1) => None,
+                false => at_time(shifted.date(), time),
+            }
+        }
+        [day, time @ ..] => {
+            if let Ok(day) = NaiveDate::parse_from_str(day, "%Y-%m-%d") {
+                return at_time(day, time);
+            }
+
+            let weekday = day.parse::<Weekday>().ok()?;
+            let resolved = at_time(
+                now.date() + Duration::days(days_until(now.weekday(), weekday)),
+                time,
+            )?;
+
+            match resolved > now {
+                true => Some(resolved),
+                false => Some(resolved + Duration::weeks(1)),
+            }
+        }
+        [] => None,
+    }
+}
+
+fn at_time(day: NaiveDate, time: &[&str]) -> Option<NaiveDateTime> {
+    Some(day.and_time(parse_time(&time.concat())?))
+}
+
+fn days_until(from: Weekday, to: Weekday) -> i64 {
+    i64::from((7 + to.num_days_from_monday() - from.num_days_from_monday()) % 7)
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    match time {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+
+    let (clock, is_pm) = if let Some(clock) = time.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = time.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (time, None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+
+    let hour = match is_pm {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
 pub fn get_relative_timestamp(date: &str) -> Result<String, chrono::ParseError> {
     let timestamp = get_unix_timestamp(date)?;
 
@@ -65,3 +191,114 @@ pub fn get_unix_timestamp(date: &str) -> Result<i64, chrono::ParseError> {
         Err(e) => Err(e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Wednesday
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-10-14 15:42", "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn date(date: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").ok()
+    }
+
+    #[test]
+    fn resolves_absolute_dates() {
+        assert_eq!(
+            resolve_natural_date("2026-12-01 19:00", now()),
+            date("2026-12-01 19:00")
+        );
+        assert_eq!(
+            resolve_natural_date("2026-12-01 7pm", now()),
+            date("2026-12-01 19:00")
+        );
+    }
+
+    #[test]
+    fn resolves_relative_days() {
+        assert_eq!(
+            resolve_natural_date("today at noon", now()),
+            date("2026-10-14 12:00")
+        );
+        assert_eq!(
+            resolve_natural_date("Tomorrow 18:00", now()),
+            date("2026-10-15 18:00")
+        );
+    }
+
+    #[test]
+    fn resolves_weekdays() {
+        assert_eq!(
+            resolve_natural_date("next friday 7pm", now()),
+            date("2026-10-16 19:00")
+        );
+        assert_eq!(
+            resolve_natural_date("next wed 7pm", now()),
+            date("2026-10-21 19:00")
+        );
+        assert_eq!(
+            resolve_natural_date("sat 8pm", now()),
+            date("2026-10-17 20:00")
+        );
+        assert_eq!(
+            resolve_natural_date("wed 3pm", now()),
+            date("2026-10-21 15:00")
+        );
+        assert_eq!(
+            resolve_natural_date("wed 4pm", now()),
+            date("2026-10-14 16:00")
+        );
+    }
+
+    #[test]
+    fn resolves_offsets() {
+        assert_eq!(
+            resolve_natural_date("in 2 weeks at 19:30", now()),
+            date("2026-10-28 19:30")
+        );
+        assert_eq!(
+            resolve_natural_date("in 3 hours", now()),
+            date("2026-10-14 18:42")
+        );
+        assert_eq!(
+            resolve_natural_date("in 1 day", now()),
+            date("2026-10-15 15:42")
+        );
+        assert_eq!(resolve_natural_date("in 3 hours at 8pm", now()), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_offsets() {
+        assert_eq!(resolve_natural_date("in 99999999999 weeks", now()), None);
+        assert_eq!(
+            resolve_natural_date(&format!("in {} minutes", i64::MAX), now()),
+            None
+        );
+        assert_eq!(resolve_natural_date("in 999999999 days", now()), None);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(resolve_natural_date("", now()), None);
+        assert_eq!(resolve_natural_date("someday", now()), None);
+        assert_eq!(resolve_natural_date("in two weeks", now()), None);
+        assert_eq!(resolve_natural_date("in 2 fortnights", now()), None);
+        assert_eq!(resolve_natural_date("tomorrow 25:00", now()), None);
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_time("noon"), NaiveTime::from_hms_opt(12, 0, 0));
+        assert_eq!(parse_time("midnight"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time("12pm"), NaiveTime::from_hms_opt(12, 0, 0));
+        assert_eq!(parse_time("7:30pm"), NaiveTime::from_hms_opt(19, 30, 0));
+        assert_eq!(parse_time("18:15"), NaiveTime::from_hms_opt(18, 15, 0));
+        assert_eq!(parse_time("13pm"), None);
+        assert_eq!(parse_time("0am"), None);
+        assert_eq!(parse_time(""), None);
+    }
+}
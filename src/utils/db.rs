@@ -1,4 +1,4 @@
-use crate::Context;
+use crate::{Context, Data};
 use diesel::prelude::*;
 use diesel::r2d2::Pool;
 use diesel::r2d2::{ConnectionManager, PooledConnection};
@@ -12,5 +12,10 @@ pub fn init_pool(database_url: &str) -> Pool<ConnectionManager<PgConnection>> {
 }
 
 pub fn get_conn(ctx: Context<'_>) -> PooledConnection<ConnectionManager<PgConnection>> {
-    ctx.data().db_pool.get().expect("Failed to get connection")
+    get_data_conn(ctx.data())
+}
+
+/// Gets a connection outside of a command, e.g. from an event handler
+pub fn get_data_conn(data: &Data) -> PooledConnection<ConnectionManager<PgConnection>> {
+    data.db_pool.get().expect("Failed to get connection")
 }
@@ -0,0 +1,181 @@
+use super::id::{
+    i64_to_guild_id, i64_to_scheduled_event_id, scheduled_event_id_to_i64, user_id_to_i64,
+};
+use super::provisioning;
+use crate::{
+    commands::dnd::campaign::members::{MEMBER_ROLE_CO_DM, MEMBER_ROLE_PLAYER},
+    models::{Campaign, NewResponse, Session},
+    ops::{member_ops, response_ops, session_ops},
+    Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+pub const SESSION_LENGTH_HOURS: i64 = 4;
+
+/// Creates or updates the guild scheduled event mirroring a session
+///
+/// Returns the ID of the event, or `None` if it could not be created
+pub async fn sync_session_event(
+    http: &serenity::Http,
+    campaign: &Campaign,
+    session: &Session,
+) -> Option<i64> {
+    let guild_id = i64_to_guild_id(campaign.guild_id).await;
+
+    let result = match session.event_id {
+        Some(event_id) => edit_session_event(http, guild_id, event_id, campaign, session).await,
+        None => create_session_event(http, guild_id, campaign, session).await,
+    };
+
+    match result {
+        Ok(event_id) => Some(event_id),
+        Err(e) => {
+            println!(
+                "Failed to sync scheduled event for session {}: {:?}",
+                session.id, e
+            );
+            session.event_id
+        }
+    }
+}
+
+/// Deletes the guild scheduled event mirroring a session, if there is one
+pub async fn remove_session_event(http: &serenity::Http, guild_id: i64, session: &Session) {
+    let event_id = match session.event_id {
+        Some(event_id) => i64_to_scheduled_event_id(event_id).await,
+        None => return,
+    };
+
+    if let Err(e) = i64_to_guild_id(guild_id)
+        .await
+        .delete_scheduled_event(http, event_id)
+        .await
+    {
+        println!(
+            "Failed to delete scheduled event for session {}: {:?}",
+            session.id, e
+        );
+    }
+}
+
+/// Records users marking a session's event as "Interested" as going, if the campaign opted in
+///
+/// Once a campaign has members, only its players and co-DMs are recorded
+pub async fn handle_event_interest(
+    ctx: &serenity::Context,
+    data: &Data,
+    subscribed: &serenity::GuildScheduledEventUserAddEvent,
+) -> Result<(), Error> {
+    let event_id = scheduled_event_id_to_i64(subscribed.scheduled_event_id).await;
+
+    let (session, campaign) = match session_ops::get_session_by_event_id(data, event_id) {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    if !campaign.import_event_interest || campaign.deleted {
+        return Ok(());
+    }
+
+    let respondee_id = user_id_to_i64(subscribed.user_id).await;
+
+    if member_ops::has_roster(data, campaign.id)
+        && !member_ops::get_member_ids(data, campaign.id, &[MEMBER_ROLE_PLAYER, MEMBER_ROLE_CO_DM])
+            .contains(&respondee_id)
+    {
+        return Ok(());
+    }
+
+    let already_responded = response_ops::get_responses_for_session(data, session.id)
+        .unwrap_or_default()
+        .iter()
+        .any(|r| r.respondee_id == respondee_id);
+
+    if already_responded {
+        return Ok(());
+    }
+
+    response_ops::create_response(
+        data,
+        NewResponse {
+            session_id: session.id,
+            respondee_id,
+            response: 1,
+            responded_date: chrono::Utc::now().naive_utc(),
+        },
+    );
+
+    provisioning::grant_campaign_role(&ctx.http, &campaign, respondee_id).await;
+
+    Ok(())
+}
+
+async fn create_session_event(
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    campaign: &Campaign,
+    session: &Session,
+) -> Result<i64, Error> {
+    let (start_time, end_time) = get_event_times(session)?;
+
+    let event = guild_id
+        .create_scheduled_event(
+            http,
+            serenity::CreateScheduledEvent::new(
+                serenity::ScheduledEventType::External,
+                &campaign.name,
+                start_time,
+            )
+            .end_time(end_time)
+            .location(session.location.as_deref().unwrap_or("TBD"))
+            .description(get_event_description(campaign, session)),
+        )
+        .await?;
+
+    Ok(scheduled_event_id_to_i64(event.id).await)
+}
+
+async fn edit_session_event(
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    event_id: i64,
+    campaign: &Campaign,
+    session: &Session,
+) -> Result<i64, Error> {
+    let (start_time, end_time) = get_event_times(session)?;
+
+    guild_id
+        .edit_scheduled_event(
+            http,
+            i64_to_scheduled_event_id(event_id).await,
+            serenity::EditScheduledEvent::new()
+                .name(&campaign.name)
+                .start_time(start_time)
+                .end_time(end_time)
+                .location(session.location.as_deref().unwrap_or("TBD"))
+                .description(get_event_description(campaign, session)),
+        )
+        .await?;
+
+    Ok(event_id)
+}
+
+fn get_event_times(session: &Session) -> Result<(serenity::Timestamp, serenity::Timestamp), Error> {
+    let scheduled_date = session
+        .scheduled_date
+        .ok_or("Session has no scheduled date")?
+        .and_utc()
+        .timestamp();
+
+    Ok((
+        serenity::Timestamp::from_unix_timestamp(scheduled_date)?,
+        serenity::Timestamp::from_unix_timestamp(scheduled_date + SESSION_LENGTH_HOURS * 3600)?,
+    ))
+}
+
+fn get_event_description(campaign: &Campaign, session: &Session) -> String {
+    match &campaign.description {
+        Some(description) => format!("{}\n\nSession ID: {}", description, session.id),
+        None => format!("Session ID: {}", session.id),
+    }
+}
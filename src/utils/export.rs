@@ -0,0 +1,408 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The current version of the campaign export format
+///
+/// Bump this whenever a change can't be read by `#[serde(default)]` alone, and add a step to `upgrade`
+pub const EXPORT_VERSION: u64 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct CampaignExport {
+    pub version: u64,
+    pub exported_date: chrono::NaiveDateTime,
+    pub campaign: ExportedCampaign,
+    #[serde(default)]
+    pub members: Vec<ExportedMember>,
+    #[serde(default)]
+    pub characters: Vec<ExportedCharacter>,
+    #[serde(default)]
+    pub sessions: Vec<ExportedSession>,
+    #[serde(default)]
+    pub polls: Vec<ExportedPoll>,
+    #[serde(default)]
+    pub lore: Vec<ExportedLoreEntry>,
+    #[serde(default)]
+    pub npcs: Vec<ExportedNpc>,
+    #[serde(default)]
+    pub quests: Vec<ExportedQuest>,
+    #[serde(default)]
+    pub calendar: Option<ExportedCalendar>,
+    #[serde(default)]
+    pub handouts: Vec<ExportedHandout>,
+    #[serde(default)]
+    pub journal_entries: Vec<ExportedJournalEntry>,
+    #[serde(default)]
+    pub safety: Vec<ExportedSafetyEntry>,
+    #[serde(default)]
+    pub questionnaire: Vec<ExportedQuestion>,
+    #[serde(default)]
+    pub lfg: Option<ExportedLfgPost>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedCampaign {
+    pub name: String,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub dm_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub min_players: Option<i16>,
+    #[serde(default = "default_quorum_deadline_hours")]
+    pub quorum_deadline_hours: i32,
+    #[serde(default)]
+    pub quorum_action: i16,
+    #[serde(default)]
+    pub import_event_interest: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedMember {
+    pub user_id: i64,
+    pub role: i16,
+    pub status: i16,
+    pub added_by: i64,
+    pub joined_date: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedCharacter {
+    pub player_id: i64,
+    pub name: String,
+    pub race: String,
+    pub class: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedSession {
+    pub author_id: i64,
+    pub location: Option<String>,
+    pub status: i16,
+    pub created_date: chrono::NaiveDateTime,
+    pub scheduled_date: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub max_players: Option<i16>,
+    #[serde(default)]
+    pub recap: Option<String>,
+    #[serde(default)]
+    pub dm_notes: Option<String>,
+    #[serde(default)]
+    pub ingame_start_day: Option<i32>,
+    #[serde(default)]
+    pub ingame_end_day: Option<i32>,
+    #[serde(default)]
+    pub responses: Vec<ExportedResponse>,
+    #[serde(default)]
+    pub attendance: Vec<ExportedAttendance>,
+    #[serde(default)]
+    pub voice_checkins: Vec<ExportedVoiceCheckin>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedResponse {
+    pub respondee_id: i64,
+    pub response: i16,
+    pub responded_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub waitlisted: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedAttendance {
+    pub player_id: i64,
+    pub attended: bool,
+    pub marked_by: i64,
+    pub marked_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub late_minutes: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedVoiceCheckin {
+    pub user_id: i64,
+    pub joined_date: chrono::NaiveDateTime,
+    pub left_date: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedPoll {
+    pub author_id: i64,
+    pub location: Option<String>,
+    pub closed: bool,
+    pub created_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub options: Vec<ExportedPollOption>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedPollOption {
+    pub scheduled_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub votes: Vec<ExportedPollVote>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedPollVote {
+    pub voter_id: i64,
+    pub voted_date: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedLoreEntry {
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub dm_only: bool,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+/// An NPC without its portrait, which would make exports too large to import again
+#[derive(Serialize, Deserialize)]
+pub struct ExportedNpc {
+    pub name: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub faction: Option<String>,
+    pub attitude: i16,
+    pub status: i16,
+    pub secrets: Option<String>,
+    #[serde(default)]
+    pub revealed: Vec<String>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedQuest {
+    pub title: String,
+    /// The name of the NPC who gave the quest, since NPC IDs change between servers
+    pub giver: Option<String>,
+    pub description: Option<String>,
+    pub reward: Option<String>,
+    pub status: i16,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub objectives: Vec<ExportedQuestObjective>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedQuestObjective {
+    pub description: String,
+    pub completed: bool,
+    pub created_date: chrono::NaiveDateTime,
+    pub completed_date: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedCalendar {
+    pub name: String,
+    pub month_names: Vec<String>,
+    pub month_lengths: Vec<i32>,
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+    pub current_day: i32,
+    pub updated_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub holidays: Vec<ExportedCalendarDay>,
+    #[serde(default)]
+    pub seasons: Vec<ExportedCalendarDay>,
+    #[serde(default)]
+    pub events: Vec<ExportedCalendarEvent>,
+}
+
+/// A named day of the year, used for both holidays and the starts of seasons
+#[derive(Serialize, Deserialize)]
+pub struct ExportedCalendarDay {
+    pub name: String,
+    pub month: i16,
+    pub day: i16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedCalendarEvent {
+    pub name: String,
+    pub description: Option<String>,
+    pub day: i32,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+/// A handout without its file, which would make exports too large to import again
+///
+/// The file name is kept so the DM knows which files to upload again
+#[derive(Serialize, Deserialize)]
+pub struct ExportedHandout {
+    pub title: String,
+    pub content: Option<String>,
+    pub file_name: Option<String>,
+    pub author_id: i64,
+    pub created_date: chrono::NaiveDateTime,
+    #[serde(default)]
+    pub recipients: Vec<ExportedHandoutRecipient>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedHandoutRecipient {
+    pub user_id: i64,
+    pub revealed_by: i64,
+    pub revealed_date: chrono::NaiveDateTime,
+}
+
+/// A journal entry shared with the party
+///
+/// Private entries stay out of exports, since whoever exports the campaign shouldn't read them
+#[derive(Serialize, Deserialize)]
+pub struct ExportedJournalEntry {
+    pub author_id: i64,
+    /// The position of the entry's session in `sessions`, since session IDs change between servers
+    pub session: Option<usize>,
+    pub title: String,
+    pub content: String,
+    pub created_date: chrono::NaiveDateTime,
+    pub updated_date: chrono::NaiveDateTime,
+}
+
+/// A line or veil, which is anonymous and so has no user to export
+#[derive(Serialize, Deserialize)]
+pub struct ExportedSafetyEntry {
+    pub kind: i16,
+    pub topic: String,
+    pub created_date: chrono::NaiveDate,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedQuestion {
+    pub position: i32,
+    pub prompt: String,
+    pub placeholder: Option<String>,
+    pub paragraph: bool,
+    pub required: bool,
+    pub created_date: chrono::NaiveDateTime,
+    pub answers: Vec<ExportedAnswer>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedAnswer {
+    pub user_id: i64,
+    pub answer: String,
+    pub answered_date: chrono::NaiveDateTime,
+}
+
+/// The looking-for-group post a one-shot was advertised with
+///
+/// Its message stays behind on the old server, so imported posts are closed
+#[derive(Serialize, Deserialize)]
+pub struct ExportedLfgPost {
+    /// The position of the advertised session in `sessions`
+    pub session: usize,
+    pub system: String,
+    pub min_level: i16,
+    pub max_level: i16,
+    pub content_warnings: Option<String>,
+    pub closed: bool,
+    pub created_date: chrono::NaiveDateTime,
+}
+
+fn default_quorum_deadline_hours() -> i32 {
+    48
+}
+
+impl CampaignExport {
+    /// Gets every user referenced anywhere in the export
+    pub fn user_ids(&self) -> BTreeSet<i64> {
+        let mut user_ids = BTreeSet::from([self.campaign.dm_id]);
+
+        for member in &self.members {
+            user_ids.extend([member.user_id, member.added_by]);
+        }
+
+        user_ids.extend(self.characters.iter().map(|c| c.player_id));
+
+        for session in &self.sessions {
+            user_ids.insert(session.author_id);
+            user_ids.extend(session.responses.iter().map(|r| r.respondee_id));
+            user_ids.extend(
+                session
+                    .attendance
+                    .iter()
+                    .flat_map(|a| [a.player_id, a.marked_by]),
+            );
+            user_ids.extend(session.voice_checkins.iter().map(|c| c.user_id));
+        }
+
+        for poll in &self.polls {
+            user_ids.insert(poll.author_id);
+            user_ids.extend(
+                poll.options
+                    .iter()
+                    .flat_map(|o| o.votes.iter().map(|v| v.voter_id)),
+            );
+        }
+
+        user_ids.extend(self.lore.iter().map(|l| l.author_id));
+        user_ids.extend(self.npcs.iter().map(|n| n.author_id));
+        user_ids.extend(self.quests.iter().map(|q| q.author_id));
+
+        if let Some(calendar) = &self.calendar {
+            user_ids.extend(calendar.events.iter().map(|e| e.author_id));
+        }
+
+        user_ids.extend(self.journal_entries.iter().map(|j| j.author_id));
+
+        for handout in &self.handouts {
+            user_ids.insert(handout.author_id);
+            user_ids.extend(
+                handout
+                    .recipients
+                    .iter()
+                    .flat_map(|r| [r.user_id, r.revealed_by]),
+            );
+        }
+
+        for question in &self.questionnaire {
+            user_ids.extend(question.answers.iter().map(|a| a.user_id));
+        }
+
+        user_ids
+    }
+}
+
+pub fn to_json(export: &CampaignExport) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(export)
+}
+
+/// Reads an export, upgrading it first if it was made by an older version of the bot
+pub fn from_json(bytes: &[u8]) -> Result<CampaignExport, String> {
+    let value: serde_json::Value =
+        serde_json::from_slice(bytes).map_err(|e| format!("Not a valid export: {}", e))?;
+
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| String::from("Not a valid export: missing version"))?;
+
+    if version > EXPORT_VERSION {
+        return Err(format!(
+            "This export uses version {} of the format, but only up to version {} is supported.",
+            version, EXPORT_VERSION
+        ));
+    }
+
+    serde_json::from_value(upgrade(value, version))
+        .map_err(|e| format!("Not a valid export: {}", e))
+}
+
+/// Rewrites an export from an older format version into the current one, oldest step first
+fn upgrade(mut value: serde_json::Value, version: u64) -> serde_json::Value {
+    if version < EXPORT_VERSION {
+        value["version"] = serde_json::Value::from(EXPORT_VERSION);
+    }
+
+    value
+}
@@ -0,0 +1,103 @@
+use super::events::SESSION_LENGTH_HOURS;
+use crate::{
+    commands::dnd::campaign::session::{STATUS_CANCELLED, STATUS_COMPLETED, STATUS_CONFIRMED},
+    models::{Campaign, Session},
+};
+
+const ICS_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Builds an iCalendar (RFC 5545) document containing a campaign's sessions
+///
+/// Each session keeps a stable UID so subscribed calendars update events in place
+pub fn build_calendar(campaign: &Campaign, sessions: &[Session]) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//dm-helper//Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&campaign.name)),
+    ];
+
+    let now = chrono::Utc::now().naive_utc().format(ICS_DATE_FORMAT);
+
+    for session in sessions {
+        let scheduled_date = match session.scheduled_date {
+            Some(scheduled_date) => scheduled_date,
+            None => continue,
+        };
+        let end_date = scheduled_date + chrono::Duration::hours(SESSION_LENGTH_HOURS);
+
+        let status = match session.status {
+            STATUS_CONFIRMED | STATUS_COMPLETED => "CONFIRMED",
+            STATUS_CANCELLED => "CANCELLED",
+            _ => "TENTATIVE",
+        };
+
+        let description = match &campaign.description {
+            Some(description) => format!("{}\n\nSession ID: {}", description, session.id),
+            None => format!("Session ID: {}", session.id),
+        };
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:session-{}-campaign-{}@dm-helper",
+            session.id, campaign.id
+        ));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!(
+            "DTSTART:{}",
+            scheduled_date.format(ICS_DATE_FORMAT)
+        ));
+        lines.push(format!("DTEND:{}", end_date.format(ICS_DATE_FORMAT)));
+        lines.push(format!("SUMMARY:{}", escape_text(&campaign.name)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+
+        if let Some(location) = &session.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+
+        if let Some(link) = &campaign.link {
+            lines.push(format!("URL:{}", link));
+        }
+
+        lines.push(format!("STATUS:{}", status));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 octets into continuation lines, as required by RFC 5545
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
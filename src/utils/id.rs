@@ -16,6 +16,10 @@ pub async fn role_id_to_i64(id: serenity::model::id::RoleId) -> i64 {
     id_to_i64(id.get()).await
 }
 
+pub async fn scheduled_event_id_to_i64(id: serenity::model::id::ScheduledEventId) -> i64 {
+    id_to_i64(id.get()).await
+}
+
 pub async fn i64_to_user_id(id: i64) -> serenity::model::id::UserId {
     serenity::model::id::UserId::from(i64_to_u64(id).await)
 }
@@ -32,6 +36,10 @@ pub async fn i64_to_role_id(id: i64) -> serenity::model::id::RoleId {
     serenity::model::id::RoleId::from(i64_to_u64(id).await)
 }
 
+pub async fn i64_to_scheduled_event_id(id: i64) -> serenity::model::id::ScheduledEventId {
+    serenity::model::id::ScheduledEventId::from(i64_to_u64(id).await)
+}
+
 async fn id_to_i64(id: u64) -> i64 {
     i64::try_from(id).expect("Failed to convert ID to i64")
 }
@@ -0,0 +1,32 @@
+use super::id::i64_to_user_id;
+use poise::serenity_prelude as serenity;
+
+/// Sends a direct message to a user, logging rather than failing if it can't be delivered
+pub async fn notify_user(ctx: &serenity::Context, user_id: i64, msg: &str) {
+    let user_id = i64_to_user_id(user_id).await;
+
+    if let Err(e) = user_id
+        .direct_message(ctx, serenity::CreateMessage::new().content(msg))
+        .await
+    {
+        println!("Failed to notify user {}: {:?}", user_id, e);
+    }
+}
+
+pub async fn notify_users(ctx: &serenity::Context, user_ids: &[i64], msg: &str) {
+    for user_id in user_ids {
+        notify_user(ctx, *user_id, msg).await;
+    }
+}
+
+pub async fn notify_promoted(ctx: &serenity::Context, session_id: i32, user_ids: &[i64]) {
+    notify_users(
+        ctx,
+        user_ids,
+        &format!(
+            "A seat opened up in session ID {}. You've been moved off the waitlist and are now going!",
+            session_id
+        ),
+    )
+    .await;
+}
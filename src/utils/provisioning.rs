@@ -0,0 +1,220 @@
+use super::id::{
+    channel_id_to_i64, i64_to_channel_id, i64_to_guild_id, i64_to_role_id, i64_to_user_id,
+    role_id_to_i64,
+};
+use crate::{models::Campaign, Error};
+use poise::serenity_prelude as serenity;
+
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Creates a role plus a category with text and voice channels that only the role can see
+///
+/// The IDs are stored on the returned campaign, and the DM is given the role
+/// The channels are also recorded as provisioned, so only they are ever deleted by the bot
+/// Anything created before a failure is removed again
+pub async fn provision_campaign(
+    http: &serenity::Http,
+    campaign: Campaign,
+    bot_id: serenity::UserId,
+) -> Result<Campaign, Error> {
+    let mut provisioned = campaign;
+
+    match create_resources(http, &mut provisioned, bot_id).await {
+        Ok(()) => Ok(provisioned),
+        Err(e) => {
+            remove_campaign_resources(http, provisioned).await;
+            Err(e)
+        }
+    }
+}
+
+/// Gives a user the campaign's role, if it has one
+pub async fn grant_campaign_role(http: &serenity::Http, campaign: &Campaign, user_id: i64) {
+    let role_id = match campaign.role_id {
+        Some(role_id) => i64_to_role_id(role_id).await,
+        None => return,
+    };
+
+    if let Err(e) = http
+        .add_member_role(
+            i64_to_guild_id(campaign.guild_id).await,
+            i64_to_user_id(user_id).await,
+            role_id,
+            Some("Responded to a campaign session"),
+        )
+        .await
+    {
+        println!(
+            "Failed to grant campaign {}'s role to user {}: {:?}",
+            campaign.id, user_id, e
+        );
+    }
+}
+
+/// Makes a campaign's provisioned channels read-only for its role, or opens them back up
+pub async fn set_campaign_locked(http: &serenity::Http, campaign: &Campaign, locked: bool) {
+    let role_id = match campaign.role_id {
+        Some(role_id) => i64_to_role_id(role_id).await,
+        None => return,
+    };
+
+    for channel_id in [
+        campaign.category_id,
+        campaign.provisioned_channel_id,
+        campaign.provisioned_voice_channel_id,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if let Err(e) = i64_to_channel_id(channel_id)
+            .await
+            .create_permission(http, role_overwrite(role_id, locked))
+            .await
+        {
+            println!(
+                "Failed to update permissions of channel {}: {:?}",
+                channel_id, e
+            );
+        }
+    }
+}
+
+/// Deletes a campaign's provisioned channels, category and role
+///
+/// Channels linked with `/campaign edit` may belong to the server, so they're only unlinked
+/// Returns the campaign with every channel and role ID cleared
+pub async fn remove_campaign_resources(http: &serenity::Http, campaign: Campaign) -> Campaign {
+    for channel_id in [
+        campaign.provisioned_channel_id,
+        campaign.provisioned_voice_channel_id,
+        campaign.category_id,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if let Err(e) = i64_to_channel_id(channel_id).await.delete(http).await {
+            println!("Failed to delete channel {}: {:?}", channel_id, e);
+        }
+    }
+
+    if let Some(role_id) = campaign.role_id {
+        if let Err(e) = i64_to_guild_id(campaign.guild_id)
+            .await
+            .delete_role(http, i64_to_role_id(role_id).await)
+            .await
+        {
+            println!("Failed to delete role {}: {:?}", role_id, e);
+        }
+    }
+
+    Campaign {
+        channel_id: None,
+        voice_channel_id: None,
+        role_id: None,
+        category_id: None,
+        provisioned_channel_id: None,
+        provisioned_voice_channel_id: None,
+        ..campaign
+    }
+}
+
+async fn create_resources(
+    http: &serenity::Http,
+    campaign: &mut Campaign,
+    bot_id: serenity::UserId,
+) -> Result<(), Error> {
+    let guild_id = i64_to_guild_id(campaign.guild_id).await;
+    let name = campaign
+        .name
+        .chars()
+        .take(MAX_NAME_LENGTH)
+        .collect::<String>();
+
+    let role = guild_id
+        .create_role(
+            http,
+            serenity::EditRole::new().name(&name).mentionable(true),
+        )
+        .await?;
+    campaign.role_id = Some(role_id_to_i64(role.id).await);
+
+    let overwrites = vec![
+        serenity::PermissionOverwrite {
+            allow: serenity::Permissions::empty(),
+            deny: serenity::Permissions::VIEW_CHANNEL,
+            kind: serenity::PermissionOverwriteType::Role(guild_id.everyone_role()),
+        },
+        role_overwrite(role.id, false),
+        serenity::PermissionOverwrite {
+            allow: serenity::Permissions::VIEW_CHANNEL
+                | serenity::Permissions::SEND_MESSAGES
+                | serenity::Permissions::READ_MESSAGE_HISTORY
+                | serenity::Permissions::EMBED_LINKS
+                | serenity::Permissions::CREATE_PUBLIC_THREADS
+                | serenity::Permissions::SEND_MESSAGES_IN_THREADS,
+            deny: serenity::Permissions::empty(),
+            kind: serenity::PermissionOverwriteType::Member(bot_id),
+        },
+    ];
+
+    let category = guild_id
+        .create_channel(
+            http,
+            serenity::CreateChannel::new(&name)
+                .kind(serenity::ChannelType::Category)
+                .permissions(overwrites.clone()),
+        )
+        .await?;
+    campaign.category_id = Some(channel_id_to_i64(category.id).await);
+
+    let channel = guild_id
+        .create_channel(
+            http,
+            serenity::CreateChannel::new(&name)
+                .kind(serenity::ChannelType::Text)
+                .category(category.id)
+                .permissions(overwrites.clone()),
+        )
+        .await?;
+    campaign.channel_id = Some(channel_id_to_i64(channel.id).await);
+    campaign.provisioned_channel_id = campaign.channel_id;
+
+    let voice_channel = guild_id
+        .create_channel(
+            http,
+            serenity::CreateChannel::new(&name)
+                .kind(serenity::ChannelType::Voice)
+                .category(category.id)
+                .permissions(overwrites),
+        )
+        .await?;
+    campaign.voice_channel_id = Some(channel_id_to_i64(voice_channel.id).await);
+    campaign.provisioned_voice_channel_id = campaign.voice_channel_id;
+
+    http.add_member_role(
+        guild_id,
+        i64_to_user_id(campaign.dm_id).await,
+        role.id,
+        Some("DM of the campaign"),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn role_overwrite(role_id: serenity::RoleId, locked: bool) -> serenity::PermissionOverwrite {
+    let talk = serenity::Permissions::SEND_MESSAGES
+        | serenity::Permissions::SEND_MESSAGES_IN_THREADS
+        | serenity::Permissions::CONNECT;
+    let read = serenity::Permissions::VIEW_CHANNEL | serenity::Permissions::READ_MESSAGE_HISTORY;
+
+    serenity::PermissionOverwrite {
+        allow: if locked { read } else { read | talk },
+        deny: if locked {
+            talk
+        } else {
+            serenity::Permissions::empty()
+        },
+        kind: serenity::PermissionOverwriteType::Role(role_id),
+    }
+}
@@ -0,0 +1,104 @@
+use super::date::get_long_date_week_day_timestamp;
+use super::id::{channel_id_to_i64, i64_to_channel_id};
+use crate::models::{Campaign, Session};
+use poise::serenity_prelude as serenity;
+
+const MAX_THREAD_NAME_LENGTH: usize = 100;
+
+/// Creates a discussion thread for a session in its campaign's channel
+///
+/// Returns the ID of the thread, or `None` if the campaign has no channel or it failed
+pub async fn create_session_thread(
+    http: &serenity::Http,
+    campaign: &Campaign,
+    session: &Session,
+) -> Option<i64> {
+    let channel_id = i64_to_channel_id(campaign.channel_id?).await;
+    let scheduled_date = session.scheduled_date?.format("%Y-%m-%d %H:%M").to_string();
+
+    let name = format!("{} {}", campaign.name, &scheduled_date[..10])
+        .chars()
+        .take(MAX_THREAD_NAME_LENGTH)
+        .collect::<String>();
+
+    let thread = match channel_id
+        .create_thread(
+            http,
+            serenity::CreateThread::new(name)
+                .kind(serenity::ChannelType::PublicThread)
+                .auto_archive_duration(serenity::AutoArchiveDuration::OneWeek),
+        )
+        .await
+    {
+        Ok(thread) => thread,
+        Err(e) => {
+            println!(
+                "Failed to create thread for session {}: {:?}",
+                session.id, e
+            );
+            return None;
+        }
+    };
+
+    let thread_id = channel_id_to_i64(thread.id).await;
+
+    post_to_thread(
+        http,
+        thread_id,
+        &format!(
+            "Discussion for session ID {} on {} at {}.",
+            session.id,
+            get_long_date_week_day_timestamp(&scheduled_date).unwrap_or(scheduled_date),
+            session.location.as_deref().unwrap_or("TBD")
+        ),
+    )
+    .await;
+
+    Some(thread_id)
+}
+
+/// Posts a message in a session's thread without pinging anyone mentioned in it
+pub async fn post_to_session_thread(http: &serenity::Http, session: &Session, msg: &str) {
+    if let Some(thread_id) = session.thread_id {
+        post_to_thread(http, thread_id, msg).await;
+    }
+}
+
+pub async fn archive_session_thread(http: &serenity::Http, session: &Session) {
+    let thread_id = match session.thread_id {
+        Some(thread_id) => i64_to_channel_id(thread_id).await,
+        None => return,
+    };
+
+    if let Err(e) = thread_id
+        .edit_thread(http, serenity::EditThread::new().archived(true))
+        .await
+    {
+        println!(
+            "Failed to archive thread of session {}: {:?}",
+            session.id, e
+        );
+    }
+}
+
+/// Posts a message in a campaign's channel, if it has one
+pub async fn post_to_campaign_channel(http: &serenity::Http, campaign: &Campaign, msg: &str) {
+    if let Some(channel_id) = campaign.channel_id {
+        post_to_thread(http, channel_id, msg).await;
+    }
+}
+
+async fn post_to_thread(http: &serenity::Http, thread_id: i64, msg: &str) {
+    if let Err(e) = i64_to_channel_id(thread_id)
+        .await
+        .send_message(
+            http,
+            serenity::CreateMessage::new()
+                .content(msg)
+                .allowed_mentions(serenity::CreateAllowedMentions::new()),
+        )
+        .await
+    {
+        println!("Failed to post in thread {}: {:?}", thread_id, e);
+    }
+}
@@ -1,7 +1,8 @@
 use super::guilds::get_guild_id;
-use super::id::{guild_id_to_i64, i64_to_role_id};
-use crate::ops::settings_ops::get_settings;
+use super::id::{guild_id_to_i64, i64_to_role_id, user_id_to_i64};
+use crate::ops::{settings_ops::get_settings, user_settings_ops::get_user_settings};
 use crate::{Context, Error};
+use chrono_tz::Tz;
 
 pub async fn has_dnd_role(ctx: Context<'_>) -> Result<bool, Error> {
     let guild_id = guild_id_to_i64(get_guild_id(ctx).await).await;
@@ -42,3 +43,11 @@ pub async fn has_dm_role(ctx: Context<'_>) -> Result<bool, Error> {
         None => Ok(false),
     }
 }
+
+pub async fn get_timezone(ctx: Context<'_>) -> Tz {
+    let user_id = user_id_to_i64(ctx.author().id).await;
+
+    get_user_settings(ctx, user_id)
+        .and_then(|settings| settings.timezone.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC)
+}
@@ -0,0 +1,92 @@
+use super::id::{channel_id_to_i64, guild_id_to_i64, user_id_to_i64};
+use crate::{
+    models::{NewAttendance, NewVoiceCheckin, Session},
+    ops::{attendance_ops, session_ops, voice_checkin_ops},
+    Data, Error,
+};
+use poise::serenity_prelude as serenity;
+
+/// Players joining within this many minutes of the start time aren't counted as late
+pub const LATE_GRACE_MINUTES: i64 = 5;
+
+/// Checks players in and out of sessions as they join and leave a campaign's voice channel
+pub async fn handle_voice_state(
+    ctx: &serenity::Context,
+    data: &Data,
+    voice_state: &serenity::VoiceState,
+) -> Result<(), Error> {
+    let guild_id = match voice_state.guild_id {
+        Some(guild_id) => guild_id_to_i64(guild_id).await,
+        None => return Ok(()),
+    };
+
+    if voice_state
+        .member
+        .as_ref()
+        .is_some_and(|member| member.user.bot)
+    {
+        return Ok(());
+    }
+
+    let user_id = user_id_to_i64(voice_state.user_id).await;
+    let now = chrono::Utc::now().naive_utc();
+
+    let session = match voice_state.channel_id {
+        Some(channel_id) => {
+            session_ops::get_voice_session(data, guild_id, channel_id_to_i64(channel_id).await, now)
+        }
+        None => None,
+    };
+
+    voice_checkin_ops::end_checkins(data, guild_id, user_id, session.as_ref().map(|s| s.id), now);
+
+    if let Some(session) = session {
+        check_in(ctx, data, &session, user_id, now).await;
+    }
+
+    Ok(())
+}
+
+/// Checks a player into a session, marking them as attended the first time they join
+///
+/// Joining after the grace period records how many minutes late they were
+pub async fn check_in(
+    ctx: &serenity::Context,
+    data: &Data,
+    session: &Session,
+    user_id: i64,
+    now: chrono::NaiveDateTime,
+) {
+    let checked_in = voice_checkin_ops::create_checkin(
+        data,
+        NewVoiceCheckin {
+            session_id: session.id,
+            user_id,
+            joined_date: now,
+        },
+    );
+
+    if !checked_in {
+        return;
+    }
+
+    let late_minutes = session
+        .scheduled_date
+        .map(|scheduled_date| (now - scheduled_date).num_minutes())
+        .filter(|minutes| *minutes > LATE_GRACE_MINUTES)
+        .and_then(|minutes| i32::try_from(minutes).ok());
+
+    let bot_id = ctx.cache.current_user().id;
+
+    attendance_ops::record_checkin_attendance(
+        data,
+        NewAttendance {
+            session_id: session.id,
+            player_id: user_id,
+            attended: true,
+            marked_by: user_id_to_i64(bot_id).await,
+            marked_date: now,
+            late_minutes,
+        },
+    );
+}
@@ -0,0 +1,39 @@
+use crate::{
+    ops::{campaign_ops, session_ops},
+    utils::ics,
+    Data,
+};
+use shuttle_axum::axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+
+const FEED_HISTORY_DAYS: i64 = 90;
+
+pub fn router(data: Data) -> Router {
+    Router::new()
+        .route("/calendars/{file}", get(calendar_feed))
+        .with_state(data)
+}
+
+/// Serves a campaign's sessions as a subscribable iCalendar feed
+async fn calendar_feed(State(data): State<Data>, Path(file): Path<String>) -> Response {
+    let token = file.strip_suffix(".ics").unwrap_or(&file);
+
+    let campaign = match campaign_ops::get_campaign_by_calendar_token(&data, token) {
+        Some(campaign) => campaign,
+        None => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let since = chrono::Utc::now().naive_utc() - chrono::Duration::days(FEED_HISTORY_DAYS);
+    let sessions = session_ops::get_sessions_since(&data, campaign.id, since).unwrap_or_default();
+
+    (
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics::build_calendar(&campaign, &sessions),
+    )
+        .into_response()
+}